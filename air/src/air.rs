@@ -1,14 +1,36 @@
+use crate::Interaction;
+use alloc::vec::Vec;
 use core::ops::{Add, Mul, Sub};
 use p3_field::{AbstractExtensionField, AbstractField, AbstractionOf, ExtensionField, Field};
 use p3_matrix::dense::RowMajorMatrix;
 use p3_matrix::MatrixRows;
 
 pub trait Air<AB: AirBuilder>: Sync {
+    /// The number of columns in the main trace.
+    fn width(&self) -> usize;
+
     fn eval(&self, builder: &mut AB);
 
+    /// The number of consecutive rows each evaluation of the constraints can see, starting with
+    /// the current row. Builders' `main` and `preprocessed` matrices have this many rows. It must
+    /// be at least two.
+    fn window_size(&self) -> usize {
+        2
+    }
+
     fn preprocessed_trace(&self) -> Option<RowMajorMatrix<AB::F>> {
         None
     }
+
+    /// Tuples this AIR sends over its buses; see `Interaction`.
+    fn sends(&self) -> Vec<Interaction<AB::F>> {
+        Vec::new()
+    }
+
+    /// Tuples this AIR receives over its buses; see `Interaction`.
+    fn receives(&self) -> Vec<Interaction<AB::F>> {
+        Vec::new()
+    }
 }
 
 pub trait AirBuilder: Sized {
@@ -128,6 +150,12 @@ pub trait AirBuilder: Sized {
     }
 }
 
+pub trait AirBuilderWithPublicValues: AirBuilder {
+    /// Values supplied by the verifier, which constraints can refer to, e.g. to bind a trace's
+    /// inputs or outputs.
+    fn public_values(&self) -> &[Self::F];
+}
+
 pub trait PairBuilder: AirBuilder {
     fn preprocessed(&self) -> Self::M;
 }
@@ -180,6 +208,12 @@ impl<'a, AB: PermutationAirBuilder> PermutationAirBuilder for FilteredAirBuilder
     }
 }
 
+impl<'a, AB: AirBuilderWithPublicValues> AirBuilderWithPublicValues for FilteredAirBuilder<'a, AB> {
+    fn public_values(&self) -> &[Self::F] {
+        self.inner.public_values()
+    }
+}
+
 impl<'a, AB: AirBuilder> AirBuilder for FilteredAirBuilder<'a, AB> {
     type F = AB::F;
     type Expr = AB::Expr;
@@ -215,6 +249,10 @@ mod tests {
     struct FibonacciAir;
 
     impl<AB: AirBuilder> Air<AB> for FibonacciAir {
+        fn width(&self) -> usize {
+            1
+        }
+
         fn eval(&self, builder: &mut AB) {
             let main = builder.main();
 
@@ -224,7 +262,11 @@ mod tests {
 
             builder.when_first_row().assert_zero(x_0);
             builder.when_first_row().assert_one(x_1);
-            builder.when_transition().assert_eq(x_0 + x_1, x_2);
+            builder.when_transition_window(3).assert_eq(x_0 + x_1, x_2);
+        }
+
+        fn window_size(&self) -> usize {
+            3
         }
     }
 }
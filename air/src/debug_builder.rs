@@ -0,0 +1,264 @@
+use crate::{Air, AirBuilder, AirBuilderWithPublicValues, PairBuilder, WindowMatrixView};
+use alloc::vec::Vec;
+use p3_field::Field;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::{Matrix, MatrixRows};
+
+/// Check that a trace satisfies an AIR's constraints, by evaluating them on each row in turn.
+///
+/// This is much cheaper than proving, and unlike a failed proof, it points to the first row and
+/// constraint which don't hold. The AIR's preprocessed trace, if any, is checked alongside `main`.
+/// Interactions aren't checked, as they only balance across a whole trace, or several.
+pub fn check_constraints<F, A>(
+    air: &A,
+    main: &RowMajorMatrix<F>,
+    public_values: &[F],
+) -> Result<(), ConstraintFailure<F>>
+where
+    F: Field,
+    A: for<'a> Air<DebugConstraintBuilder<'a, F>>,
+{
+    assert_eq!(
+        main.width(),
+        air.width(),
+        "the trace must have the AIR's width"
+    );
+    let height = main.height();
+    let preprocessed = air.preprocessed_trace();
+    if let Some(preprocessed) = &preprocessed {
+        assert_eq!(
+            preprocessed.height(),
+            height,
+            "the trace and preprocessed trace must have the same height"
+        );
+    }
+
+    let window_size = air.window_size();
+    for row in 0..height {
+        // The trace domain is cyclic, so the window wraps around past the last row.
+        let window = |matrix: &RowMajorMatrix<F>| -> Vec<F> {
+            (row..row + window_size)
+                .flat_map(|r| matrix.row(r % height).iter().copied())
+                .collect()
+        };
+        let preprocessed_window = preprocessed.as_ref().map(window).unwrap_or_default();
+        let main_window = window(main);
+        let mut builder = DebugConstraintBuilder {
+            row,
+            height,
+            preprocessed: WindowMatrixView::new(&preprocessed_window, window_size),
+            main: WindowMatrixView::new(&main_window, window_size),
+            public_values,
+            constraint_index: 0,
+            failure: None,
+        };
+        air.eval(&mut builder);
+        if let Some(failure) = builder.failure {
+            return Err(failure);
+        }
+    }
+    Ok(())
+}
+
+/// One if `condition` holds, otherwise zero.
+fn indicator<F: Field>(condition: bool) -> F {
+    if condition {
+        F::ONE
+    } else {
+        F::ZERO
+    }
+}
+
+/// A constraint which didn't hold on some row of a trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstraintFailure<F> {
+    /// The index of the row the constraint was evaluated on.
+    pub row: usize,
+    /// The index of the constraint, in the order the AIR asserts them.
+    pub constraint: usize,
+    /// The constraint's nonzero value.
+    pub value: F,
+    /// The main trace's values on this row and those after it, as far as the AIR's window reaches.
+    pub window: Vec<Vec<F>>,
+}
+
+/// An `AirBuilder` which evaluates constraints on one concrete row of a trace, recording the first
+/// which doesn't hold. See `check_constraints`.
+pub struct DebugConstraintBuilder<'a, F: Field> {
+    row: usize,
+    height: usize,
+    preprocessed: WindowMatrixView<'a, F>,
+    main: WindowMatrixView<'a, F>,
+    public_values: &'a [F],
+    constraint_index: usize,
+    failure: Option<ConstraintFailure<F>>,
+}
+
+impl<'a, F: Field> AirBuilder for DebugConstraintBuilder<'a, F> {
+    type F = F;
+    type Expr = F;
+    type Var = F;
+    type M = WindowMatrixView<'a, F>;
+
+    fn main(&self) -> Self::M {
+        self.main
+    }
+
+    fn is_first_row(&self) -> Self::Expr {
+        indicator(self.row == 0)
+    }
+
+    fn is_last_row(&self) -> Self::Expr {
+        indicator(self.row == self.height - 1)
+    }
+
+    fn is_transition_window(&self, size: usize) -> Self::Expr {
+        indicator(self.row + size <= self.height)
+    }
+
+    fn assert_zero<I: Into<Self::Expr>>(&mut self, x: I) {
+        let value = x.into();
+        if value != F::ZERO && self.failure.is_none() {
+            self.failure = Some(ConstraintFailure {
+                row: self.row,
+                constraint: self.constraint_index,
+                value,
+                window: (0..self.main.height())
+                    .map(|r| self.main.row(r).to_vec())
+                    .collect(),
+            });
+        }
+        self.constraint_index += 1;
+    }
+}
+
+impl<'a, F: Field> AirBuilderWithPublicValues for DebugConstraintBuilder<'a, F> {
+    fn public_values(&self) -> &[Self::F] {
+        self.public_values
+    }
+}
+
+impl<'a, F: Field> PairBuilder for DebugConstraintBuilder<'a, F> {
+    fn preprocessed(&self) -> Self::M {
+        self.preprocessed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{check_constraints, Air, AirBuilder, ConstraintFailure};
+    use alloc::vec;
+    use p3_field::AbstractField;
+    use p3_matrix::dense::RowMajorMatrix;
+    use p3_matrix::MatrixRows;
+    use p3_mersenne_31::Mersenne31;
+
+    type F = Mersenne31;
+
+    /// A Fibonacci sequence starting from 0, 1, with the pairs `(x_i, x_{i+1})` as rows.
+    struct FibonacciAir;
+
+    impl<AB: AirBuilder> Air<AB> for FibonacciAir {
+        fn width(&self) -> usize {
+            2
+        }
+
+        fn eval(&self, builder: &mut AB) {
+            let main = builder.main();
+            let (local, next) = (main.row(0), main.row(1));
+
+            let mut when_first_row = builder.when_first_row();
+            when_first_row.assert_zero(local[0]);
+            when_first_row.assert_one(local[1]);
+
+            let mut when_transition = builder.when_transition();
+            when_transition.assert_eq(next[0], local[1]);
+            when_transition.assert_eq(next[1], local[0] + local[1]);
+        }
+    }
+
+    fn trace(values: [u32; 5]) -> RowMajorMatrix<F> {
+        let values = values
+            .windows(2)
+            .flatten()
+            .map(|&v| F::from_canonical_u32(v))
+            .collect();
+        RowMajorMatrix::new(values, 2)
+    }
+
+    /// The same sequence in a single column, which needs a window of three rows.
+    struct WindowedFibonacciAir;
+
+    impl<AB: AirBuilder> Air<AB> for WindowedFibonacciAir {
+        fn width(&self) -> usize {
+            1
+        }
+
+        fn eval(&self, builder: &mut AB) {
+            let main = builder.main();
+            let (x_0, x_1, x_2) = (main.row(0)[0], main.row(1)[0], main.row(2)[0]);
+
+            let mut when_first_row = builder.when_first_row();
+            when_first_row.assert_zero(x_0);
+            when_first_row.assert_one(x_1);
+
+            builder.when_transition_window(3).assert_eq(x_2, x_0 + x_1);
+        }
+
+        fn window_size(&self) -> usize {
+            3
+        }
+    }
+
+    #[test]
+    fn valid_trace() {
+        assert_eq!(
+            check_constraints(&FibonacciAir, &trace([0, 1, 1, 2, 3]), &[]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn invalid_transition() {
+        let failure = check_constraints(&FibonacciAir, &trace([0, 1, 1, 3, 4]), &[]);
+        let [one, three] = [1, 3].map(F::from_canonical_u32);
+        assert_eq!(
+            failure,
+            Err(ConstraintFailure {
+                row: 1,
+                constraint: 3,
+                value: F::ONE,
+                window: vec![vec![one, one], vec![one, three]],
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_first_row() {
+        let failure = check_constraints(&FibonacciAir, &trace([1, 1, 2, 3, 5]), &[]).unwrap_err();
+        assert_eq!((failure.row, failure.constraint), (0, 0));
+    }
+
+    #[test]
+    fn windowed() {
+        let column =
+            |values: [u32; 8]| RowMajorMatrix::new(values.map(F::from_canonical_u32).to_vec(), 1);
+        assert_eq!(
+            check_constraints(
+                &WindowedFibonacciAir,
+                &column([0, 1, 1, 2, 3, 5, 8, 13]),
+                &[]
+            ),
+            Ok(())
+        );
+
+        let failure = check_constraints(
+            &WindowedFibonacciAir,
+            &column([0, 1, 1, 2, 3, 5, 8, 14]),
+            &[],
+        )
+        .unwrap_err();
+        assert_eq!((failure.row, failure.constraint), (5, 2));
+        assert_eq!(failure.window.len(), 3);
+    }
+}
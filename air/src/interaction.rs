@@ -0,0 +1,38 @@
+use crate::VirtualPairCol;
+use alloc::vec::Vec;
+use p3_field::Field;
+
+/// A tuple of values which an AIR sends or receives over a bus, in every row, with some
+/// multiplicity. A LogUp argument checks that, over each bus, every tuple is sent exactly as many
+/// times as it is received.
+#[derive(Clone, Debug)]
+pub struct Interaction<F: Field> {
+    pub fields: Vec<VirtualPairCol<F>>,
+    /// The number of times the tuple is sent or received in a given row. Rows with a count of zero
+    /// don't take part in the interaction.
+    pub count: VirtualPairCol<F>,
+    /// The bus over which the tuple is sent or received. Tuples on different buses never match.
+    pub argument_index: usize,
+}
+
+impl<F: Field> Interaction<F> {
+    #[must_use]
+    pub fn new(
+        fields: Vec<VirtualPairCol<F>>,
+        count: VirtualPairCol<F>,
+        argument_index: usize,
+    ) -> Self {
+        Self {
+            fields,
+            count,
+            argument_index,
+        }
+    }
+}
+
+/// Whether an AIR sends or receives the tuples of an `Interaction`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InteractionType {
+    Send,
+    Receive,
+}
@@ -5,9 +5,17 @@
 extern crate alloc;
 
 mod air;
+mod debug_builder;
+mod interaction;
+mod logup;
 mod two_row_matrix;
 mod virtual_column;
+mod window_matrix;
 
 pub use air::*;
+pub use debug_builder::*;
+pub use interaction::*;
+pub use logup::*;
 pub use two_row_matrix::*;
 pub use virtual_column::*;
+pub use window_matrix::*;
@@ -0,0 +1,251 @@
+//! A LogUp argument, which checks that the tuples sent over each bus match the tuples received.
+//!
+//! The permutation trace has one column per interaction, holding `±count / (alpha - fingerprint)`
+//! for each row, where sends count positively and receives negatively. A final column holds the
+//! running sum of all the others, so its value in the last row, the cumulative sum, is zero when
+//! the sends and receives balance.
+
+use crate::{AirBuilder, Interaction, InteractionType, PairBuilder, PermutationAirBuilder};
+use alloc::vec::Vec;
+use core::iter;
+use p3_field::{
+    batch_multiplicative_inverse, AbstractExtensionField, AbstractField, AbstractionOf,
+    ExtensionField, Field,
+};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::{Matrix, MatrixRows};
+
+/// The number of random elements the LogUp argument draws: `alpha`, which is shifted by each
+/// fingerprint, and `beta`, which is used to reduce each tuple to its fingerprint.
+pub const NUM_PERMUTATION_CHALLENGES: usize = 2;
+
+/// The width of the permutation trace for the given interactions, in extension field elements.
+pub fn permutation_trace_width<F: Field>(
+    sends: &[Interaction<F>],
+    receives: &[Interaction<F>],
+) -> usize {
+    sends.len() + receives.len() + 1
+}
+
+/// Generate the permutation trace for the given interactions, from the preprocessed and main
+/// traces of an AIR.
+pub fn generate_permutation_trace<F, EF>(
+    sends: &[Interaction<F>],
+    receives: &[Interaction<F>],
+    preprocessed: Option<&RowMajorMatrix<F>>,
+    main: &RowMajorMatrix<F>,
+    random_elements: &[EF],
+) -> RowMajorMatrix<EF>
+where
+    F: Field,
+    EF: ExtensionField<F>,
+{
+    let interactions = all_interactions(sends, receives).collect::<Vec<_>>();
+    assert!(
+        !interactions.is_empty(),
+        "no interactions to generate a trace for"
+    );
+    let [alpha, beta]: [EF; NUM_PERMUTATION_CHALLENGES] = random_elements
+        .try_into()
+        .expect("wrong number of permutation challenges");
+
+    let height = main.height();
+    let mut counts = Vec::with_capacity(height * interactions.len());
+    let mut denominators = Vec::with_capacity(height * interactions.len());
+    for r in 0..height {
+        let preprocessed_row = preprocessed.map_or(&[][..], |p| p.row(r));
+        let main_row = main.row(r);
+        for (interaction, interaction_type) in &interactions {
+            let fields = interaction
+                .fields
+                .iter()
+                .map(|f| f.apply::<F, F>(preprocessed_row, main_row));
+            denominators.push(reduce_fingerprint::<F, EF, EF>(
+                alpha,
+                beta,
+                interaction.argument_index,
+                fields,
+            ));
+            let count: F = interaction.count.apply(preprocessed_row, main_row);
+            counts.push(signed_count(count, *interaction_type));
+        }
+    }
+    let denominator_invs = batch_multiplicative_inverse(&denominators);
+
+    let width = interactions.len() + 1;
+    let mut values = Vec::with_capacity(height * width);
+    let mut running_sum = EF::ZERO;
+    for (row_counts, row_invs) in counts
+        .chunks(interactions.len())
+        .zip(denominator_invs.chunks(interactions.len()))
+    {
+        for (&count, &inv) in row_counts.iter().zip(row_invs) {
+            let term = inv * count;
+            values.push(term);
+            running_sum += term;
+        }
+        values.push(running_sum);
+    }
+    RowMajorMatrix::new(values, width)
+}
+
+/// The cumulative sum of a permutation trace, i.e. the running sum in its last row.
+pub fn cumulative_sum<EF: Field>(permutation_trace: &RowMajorMatrix<EF>) -> EF {
+    *permutation_trace
+        .values
+        .last()
+        .expect("empty permutation trace")
+}
+
+/// Evaluate the constraints which check that the permutation trace is well-formed, and that its
+/// cumulative sum is `cumulative_sum`. These have degree 2, since the fields and counts of an
+/// interaction are affine in the trace.
+pub fn eval_permutation_constraints<AB>(
+    sends: &[Interaction<AB::F>],
+    receives: &[Interaction<AB::F>],
+    builder: &mut AB,
+    cumulative_sum: AB::EF,
+) where
+    AB: PermutationAirBuilder + PairBuilder,
+{
+    let random_elements = builder.permutation_randomness();
+    let (alpha, beta) = (random_elements[0], random_elements[1]);
+
+    let preprocessed = builder.preprocessed();
+    let main = builder.main();
+    let permutation = builder.permutation();
+    let (preprocessed_local, main_local) = (preprocessed.row(0), main.row(0));
+    let (permutation_local, permutation_next) = (permutation.row(0), permutation.row(1));
+
+    let interactions = all_interactions(sends, receives).collect::<Vec<_>>();
+    let mut sum_local = AB::ExprEF::ZERO;
+    let mut sum_next = AB::ExprEF::ZERO;
+    for (i, (interaction, interaction_type)) in interactions.iter().enumerate() {
+        let fields = interaction
+            .fields
+            .iter()
+            .map(|f| f.apply::<AB::Expr, AB::Var>(preprocessed_local, main_local));
+        let denominator: AB::ExprEF =
+            reduce_fingerprint(alpha, beta, interaction.argument_index, fields);
+        let count = interaction
+            .count
+            .apply::<AB::Expr, AB::Var>(preprocessed_local, main_local);
+
+        let term_local: AB::ExprEF = permutation_local[i].into();
+        builder.assert_eq_ext::<AB::ExprEF, _, _>(
+            term_local.clone() * denominator,
+            AB::ExprEF::from_base(signed_count(count, *interaction_type)),
+        );
+        sum_local += term_local;
+        sum_next += permutation_next[i].into();
+    }
+
+    let running_sum_local: AB::ExprEF = permutation_local[interactions.len()].into();
+    let running_sum_next: AB::ExprEF = permutation_next[interactions.len()].into();
+    builder
+        .when_first_row()
+        .assert_eq_ext::<AB::ExprEF, _, _>(running_sum_local.clone(), sum_local);
+    builder
+        .when_transition()
+        .assert_eq_ext::<AB::ExprEF, _, _>(running_sum_next, running_sum_local.clone() + sum_next);
+    builder
+        .when_last_row()
+        .assert_eq_ext::<AB::ExprEF, _, _>(running_sum_local, AB::ExprEF::from(cumulative_sum));
+}
+
+fn all_interactions<'a, F: Field>(
+    sends: &'a [Interaction<F>],
+    receives: &'a [Interaction<F>],
+) -> impl Iterator<Item = (&'a Interaction<F>, InteractionType)> {
+    let sends = sends.iter().map(|i| (i, InteractionType::Send));
+    let receives = receives.iter().map(|i| (i, InteractionType::Receive));
+    sends.chain(receives)
+}
+
+fn signed_count<Expr: AbstractField>(count: Expr, interaction_type: InteractionType) -> Expr {
+    match interaction_type {
+        InteractionType::Send => count,
+        InteractionType::Receive => -count,
+    }
+}
+
+/// Returns `alpha - sum_j beta^j t_j`, where `t` is the bus index followed by `fields`.
+fn reduce_fingerprint<Expr, EF, ExprEF>(
+    alpha: EF,
+    beta: EF,
+    argument_index: usize,
+    fields: impl Iterator<Item = Expr>,
+) -> ExprEF
+where
+    Expr: AbstractField,
+    EF: Field,
+    ExprEF: AbstractExtensionField<Expr> + AbstractionOf<EF>,
+{
+    let tuple = iter::once(Expr::from_canonical_usize(argument_index)).chain(fields);
+    let mut result = ExprEF::from(alpha);
+    for (t, beta_pow) in tuple.zip(beta.powers()) {
+        result -= ExprEF::from_base(t) * beta_pow;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cumulative_sum, generate_permutation_trace, Interaction, VirtualPairCol};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use p3_field::AbstractField;
+    use p3_matrix::dense::RowMajorMatrix;
+    use p3_matrix::Matrix;
+    use p3_mersenne_31::Mersenne31;
+
+    type F = Mersenne31;
+
+    /// Sends column 0, and receives column 1.
+    fn interactions() -> (Vec<Interaction<F>>, Vec<Interaction<F>>) {
+        let send = Interaction::new(
+            vec![VirtualPairCol::single_main(0)],
+            VirtualPairCol::one(),
+            0,
+        );
+        let receive = Interaction::new(
+            vec![VirtualPairCol::single_main(1)],
+            VirtualPairCol::one(),
+            0,
+        );
+        (vec![send], vec![receive])
+    }
+
+    fn trace(columns: [[u32; 4]; 2]) -> RowMajorMatrix<F> {
+        let values = (0..4)
+            .flat_map(|r| columns.map(|c| F::from_canonical_u32(c[r])))
+            .collect();
+        RowMajorMatrix::new(values, 2)
+    }
+
+    fn random_elements() -> [F; 2] {
+        [
+            F::from_canonical_u32(1234567),
+            F::from_canonical_u32(7654321),
+        ]
+    }
+
+    #[test]
+    fn balanced() {
+        let (sends, receives) = interactions();
+        let main = trace([[1, 2, 3, 2], [2, 3, 2, 1]]);
+        let permutation =
+            generate_permutation_trace(&sends, &receives, None, &main, &random_elements());
+        assert_eq!(permutation.width(), 3);
+        assert_eq!(cumulative_sum(&permutation), F::ZERO);
+    }
+
+    #[test]
+    fn unbalanced() {
+        let (sends, receives) = interactions();
+        let main = trace([[1, 2, 3, 2], [2, 3, 3, 1]]);
+        let permutation =
+            generate_permutation_trace(&sends, &receives, None, &main, &random_elements());
+        assert_ne!(cumulative_sum(&permutation), F::ZERO);
+    }
+}
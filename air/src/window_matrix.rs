@@ -0,0 +1,39 @@
+use p3_matrix::{Matrix, MatrixRows};
+
+/// A window of consecutive rows of a trace, stored contiguously in row-major order. Row 0 is the
+/// current row, row 1 the next, and so on.
+#[derive(Copy, Clone)]
+pub struct WindowMatrixView<'a, T> {
+    values: &'a [T],
+    /// We store the height rather than the width, since the width may be zero, as with an absent
+    /// preprocessed trace.
+    height: usize,
+}
+
+impl<'a, T> WindowMatrixView<'a, T> {
+    pub fn new(values: &'a [T], height: usize) -> Self {
+        assert!(height > 0, "a window must have at least one row");
+        assert_eq!(values.len() % height, 0, "ragged window");
+        Self { values, height }
+    }
+}
+
+impl<'a, T> Matrix<T> for WindowMatrixView<'a, T> {
+    fn width(&self) -> usize {
+        self.values.len() / self.height
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<'a, T: 'a> MatrixRows<'a, T> for WindowMatrixView<'_, T> {
+    type Row = &'a [T];
+
+    fn row(&'a self, r: usize) -> &'a [T] {
+        assert!(r < self.height, "only {} rows available", self.height);
+        let width = self.width();
+        &self.values[r * width..(r + 1) * width]
+    }
+}
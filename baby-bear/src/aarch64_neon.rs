@@ -0,0 +1,228 @@
+use crate::{BabyBear, MONTY_MU, P};
+use core::arch::aarch64::*;
+use core::mem::transmute;
+use core::ops::{Add, Mul, Neg, Sub};
+use p3_field::{impl_packed_field_arithmetic, AbstractField, PackedField};
+
+const WIDTH: usize = 4;
+const P_VEC: uint32x4_t = unsafe { transmute::<[u32; WIDTH], _>([P; WIDTH]) };
+
+/// Vectorized NEON implementation of `BabyBear` arithmetic.
+///
+/// Like `BabyBear`, each lane holds a canonical value in Montgomery form.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)] // This is needed to make `transmute`s safe.
+pub struct PackedBabyBearNeon(pub [BabyBear; WIDTH]);
+
+impl PackedBabyBearNeon {
+    #[inline]
+    #[must_use]
+    fn to_vector(self) -> uint32x4_t {
+        // SAFETY: `Self` is `repr(transparent)` over `[BabyBear; WIDTH]`, which has the same size
+        // as `uint32x4_t`, and `BabyBear` is `repr(transparent)` over `u32`.
+        unsafe { transmute(self) }
+    }
+
+    /// # Safety
+    /// Each lane of `vector` must be less than `P`.
+    #[inline]
+    #[must_use]
+    unsafe fn from_vector(vector: uint32x4_t) -> Self {
+        transmute(vector)
+    }
+}
+
+/// Add two vectors of values less than `P`.
+#[inline]
+#[must_use]
+fn add(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+    // See the AVX2 implementation; the smaller of t and t - P is the reduced sum.
+    unsafe {
+        let t = vaddq_u32(lhs, rhs);
+        let u = vsubq_u32(t, P_VEC);
+        vminq_u32(t, u)
+    }
+}
+
+/// Subtract two vectors of values less than `P`.
+#[inline]
+#[must_use]
+fn sub(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+    unsafe {
+        let t = vsubq_u32(lhs, rhs);
+        let u = vaddq_u32(t, P_VEC);
+        vminq_u32(t, u)
+    }
+}
+
+/// Negate a vector of values less than `P`.
+#[inline]
+#[must_use]
+fn neg(val: uint32x4_t) -> uint32x4_t {
+    // Subtracting from zero maps zero to itself, rather than to P.
+    sub(unsafe { vdupq_n_u32(0) }, val)
+}
+
+/// Partially Montgomery-reduce a pair of 64-bit products, to `(prod - t * P) / 2^32`, which lies
+/// strictly between `-P` and `P`.
+#[inline]
+#[must_use]
+fn monty_reduce_partial(prod: uint64x2_t) -> uint32x2_t {
+    // t is the low half of the product times MU, so `t * P` agrees with `prod` in its low 32 bits.
+    unsafe {
+        let t = vmul_u32(vmovn_u64(prod), vdup_n_u32(MONTY_MU));
+        let t_p = vmull_u32(t, vdup_n_u32(P));
+        vshrn_n_u64::<32>(vsubq_u64(prod, t_p))
+    }
+}
+
+/// Multiply two vectors of values less than `P`, in Montgomery form.
+#[inline]
+#[must_use]
+fn mul(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+    unsafe {
+        let prod_lo = vmull_u32(vget_low_u32(lhs), vget_low_u32(rhs));
+        let prod_hi = vmull_high_u32(lhs, rhs);
+        let d = vcombine_u32(monty_reduce_partial(prod_lo), monty_reduce_partial(prod_hi));
+
+        // Add P to the negative results.
+        let u = vaddq_u32(d, P_VEC);
+        vminq_u32(d, u)
+    }
+}
+
+impl Add for PackedBabyBearNeon {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(add(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Sub for PackedBabyBearNeon {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(sub(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Neg for PackedBabyBearNeon {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        unsafe { Self::from_vector(neg(self.to_vector())) }
+    }
+}
+
+impl Mul for PackedBabyBearNeon {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(mul(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl_packed_field_arithmetic!(PackedBabyBearNeon, BabyBear, WIDTH);
+
+/// Interleave the lanes of `a` and `b` in blocks of `block_len`; see `PackedField::interleave`.
+#[inline]
+#[must_use]
+fn interleave(a: uint32x4_t, b: uint32x4_t, block_len: usize) -> (uint32x4_t, uint32x4_t) {
+    unsafe {
+        match block_len {
+            1 => (vtrn1q_u32(a, b), vtrn2q_u32(a, b)),
+            2 => {
+                let (a, b) = (vreinterpretq_u64_u32(a), vreinterpretq_u64_u32(b));
+                (
+                    vreinterpretq_u32_u64(vtrn1q_u64(a, b)),
+                    vreinterpretq_u32_u64(vtrn2q_u64(a, b)),
+                )
+            }
+            WIDTH => (a, b),
+            _ => panic!("unsupported block length"),
+        }
+    }
+}
+
+unsafe impl PackedField for PackedBabyBearNeon {
+    type Scalar = BabyBear;
+
+    const WIDTH: usize = WIDTH;
+    const ZEROS: Self = Self::ZERO;
+    const ONES: Self = Self::ONE;
+
+    #[inline]
+    fn from_slice(slice: &[BabyBear]) -> &Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[BabyBear; WIDTH]`.
+        unsafe { &*slice.as_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_slice_mut(slice: &mut [BabyBear]) -> &mut Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[BabyBear; WIDTH]`.
+        unsafe { &mut *slice.as_mut_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> BabyBear>(f: F) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[BabyBear] {
+        &self.0
+    }
+
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [BabyBear] {
+        &mut self.0
+    }
+
+    #[inline]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (a, b) = interleave(self.to_vector(), other.to_vector(), block_len);
+        unsafe { (Self::from_vector(a), Self::from_vector(b)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedBabyBearNeon;
+    use crate::{BabyBear, P};
+    use p3_field::{AbstractField, PackedField};
+    use p3_field_testing::check_packed_field;
+
+    type F = BabyBear;
+    type V = PackedBabyBearNeon;
+
+    #[test]
+    fn packed_field() {
+        check_packed_field::<V>();
+    }
+
+    #[test]
+    fn extreme_lanes() {
+        // Products of the largest Montgomery forms exercise the full range of the reduction, and
+        // every result must stay canonical.
+        let x = V::from_fn(|i| F {
+            value: P - 1 - i as u32,
+        });
+        let y = V::from_fn(|i| F {
+            value: P - 1 - 2 * i as u32,
+        });
+        for (a, b) in [(x, y), (x, x), (x, -y), (-x, V::ZERO)] {
+            for (i, &c) in (a * b).0.iter().enumerate() {
+                assert_eq!(c, a.0[i] * b.0[i]);
+                assert!(c.value < P);
+            }
+        }
+        assert_eq!(-V::ZERO, V::ZERO);
+    }
+}
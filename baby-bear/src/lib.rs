@@ -0,0 +1,403 @@
+//! The prime field known as BabyBear, defined as `F_p` where `p = 15 * 2^27 + 1`.
+
+#![no_std]
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod aarch64_neon;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+mod x86_64_avx2;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+pub use aarch64_neon::*;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+pub use x86_64_avx2::*;
+
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+use p3_field::{
+    impl_packed_array_ops, AbstractField, Field, PrimeField, PrimeField32, TwoAdicField,
+};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+/// The BabyBear prime, `15 * 2^27 + 1`.
+const P: u32 = 0x7800_0001;
+/// The Montgomery radix is `2^MONTY_BITS`.
+const MONTY_BITS: u32 = 32;
+/// `P^-1 mod 2^MONTY_BITS`.
+const MONTY_MU: u32 = 0x8800_0001;
+
+/// The prime field known as BabyBear, defined as `F_p` where `p = 15 * 2^27 + 1`.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)] // Packed field implementations rely on this.
+pub struct BabyBear {
+    /// The element `x`, in Montgomery form `x * 2^32 mod P`. Always canonical, i.e. less than `P`,
+    /// so the derived equality and hashing are correct.
+    value: u32,
+}
+
+impl BabyBear {
+    /// Create a field element from its canonical value, which must be less than `P`.
+    const fn new(value: u32) -> Self {
+        Self {
+            value: to_monty(value),
+        }
+    }
+}
+
+impl Ord for BabyBear {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_canonical_u32().cmp(&other.as_canonical_u32())
+    }
+}
+
+impl PartialOrd for BabyBear {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BabyBear {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.as_canonical_u32(), f)
+    }
+}
+
+impl Debug for BabyBear {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.as_canonical_u32(), f)
+    }
+}
+
+impl Distribution<BabyBear> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> BabyBear {
+        // A uniformly random Montgomery form is a uniformly random element.
+        loop {
+            let next_u31 = rng.next_u32() >> 1;
+            let is_canonical = next_u31 < P;
+            if is_canonical {
+                return BabyBear { value: next_u31 };
+            }
+        }
+    }
+}
+
+impl AbstractField for BabyBear {
+    const ZERO: Self = Self::new(0);
+    const ONE: Self = Self::new(1);
+    const TWO: Self = Self::new(2);
+    const NEG_ONE: Self = Self::new(P - 1);
+
+    fn from_canonical_u8(n: u8) -> Self {
+        Self::from_canonical_u32(u32::from(n))
+    }
+
+    fn from_canonical_u16(n: u8) -> Self {
+        Self::from_canonical_u32(u32::from(n))
+    }
+
+    fn from_canonical_u32(n: u32) -> Self {
+        debug_assert!(n < P);
+        Self::from_wrapped_u32(n)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_canonical_u64(n: u64) -> Self {
+        debug_assert!(n < u64::from(P));
+        Self::from_canonical_u32(n as u32)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_canonical_usize(n: usize) -> Self {
+        debug_assert!(n < P as usize);
+        Self::from_canonical_u32(n as u32)
+    }
+
+    fn from_wrapped_u32(n: u32) -> Self {
+        Self { value: to_monty(n) }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_wrapped_u64(n: u64) -> Self {
+        Self {
+            value: to_monty((n % u64::from(P)) as u32),
+        }
+    }
+
+    // Sage: GF(15 * 2^27 + 1).multiplicative_generator()
+    fn multiplicative_group_generator() -> Self {
+        Self::new(31)
+    }
+}
+
+impl Field for BabyBear {
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    type Packing = PackedBabyBearNeon;
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+    type Packing = PackedBabyBearAVX2;
+    #[cfg(not(any(
+        all(target_arch = "aarch64", target_feature = "neon"),
+        all(target_arch = "x86_64", target_feature = "avx2"),
+    )))]
+    type Packing = p3_field::PackedArray<Self, 8>;
+
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    fn try_inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // Compute x^(p - 2) = x^(15 * 2^27 - 1), whose exponent is 1110 followed by 27 ones in
+        // binary. The names below refer to the number of ones in the binary exponent of each
+        // power.
+        let p2 = self.square() * *self;
+        let p3 = p2.square() * *self;
+        let p6 = exp_acc::<3>(p3, p3);
+        let p12 = exp_acc::<6>(p6, p6);
+        let p24 = exp_acc::<12>(p12, p12);
+        let p27 = exp_acc::<3>(p24, p3);
+        Some(exp_acc::<27>(p3.square(), p27))
+    }
+}
+
+impl_packed_array_ops!(BabyBear);
+
+impl PrimeField for BabyBear {}
+
+impl PrimeField32 for BabyBear {
+    const ORDER_U32: u32 = P;
+
+    fn as_canonical_u32(&self) -> u32 {
+        from_monty(self.value)
+    }
+}
+
+impl TwoAdicField for BabyBear {
+    const TWO_ADICITY: usize = 27;
+
+    // 31^15, i.e. the multiplicative group generator raised to (p - 1) / 2^27.
+    fn power_of_two_generator() -> Self {
+        Self::new(0x1a42_7a41)
+    }
+}
+
+impl Add for BabyBear {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        // Both values are less than P < 2^31, so their sum can't overflow.
+        let mut sum = self.value + rhs.value;
+        if sum >= P {
+            sum -= P;
+        }
+        Self { value: sum }
+    }
+}
+
+impl AddAssign for BabyBear {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for BabyBear {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x + y).unwrap_or(Self::ZERO)
+    }
+}
+
+impl Sub for BabyBear {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let (mut diff, under) = self.value.overflowing_sub(rhs.value);
+        if under {
+            diff = diff.wrapping_add(P);
+        }
+        Self { value: diff }
+    }
+}
+
+impl SubAssign for BabyBear {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for BabyBear {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::ZERO - self
+    }
+}
+
+impl Mul for BabyBear {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let long_prod = u64::from(self.value) * u64::from(rhs.value);
+        Self {
+            value: monty_reduce(long_prod),
+        }
+    }
+}
+
+impl MulAssign for BabyBear {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Product for BabyBear {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x * y).unwrap_or(Self::ONE)
+    }
+}
+
+impl Div for BabyBear {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+/// Squares `base` `N` times, then multiplies by `tail`.
+#[inline(always)]
+fn exp_acc<const N: usize>(base: BabyBear, tail: BabyBear) -> BabyBear {
+    base.exp_power_of_2(N) * tail
+}
+
+/// Convert `x`, which needn't be reduced, to Montgomery form.
+#[inline]
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+const fn to_monty(x: u32) -> u32 {
+    (((x as u64) << MONTY_BITS) % P as u64) as u32
+}
+
+/// Convert `x` out of Montgomery form.
+#[inline]
+#[must_use]
+fn from_monty(x: u32) -> u32 {
+    monty_reduce(u64::from(x))
+}
+
+/// Montgomery reduction: given `x < 2^32 * P`, compute `x * 2^-32 mod P`, in canonical form.
+#[inline]
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+fn monty_reduce(x: u64) -> u32 {
+    // Choose t so that t * P = x mod 2^32. Then x - t * P is a multiple of 2^32, and its quotient
+    // by 2^32 lies strictly between -P and P.
+    let t = (x as u32).wrapping_mul(MONTY_MU);
+    let u = u64::from(t) * u64::from(P);
+    let (x_sub_u, under) = x.overflowing_sub(u);
+    let x_sub_u_hi = (x_sub_u >> MONTY_BITS) as u32;
+    let corr = if under { P } else { 0 };
+    x_sub_u_hi.wrapping_add(corr)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use crate::{BabyBear, P};
+    use alloc::format;
+    use p3_field::{AbstractField, Field, PackedArray, PrimeField32, TwoAdicField};
+    use p3_field_testing::{check_field_axioms, check_packed_field};
+
+    type F = BabyBear;
+
+    #[test]
+    fn add() {
+        assert_eq!(F::ONE + F::ONE, F::TWO);
+        assert_eq!(F::NEG_ONE + F::ONE, F::ZERO);
+        assert_eq!(F::NEG_ONE + F::TWO, F::ONE);
+        assert_eq!(F::NEG_ONE + F::NEG_ONE, F::new(P - 2));
+    }
+
+    #[test]
+    fn sub() {
+        assert_eq!(F::ONE - F::ONE, F::ZERO);
+        assert_eq!(F::TWO - F::TWO, F::ZERO);
+        assert_eq!(F::NEG_ONE - F::NEG_ONE, F::ZERO);
+        assert_eq!(F::TWO - F::ONE, F::ONE);
+        assert_eq!(F::NEG_ONE - F::ZERO, F::NEG_ONE);
+        assert_eq!(F::ZERO - F::ONE, F::NEG_ONE);
+    }
+
+    #[test]
+    fn mul() {
+        assert_eq!(F::TWO * F::TWO, F::new(4));
+        assert_eq!(F::NEG_ONE * F::NEG_ONE, F::ONE);
+        assert_eq!(
+            F::new(1 << 20) * F::new(1 << 20),
+            F::new(((1u64 << 40) % u64::from(P)) as u32)
+        );
+        assert_eq!(
+            F::new(P - 5) * F::new(7),
+            F::new((u64::from(P - 5) * 7 % u64::from(P)) as u32)
+        );
+    }
+
+    #[test]
+    fn canonical_values() {
+        for x in [0, 1, 2, 31, P / 2, P - 1] {
+            assert_eq!(F::from_canonical_u32(x).as_canonical_u32(), x);
+        }
+        assert_eq!(F::from_wrapped_u32(P), F::ZERO);
+        assert_eq!(F::from_wrapped_u32(u32::MAX), F::new(u32::MAX - 2 * P));
+        assert_eq!(
+            F::from_wrapped_u64(u64::MAX),
+            F::new((u64::MAX % u64::from(P)) as u32)
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(F::ZERO.try_inverse(), None);
+        assert_eq!(F::ONE.inverse(), F::ONE);
+        assert_eq!(F::NEG_ONE.inverse(), F::NEG_ONE);
+        assert_eq!(F::TWO.inverse() * F::TWO, F::ONE);
+        assert_eq!(F::new(P - 5).inverse() * F::new(P - 5), F::ONE);
+    }
+
+    #[test]
+    fn generators() {
+        let g = F::multiplicative_group_generator();
+        // The group order is 2^27 * 3 * 5.
+        for q in [2, 3, 5] {
+            assert_ne!(g.exp_u64(u64::from(P - 1) / q), F::ONE);
+        }
+
+        let g = F::power_of_two_generator();
+        assert_eq!(g.exp_power_of_2(F::TWO_ADICITY), F::ONE);
+        assert_ne!(g.exp_power_of_2(F::TWO_ADICITY - 1), F::ONE);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", F::new(5)), "5");
+        assert_eq!(format!("{:?}", F::NEG_ONE), format!("{}", P - 1));
+    }
+
+    #[test]
+    fn field_axioms() {
+        check_field_axioms::<F>();
+    }
+
+    #[test]
+    fn packed_array() {
+        check_packed_field::<PackedArray<F, 1>>();
+        check_packed_field::<PackedArray<F, 4>>();
+        check_packed_field::<PackedArray<F, 8>>();
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{BabyBear, MONTY_MU, P};
+use core::arch::x86_64::*;
+use core::mem::transmute;
+use core::ops::{Add, Mul, Neg, Sub};
+use p3_field::{impl_packed_field_arithmetic, AbstractField, PackedField};
+
+const WIDTH: usize = 8;
+const P_VEC: __m256i = unsafe { transmute::<[u32; WIDTH], _>([P; WIDTH]) };
+const MU_VEC: __m256i = unsafe { transmute::<[u32; WIDTH], _>([MONTY_MU; WIDTH]) };
+
+/// Vectorized AVX2 implementation of `BabyBear` arithmetic.
+///
+/// Like `BabyBear`, each lane holds a canonical value in Montgomery form.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)] // This is needed to make `transmute`s safe.
+pub struct PackedBabyBearAVX2(pub [BabyBear; WIDTH]);
+
+impl PackedBabyBearAVX2 {
+    #[inline]
+    #[must_use]
+    fn to_vector(self) -> __m256i {
+        // SAFETY: `Self` is `repr(transparent)` over `[BabyBear; WIDTH]`, which has the same size
+        // as `__m256i`, and `BabyBear` is `repr(transparent)` over `u32`.
+        unsafe { transmute(self) }
+    }
+
+    /// # Safety
+    /// Each lane of `vector` must be less than `P`.
+    #[inline]
+    #[must_use]
+    unsafe fn from_vector(vector: __m256i) -> Self {
+        transmute(vector)
+    }
+}
+
+/// Add two vectors of values less than `P`.
+#[inline]
+#[must_use]
+fn add(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // The sum t is less than 2P < 2^32. If t >= P, then t - P is less than t; otherwise t - P
+    // wraps around to more than t. Either way, the smaller of the two is the reduced sum.
+    unsafe {
+        let t = _mm256_add_epi32(lhs, rhs);
+        let u = _mm256_sub_epi32(t, P_VEC);
+        _mm256_min_epu32(t, u)
+    }
+}
+
+/// Subtract two vectors of values less than `P`.
+#[inline]
+#[must_use]
+fn sub(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // If lhs >= rhs, then t = lhs - rhs is less than P, and t + P is more than t; otherwise t wraps
+    // around, and t + P wraps back to the reduced difference, which is less than t.
+    unsafe {
+        let t = _mm256_sub_epi32(lhs, rhs);
+        let u = _mm256_add_epi32(t, P_VEC);
+        _mm256_min_epu32(t, u)
+    }
+}
+
+/// Negate a vector of values less than `P`.
+#[inline]
+#[must_use]
+fn neg(val: __m256i) -> __m256i {
+    // Subtracting from zero maps zero to itself, rather than to P.
+    sub(unsafe { _mm256_setzero_si256() }, val)
+}
+
+/// Partially Montgomery-reduce the product in each 64-bit lane. The high half of each result is
+/// `(prod - t * P) / 2^32`, which lies strictly between `-P` and `P`.
+#[inline]
+#[must_use]
+fn monty_reduce_partial(prod: __m256i) -> __m256i {
+    // `_mm256_mul_epu32` only reads the low 32 bits of each 64-bit lane, so `t` is the low half of
+    // the product times MU, and `t * P` agrees with `prod` in its low 32 bits.
+    unsafe {
+        let t = _mm256_mul_epu32(prod, MU_VEC);
+        let t_p = _mm256_mul_epu32(t, P_VEC);
+        _mm256_sub_epi64(prod, t_p)
+    }
+}
+
+/// Multiply two vectors of values less than `P`, in Montgomery form.
+#[inline]
+#[must_use]
+fn mul(lhs: __m256i, rhs: __m256i) -> __m256i {
+    unsafe {
+        let lhs_odd = _mm256_srli_epi64::<32>(lhs);
+        let rhs_odd = _mm256_srli_epi64::<32>(rhs);
+        let d_even = monty_reduce_partial(_mm256_mul_epu32(lhs, rhs));
+        let d_odd = monty_reduce_partial(_mm256_mul_epu32(lhs_odd, rhs_odd));
+
+        // Gather the high halves, then add P to those which are negative.
+        let d = _mm256_blend_epi32::<0b10101010>(_mm256_srli_epi64::<32>(d_even), d_odd);
+        let u = _mm256_add_epi32(d, P_VEC);
+        _mm256_min_epu32(d, u)
+    }
+}
+
+impl Add for PackedBabyBearAVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(add(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Sub for PackedBabyBearAVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(sub(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Neg for PackedBabyBearAVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        unsafe { Self::from_vector(neg(self.to_vector())) }
+    }
+}
+
+impl Mul for PackedBabyBearAVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(mul(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl_packed_field_arithmetic!(PackedBabyBearAVX2, BabyBear, WIDTH);
+
+/// Interleave the lanes of `a` and `b` in blocks of `block_len`; see `PackedField::interleave`.
+#[inline]
+#[must_use]
+fn interleave(a: __m256i, b: __m256i, block_len: usize) -> (__m256i, __m256i) {
+    unsafe {
+        match block_len {
+            1 => (
+                _mm256_blend_epi32::<0b10101010>(a, _mm256_slli_epi64::<32>(b)),
+                _mm256_blend_epi32::<0b10101010>(_mm256_srli_epi64::<32>(a), b),
+            ),
+            2 => (_mm256_unpacklo_epi64(a, b), _mm256_unpackhi_epi64(a, b)),
+            4 => (
+                _mm256_permute2x128_si256::<0x20>(a, b),
+                _mm256_permute2x128_si256::<0x31>(a, b),
+            ),
+            WIDTH => (a, b),
+            _ => panic!("unsupported block length"),
+        }
+    }
+}
+
+unsafe impl PackedField for PackedBabyBearAVX2 {
+    type Scalar = BabyBear;
+
+    const WIDTH: usize = WIDTH;
+    const ZEROS: Self = Self::ZERO;
+    const ONES: Self = Self::ONE;
+
+    #[inline]
+    fn from_slice(slice: &[BabyBear]) -> &Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[BabyBear; WIDTH]`.
+        unsafe { &*slice.as_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_slice_mut(slice: &mut [BabyBear]) -> &mut Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[BabyBear; WIDTH]`.
+        unsafe { &mut *slice.as_mut_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> BabyBear>(f: F) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[BabyBear] {
+        &self.0
+    }
+
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [BabyBear] {
+        &mut self.0
+    }
+
+    #[inline]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (a, b) = interleave(self.to_vector(), other.to_vector(), block_len);
+        unsafe { (Self::from_vector(a), Self::from_vector(b)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedBabyBearAVX2;
+    use crate::{BabyBear, P};
+    use p3_field::{AbstractField, PackedField};
+    use p3_field_testing::check_packed_field;
+
+    type F = BabyBear;
+    type V = PackedBabyBearAVX2;
+
+    #[test]
+    fn packed_field() {
+        check_packed_field::<V>();
+    }
+
+    #[test]
+    fn extreme_lanes() {
+        // Products of the largest Montgomery forms exercise the full range of the reduction, and
+        // every result must stay canonical.
+        let x = V::from_fn(|i| F {
+            value: P - 1 - i as u32,
+        });
+        let y = V::from_fn(|i| F {
+            value: P - 1 - 2 * i as u32,
+        });
+        for (a, b) in [(x, y), (x, x), (x, -y), (-x, V::ZERO)] {
+            for (i, &c) in (a * b).0.iter().enumerate() {
+                assert_eq!(c, a.0[i] * b.0[i]);
+                assert!(c.value < P);
+            }
+        }
+        assert_eq!(-V::ZERO, V::ZERO);
+    }
+}
@@ -11,7 +11,8 @@ pub use duplex_challenger::*;
 pub use hash_challenger::*;
 
 use alloc::vec::Vec;
-use p3_field::{AbstractExtensionField, Field};
+use p3_field::{AbstractExtensionField, Field, PrimeField64};
+use p3_maybe_rayon::{MaybeIntoParIter, ParallelIterator};
 
 /// Observes prover messages during an IOP, and generates Fiat-Shamir challenges in response.
 pub trait Challenger<F: Field> {
@@ -41,4 +42,44 @@ pub trait Challenger<F: Field> {
     fn random_vec(&mut self, n: usize) -> Vec<F> {
         (0..n).map(|_| self.random_element()).collect()
     }
+
+    /// Sample a random `bits`-bit integer, e.g. a query index. The result is close to uniform as
+    /// long as `2^bits` is much smaller than the field order.
+    fn random_bits(&mut self, bits: usize) -> usize
+    where
+        F: PrimeField64,
+    {
+        debug_assert!(bits < usize::BITS as usize);
+        debug_assert!((1 << bits) < F::ORDER_U64);
+        let rand_f = self.random_element();
+        let rand_usize = rand_f.as_canonical_u64() as usize;
+        rand_usize & ((1 << bits) - 1)
+    }
+
+    /// Search for a proof-of-work witness: a field element which, once observed, causes the next
+    /// `bits` random bits to be zero. The witness is observed before it is returned.
+    ///
+    /// Candidates are checked in parallel, each against its own clone of the challenger.
+    fn grind(&mut self, bits: usize) -> F
+    where
+        Self: Clone + Sync,
+        F: PrimeField64,
+    {
+        let witness = (0..F::ORDER_U64)
+            .into_par_iter()
+            .map(F::from_canonical_u64)
+            .find_any(|&witness| self.clone().check_witness(bits, witness))
+            .expect("Failed to find a proof-of-work witness");
+        assert!(self.check_witness(bits, witness));
+        witness
+    }
+
+    /// Observe a proof-of-work witness, then check that the next `bits` random bits are zero.
+    fn check_witness(&mut self, bits: usize, witness: F) -> bool
+    where
+        F: PrimeField64,
+    {
+        self.observe_element(witness);
+        self.random_bits(bits) == 0
+    }
 }
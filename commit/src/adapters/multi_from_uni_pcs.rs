@@ -1,5 +1,5 @@
 use crate::pcs::{UnivariatePCS, PCS};
-use crate::MultivariatePCS;
+use crate::{Dimensions, MultivariatePCS};
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 use p3_challenger::Challenger;
@@ -47,7 +47,7 @@ where
     ) -> (Vec<Vec<Vec<EF>>>, Self::Proof)
     where
         EF: ExtensionField<F>,
-        Chal: Challenger<F>,
+        Chal: Challenger<F> + Clone + Sync,
     {
         todo!()
     }
@@ -55,9 +55,11 @@ where
     fn verify_multi_batches<EF, Chal>(
         &self,
         _commits: &[Self::Commitment],
+        _dims: &[Vec<Dimensions>],
         _points: &[Vec<EF>],
         _values: &[Vec<Vec<EF>>],
         _proof: &Self::Proof,
+        _challenger: &mut Chal,
     ) -> Result<(), Self::Error>
     where
         EF: ExtensionField<F>,
@@ -17,24 +17,59 @@ pub trait MMCS<T> {
     type ProverData;
     type Commitment;
     type Proof;
+    type MultiProof;
     type Error;
     type Mat: for<'a> MatrixRows<'a, T>;
 
-    fn open_batch(row: usize, prover_data: &Self::ProverData) -> (Vec<Vec<T>>, Self::Proof);
+    /// Whether commitments and openings reveal nothing about the rows which weren't opened.
+    fn is_hiding(&self) -> bool {
+        false
+    }
+
+    /// Open the row at `index` of each committed matrix. Returns the opened rows, in the order the
+    /// matrices were committed, along with a proof of their correctness.
+    fn open_batch(
+        &self,
+        index: usize,
+        prover_data: &Self::ProverData,
+    ) -> (Vec<Vec<T>>, Self::Proof);
+
+    /// Open the rows at each of `indices`, as in `open_batch`. Returns the opened rows for each
+    /// index, along with a single proof covering all of them, which may be smaller than separate
+    /// proofs for each index.
+    fn open_batch_multi(
+        &self,
+        indices: &[usize],
+        prover_data: &Self::ProverData,
+    ) -> (Vec<Vec<Vec<T>>>, Self::MultiProof);
 
     /// Get the matrices that were committed to.
     fn get_matrices(prover_data: &Self::ProverData) -> &[Self::Mat];
 
-    /// Verify a batch opening.
+    /// Verify a batch opening. `dimensions` and `opened_values` should each have one entry per
+    /// committed matrix, in the order the matrices were committed.
     fn verify_batch(
+        &self,
         commit: &Self::Commitment,
         dimensions: &[Dimensions],
         index: usize,
-        item: Vec<T>,
+        opened_values: &[Vec<T>],
         proof: &Self::Proof,
     ) -> Result<(), Self::Error>;
+
+    /// Verify an opening produced by `open_batch_multi`. `opened_values[i]` holds the rows opened at
+    /// `indices[i]`, in the same layout as for `verify_batch`.
+    fn verify_batch_multi(
+        &self,
+        commit: &Self::Commitment,
+        dimensions: &[Dimensions],
+        indices: &[usize],
+        opened_values: &[Vec<Vec<T>>],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error>;
 }
 
+#[derive(Copy, Clone, Debug)]
 pub struct Dimensions {
     pub width: usize,
     pub log2_height: usize,
@@ -7,6 +7,8 @@ use alloc::vec::Vec;
 use p3_challenger::Challenger;
 use p3_matrix::MatrixRows;
 
+use crate::Dimensions;
+
 /// A (not necessarily hiding) polynomial commitment scheme, for committing to (batches of)
 /// polynomials defined over the field `F`.
 ///
@@ -25,6 +27,12 @@ pub trait PCS<F: Field, In: for<'a> MatrixRows<'a, F>> {
 
     type Error;
 
+    /// Whether commitments and opening proofs reveal nothing about the committed polynomials,
+    /// beyond their evaluations at the opened points.
+    fn is_hiding(&self) -> bool {
+        false
+    }
+
     fn commit_batches(&self, polynomials: Vec<In>) -> (Self::Commitment, Self::ProverData);
 
     fn commit_batch(&self, polynomials: In) -> (Self::Commitment, Self::ProverData) {
@@ -33,6 +41,13 @@ pub trait PCS<F: Field, In: for<'a> MatrixRows<'a, F>> {
 }
 
 pub trait UnivariatePCS<F: Field, In: for<'a> MatrixRows<'a, F>>: PCS<F, In> {
+    /// The log of the ratio between the size of the domain each polynomial is committed over and
+    /// its degree bound.
+    fn log_blowup(&self) -> usize;
+
+    /// Open each committed batch at each point. `values[i][j]` holds the evaluations at
+    /// `points[j]` of every column of batch `i`, with the columns of each matrix in the batch
+    /// concatenated in the order the matrices were committed.
     fn open_multi_batches<EF, Chal>(
         &self,
         prover_data: &[&Self::ProverData],
@@ -41,14 +56,18 @@ pub trait UnivariatePCS<F: Field, In: for<'a> MatrixRows<'a, F>>: PCS<F, In> {
     ) -> (Vec<Vec<Vec<EF>>>, Self::Proof)
     where
         EF: ExtensionField<F>,
-        Chal: Challenger<F>;
+        Chal: Challenger<F> + Clone + Sync;
 
+    /// Verify an opening produced by `open_multi_batches`. `dims[i]` holds the dimensions of each
+    /// polynomial matrix in batch `i`.
     fn verify_multi_batches<EF, Chal>(
         &self,
         commits: &[Self::Commitment],
+        dims: &[Vec<Dimensions>],
         points: &[EF],
         values: &[Vec<Vec<EF>>],
         proof: &Self::Proof,
+        challenger: &mut Chal,
     ) -> Result<(), Self::Error>
     where
         EF: ExtensionField<F>,
@@ -64,14 +83,16 @@ pub trait MultivariatePCS<F: Field, In: for<'a> MatrixRows<'a, F>>: PCS<F, In> {
     ) -> (Vec<Vec<Vec<EF>>>, Self::Proof)
     where
         EF: ExtensionField<F>,
-        Chal: Challenger<F>;
+        Chal: Challenger<F> + Clone + Sync;
 
     fn verify_multi_batches<EF, Chal>(
         &self,
         commits: &[Self::Commitment],
+        dims: &[Vec<Dimensions>],
         points: &[Vec<EF>],
         values: &[Vec<Vec<EF>>],
         proof: &Self::Proof,
+        challenger: &mut Chal,
     ) -> Result<(), Self::Error>
     where
         EF: ExtensionField<F>,
@@ -0,0 +1,196 @@
+//! Test suites shared by the field implementations.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use p3_field::{AbstractField, Field, PackedField};
+use rand::distributions::{Distribution, Standard};
+use rand::rngs::ThreadRng;
+use rand::{thread_rng, Rng};
+
+/// Check the field axioms on random elements.
+pub fn check_field_axioms<F: Field>()
+where
+    Standard: Distribution<F>,
+{
+    let mut rng = thread_rng();
+    for _ in 0..1000 {
+        let (x, y, z): (F, F, F) = (rng.gen(), rng.gen(), rng.gen());
+
+        assert_eq!(x + y, y + x);
+        assert_eq!(x * y, y * x);
+        assert_eq!((x + y) + z, x + (y + z));
+        assert_eq!((x * y) * z, x * (y * z));
+        assert_eq!(x * (y + z), x * y + x * z);
+
+        assert_eq!(x + F::ZERO, x);
+        assert_eq!(x * F::ONE, x);
+        assert_eq!(x * F::ZERO, F::ZERO);
+        assert_eq!(x + -x, F::ZERO);
+        assert_eq!(x - y, x + -y);
+        assert_eq!(x.square(), x * x);
+
+        match x.try_inverse() {
+            Some(x_inv) => {
+                assert_eq!(x * x_inv, F::ONE);
+                assert_eq!(y / x, y * x_inv);
+            }
+            None => assert!(x.is_zero()),
+        }
+    }
+}
+
+/// Check the arithmetic and layout of a packed field against its scalar field, lane by lane, on
+/// random vectors along with vectors of special values.
+pub fn check_packed_field<P: PackedField>()
+where
+    Standard: Distribution<P::Scalar>,
+{
+    let special = [
+        P::Scalar::ZERO,
+        P::Scalar::ONE,
+        P::Scalar::TWO,
+        P::Scalar::NEG_ONE,
+    ];
+    let mut rng = thread_rng();
+    let random_packed = |rng: &mut ThreadRng| -> P {
+        P::from_fn(|_| {
+            if rng.gen_ratio(1, 4) {
+                special[rng.gen_range(0..special.len())]
+            } else {
+                rng.gen()
+            }
+        })
+    };
+
+    for _ in 0..100 {
+        let (x, y) = (random_packed(&mut rng), random_packed(&mut rng));
+        let s: P::Scalar = rng.gen();
+
+        check_lanes(x + y, x, y, |a, b| a + b);
+        check_lanes(x - y, x, y, |a, b| a - b);
+        check_lanes(x * y, x, y, |a, b| a * b);
+        check_lanes(-x, x, y, |a, _| -a);
+        check_lanes(x.square(), x, y, |a, _| a.square());
+        check_lanes(x.doubles(), x, y, |a, _| a + a);
+
+        // Division needs every lane of the divisor to be nonzero.
+        let divisor = P::from_fn(|i| match y.as_slice()[i] {
+            b if b.is_zero() => P::Scalar::ONE,
+            b => b,
+        });
+        check_lanes(x / divisor, x, divisor, |a, b| a / b);
+
+        check_lanes(x + s, x, y, |a, _| a + s);
+        check_lanes(x - s, x, y, |a, _| a - s);
+        check_lanes(x * s, x, y, |a, _| a * s);
+        check_lanes(s + x, x, y, |a, _| s + a);
+        check_lanes(s - x, x, y, |a, _| s - a);
+        check_lanes(s * x, x, y, |a, _| s * a);
+        if !s.is_zero() {
+            check_lanes(x / s, x, y, |a, _| a / s);
+        }
+
+        let mut z = x;
+        z += y;
+        z -= s;
+        z *= y;
+        z *= s;
+        z -= x;
+        z += s;
+        check_lanes(z, x, y, |a, b| ((a + b - s) * b * s - a) + s);
+
+        check_lanes(P::from(s), x, y, |_, _| s);
+        check_lanes([x, y].into_iter().sum(), x, y, |a, b| a + b);
+        check_lanes([x, y].into_iter().product(), x, y, |a, b| a * b);
+    }
+
+    check_lanes(P::ZEROS, P::ZEROS, P::ZEROS, |_, _| P::Scalar::ZERO);
+    check_lanes(P::ONES, P::ZEROS, P::ZEROS, |_, _| P::Scalar::ONE);
+    check_lanes(P::ZERO, P::ZEROS, P::ZEROS, |_, _| P::Scalar::ZERO);
+    check_lanes(P::ONE, P::ZEROS, P::ZEROS, |_, _| P::Scalar::ONE);
+    check_lanes(P::TWO, P::ZEROS, P::ZEROS, |_, _| P::Scalar::TWO);
+    check_lanes(P::NEG_ONE, P::ZEROS, P::ZEROS, |_, _| P::Scalar::NEG_ONE);
+
+    check_layout::<P>();
+    check_interleave::<P>();
+}
+
+/// Check that each lane of `result` is `f` applied to the corresponding lanes of `x` and `y`.
+fn check_lanes<P, F>(result: P, x: P, y: P, f: F)
+where
+    P: PackedField,
+    F: Fn(P::Scalar, P::Scalar) -> P::Scalar,
+{
+    for lane in 0..P::WIDTH {
+        assert_eq!(
+            result.as_slice()[lane],
+            f(x.as_slice()[lane], y.as_slice()[lane]),
+            "lane {lane}"
+        );
+    }
+}
+
+fn check_layout<P: PackedField>()
+where
+    Standard: Distribution<P::Scalar>,
+{
+    let mut rng = thread_rng();
+    let scalars: Vec<P::Scalar> = (0..4 * P::WIDTH).map(|_| rng.gen()).collect();
+
+    let packed = P::pack_slice(&scalars);
+    assert_eq!(packed.len(), 4);
+    for (i, p) in packed.iter().enumerate() {
+        assert_eq!(p.as_slice(), &scalars[i * P::WIDTH..(i + 1) * P::WIDTH]);
+        assert_eq!(P::from_slice(p.as_slice()).as_slice(), p.as_slice());
+        assert_eq!(
+            P::from_fn(|j| scalars[i * P::WIDTH + j]).as_slice(),
+            p.as_slice()
+        );
+    }
+
+    let mut scalars = scalars;
+    P::from_slice_mut(&mut scalars[..P::WIDTH]).as_slice_mut()[0] = P::Scalar::TWO;
+    assert_eq!(scalars[0], P::Scalar::TWO);
+}
+
+fn check_interleave<P: PackedField>()
+where
+    Standard: Distribution<P::Scalar>,
+{
+    let mut rng = thread_rng();
+    let x = P::from_fn(|_| rng.gen());
+    let y = P::from_fn(|_| rng.gen());
+
+    let mut block_len = 1;
+    while block_len <= P::WIDTH {
+        let (a, b) = x.interleave(y, block_len);
+        let (expected_a, expected_b) = interleave_reference(x.as_slice(), y.as_slice(), block_len);
+        assert_eq!(a.as_slice(), expected_a, "block_len {block_len}");
+        assert_eq!(b.as_slice(), expected_b, "block_len {block_len}");
+        block_len *= 2;
+    }
+}
+
+/// A straightforward implementation of `PackedField::interleave` on slices: within each group of
+/// `2 * block_len` lanes, the first output takes the first block of `x` and then of `y`, while the
+/// second output takes their second blocks.
+fn interleave_reference<F: Copy>(x: &[F], y: &[F], block_len: usize) -> (Vec<F>, Vec<F>) {
+    let width = x.len();
+    if block_len == width {
+        return (x.to_vec(), y.to_vec());
+    }
+    let mut a = Vec::with_capacity(width);
+    let mut b = Vec::with_capacity(width);
+    for (x_group, y_group) in x.chunks(2 * block_len).zip(y.chunks(2 * block_len)) {
+        let (x_lo, x_hi) = x_group.split_at(block_len);
+        let (y_lo, y_hi) = y_group.split_at(block_len);
+        a.extend_from_slice(x_lo);
+        a.extend_from_slice(y_lo);
+        b.extend_from_slice(x_hi);
+        b.extend_from_slice(y_hi);
+    }
+    (a, b)
+}
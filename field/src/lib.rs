@@ -8,10 +8,13 @@ mod batch_inverse;
 mod field;
 mod helpers;
 mod packed;
+mod packed_array;
+mod packed_macros;
 mod symbolic;
 
 pub use batch_inverse::*;
 pub use field::*;
 pub use helpers::*;
 pub use packed::*;
+pub use packed_array::*;
 pub use symbolic::*;
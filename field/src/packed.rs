@@ -7,21 +7,22 @@ use crate::field::{AbstractField, AbstractionOf, Field};
 /// - `WIDTH` is assumed to be a power of 2.
 /// - If `P` implements `PackedField` then `P` must be castable to/from `[P::Scalar; P::WIDTH]`
 ///   without UB.
-pub unsafe trait PackedField: AbstractionOf<Self::Scalar>
+pub unsafe trait PackedField:
+    AbstractionOf<Self::Scalar>
     + 'static
     + Copy
     + Default
     + AddAssign<Self::Scalar>
     + SubAssign<Self::Scalar>
     + MulAssign<Self::Scalar>
-    // TODO: Implement packed / packed division
+    + Div<Self, Output = Self>
     + Div<Self::Scalar, Output = Self>
     + Send
     + Sync
-    where
-        Self::Scalar: Add<Self, Output = Self>,
-        Self::Scalar: Mul<Self, Output = Self>,
-        Self::Scalar: Sub<Self, Output = Self>,
+where
+    Self::Scalar: Add<Self, Output = Self>,
+    Self::Scalar: Mul<Self, Output = Self>,
+    Self::Scalar: Sub<Self, Output = Self>,
 {
     type Scalar: Field;
 
@@ -33,7 +34,9 @@ pub unsafe trait PackedField: AbstractionOf<Self::Scalar>
     fn from_slice_mut(slice: &mut [Self::Scalar]) -> &mut Self;
 
     /// Similar to `core:array::from_fn`.
-    fn from_fn<F>(f: F) -> Self where F: FnMut(usize) -> Self::Scalar;
+    fn from_fn<F>(f: F) -> Self
+    where
+        F: FnMut(usize) -> Self::Scalar;
 
     fn as_slice(&self) -> &[Self::Scalar];
     fn as_slice_mut(&mut self) -> &mut [Self::Scalar];
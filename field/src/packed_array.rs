@@ -0,0 +1,318 @@
+use core::array;
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::field::{AbstractExtensionField, AbstractField, AbstractionOf, Field};
+use crate::packed::PackedField;
+
+/// A portable `PackedField` made of `N` scalars, with each operation applied lane by lane. It
+/// needs no intrinsics, but its simple loops leave room for the compiler to vectorize them.
+///
+/// `N` must be a power of 2. A field opts into this packing by invoking `impl_packed_array_ops!`,
+/// which provides the arithmetic with a scalar on the left that Rust's orphan rules keep this
+/// crate from implementing for every field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)] // `PackedField` needs this to cast slices of scalars.
+pub struct PackedArray<F, const N: usize>(pub [F; N]);
+
+impl<F: Field, const N: usize> PackedArray<F, N> {
+    #[inline]
+    #[must_use]
+    fn map(self, f: impl Fn(F) -> F) -> Self {
+        Self(self.0.map(f))
+    }
+
+    #[inline]
+    #[must_use]
+    fn zip_map(self, rhs: Self, f: impl Fn(F, F) -> F) -> Self {
+        Self(array::from_fn(|i| f(self.0[i], rhs.0[i])))
+    }
+}
+
+impl<F: Field, const N: usize> Default for PackedArray<F, N> {
+    fn default() -> Self {
+        Self([F::ZERO; N])
+    }
+}
+
+impl<F: Field, const N: usize> From<F> for PackedArray<F, N> {
+    fn from(value: F) -> Self {
+        Self([value; N])
+    }
+}
+
+impl<F: Field, const N: usize> Add for PackedArray<F, N> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        self.zip_map(rhs, |a, b| a + b)
+    }
+}
+
+impl<F: Field, const N: usize> Sub for PackedArray<F, N> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self.zip_map(rhs, |a, b| a - b)
+    }
+}
+
+impl<F: Field, const N: usize> Neg for PackedArray<F, N> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+
+impl<F: Field, const N: usize> Mul for PackedArray<F, N> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        self.zip_map(rhs, |a, b| a * b)
+    }
+}
+
+impl<F: Field, const N: usize> Div for PackedArray<F, N> {
+    type Output = Self;
+
+    /// Divides lane by lane, with a single inversion by Montgomery's trick. Like scalar division,
+    /// this panics if any lane of `rhs` is zero.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        // prefix[i] holds the product of the first i lanes of rhs.
+        let mut prefix = [F::ONE; N];
+        let mut acc = F::ONE;
+        for (p, &r) in prefix.iter_mut().zip(&rhs.0) {
+            *p = acc;
+            acc *= r;
+        }
+
+        // Walk back from the inverse of the full product, which at step i is the inverse of the
+        // product of the first i + 1 lanes.
+        let mut inv = acc.inverse();
+        let mut result = self;
+        for i in (0..N).rev() {
+            result.0[i] *= prefix[i] * inv;
+            inv *= rhs.0[i];
+        }
+        result
+    }
+}
+
+impl<F: Field, const N: usize> AddAssign for PackedArray<F, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<F: Field, const N: usize> SubAssign for PackedArray<F, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<F: Field, const N: usize> MulAssign for PackedArray<F, N> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<F: Field, const N: usize> Sum for PackedArray<F, N> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x + y).unwrap_or(Self::ZERO)
+    }
+}
+
+impl<F: Field, const N: usize> Product for PackedArray<F, N> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|x, y| x * y).unwrap_or(Self::ONE)
+    }
+}
+
+impl<F: Field, const N: usize> AbstractField for PackedArray<F, N> {
+    const ZERO: Self = Self([F::ZERO; N]);
+    const ONE: Self = Self([F::ONE; N]);
+    const TWO: Self = Self([F::TWO; N]);
+    const NEG_ONE: Self = Self([F::NEG_ONE; N]);
+
+    fn from_canonical_u8(n: u8) -> Self {
+        F::from_canonical_u8(n).into()
+    }
+
+    fn from_canonical_u16(n: u8) -> Self {
+        F::from_canonical_u16(n).into()
+    }
+
+    fn from_canonical_u32(n: u32) -> Self {
+        F::from_canonical_u32(n).into()
+    }
+
+    fn from_canonical_u64(n: u64) -> Self {
+        F::from_canonical_u64(n).into()
+    }
+
+    fn from_canonical_usize(n: usize) -> Self {
+        F::from_canonical_usize(n).into()
+    }
+
+    fn from_wrapped_u32(n: u32) -> Self {
+        F::from_wrapped_u32(n).into()
+    }
+
+    fn from_wrapped_u64(n: u64) -> Self {
+        F::from_wrapped_u64(n).into()
+    }
+
+    fn multiplicative_group_generator() -> Self {
+        F::multiplicative_group_generator().into()
+    }
+}
+
+impl<F: Field, const N: usize> Add<F> for PackedArray<F, N> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: F) -> Self {
+        self.map(|a| a + rhs)
+    }
+}
+
+impl<F: Field, const N: usize> Sub<F> for PackedArray<F, N> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: F) -> Self {
+        self.map(|a| a - rhs)
+    }
+}
+
+impl<F: Field, const N: usize> Mul<F> for PackedArray<F, N> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: F) -> Self {
+        self.map(|a| a * rhs)
+    }
+}
+
+impl<F: Field, const N: usize> Div<F> for PackedArray<F, N> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: F) -> Self {
+        self * rhs.inverse()
+    }
+}
+
+impl<F: Field, const N: usize> AddAssign<F> for PackedArray<F, N> {
+    fn add_assign(&mut self, rhs: F) {
+        *self = *self + rhs;
+    }
+}
+
+impl<F: Field, const N: usize> SubAssign<F> for PackedArray<F, N> {
+    fn sub_assign(&mut self, rhs: F) {
+        *self = *self - rhs;
+    }
+}
+
+impl<F: Field, const N: usize> MulAssign<F> for PackedArray<F, N> {
+    fn mul_assign(&mut self, rhs: F) {
+        *self = *self * rhs;
+    }
+}
+
+impl<F: Field, const N: usize> Sum<F> for PackedArray<F, N> {
+    fn sum<I: Iterator<Item = F>>(iter: I) -> Self {
+        iter.sum::<F>().into()
+    }
+}
+
+impl<F: Field, const N: usize> Product<F> for PackedArray<F, N> {
+    fn product<I: Iterator<Item = F>>(iter: I) -> Self {
+        iter.product::<F>().into()
+    }
+}
+
+impl<F: Field, const N: usize> AbstractionOf<F> for PackedArray<F, N> {}
+
+impl<F: Field, const N: usize> AbstractExtensionField<Self> for PackedArray<F, N> {
+    const D: usize = 1;
+
+    fn from_base(b: Self) -> Self {
+        b
+    }
+
+    fn from_base_slice(bs: &[Self]) -> Self {
+        assert_eq!(bs.len(), 1);
+        bs[0]
+    }
+
+    fn as_base_slice(&self) -> &[Self] {
+        core::slice::from_ref(self)
+    }
+}
+
+unsafe impl<F: Field, const N: usize> PackedField for PackedArray<F, N>
+where
+    F: Add<Self, Output = Self> + Sub<Self, Output = Self> + Mul<Self, Output = Self>,
+{
+    type Scalar = F;
+
+    const WIDTH: usize = {
+        assert!(
+            N.is_power_of_two(),
+            "a packing's width must be a power of 2"
+        );
+        N
+    };
+    const ZEROS: Self = Self::ZERO;
+    const ONES: Self = Self::ONE;
+
+    fn from_slice(slice: &[F]) -> &Self {
+        assert_eq!(slice.len(), N);
+        // SAFETY: `Self` is `repr(transparent)` over `[F; N]`.
+        unsafe { &*slice.as_ptr().cast() }
+    }
+
+    fn from_slice_mut(slice: &mut [F]) -> &mut Self {
+        assert_eq!(slice.len(), N);
+        // SAFETY: `Self` is `repr(transparent)` over `[F; N]`.
+        unsafe { &mut *slice.as_mut_ptr().cast() }
+    }
+
+    fn from_fn<Fn: FnMut(usize) -> F>(f: Fn) -> Self {
+        Self(array::from_fn(f))
+    }
+
+    fn as_slice(&self) -> &[F] {
+        &self.0
+    }
+
+    fn as_slice_mut(&mut self) -> &mut [F] {
+        &mut self.0
+    }
+
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        assert!(
+            block_len.is_power_of_two() && block_len <= N,
+            "unsupported block length"
+        );
+        // Within each group of 2 * block_len lanes, the first output keeps our first block and
+        // takes the other's first block in place of our second, which goes to the second output.
+        let (mut a, mut b) = (*self, other);
+        if block_len < N {
+            for group in (0..N).step_by(2 * block_len) {
+                for i in group..group + block_len {
+                    core::mem::swap(&mut a.0[i + block_len], &mut b.0[i]);
+                }
+            }
+        }
+        (a, b)
+    }
+}
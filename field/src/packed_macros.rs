@@ -0,0 +1,236 @@
+/// Implement the arithmetic that a `PackedField` needs in addition to its packed/packed operations.
+///
+/// `$packed` must be a tuple struct wrapping a `[$scalar; $width]`, which implements `Add`, `Sub`,
+/// `Mul` and `Neg` for itself. This derives the assigning operators, `Sum` and `Product`,
+/// `AbstractField`, mixed packed/scalar arithmetic in both directions, division, and
+/// `AbstractExtensionField<Self>`, the latter so that a packing can serve as its own packed
+/// challenge type. Scalars are broadcast to every lane.
+///
+/// The `PackedField` impl itself is left to the caller, since `interleave` is specific to each
+/// type's representation.
+#[macro_export]
+macro_rules! impl_packed_field_arithmetic {
+    ($packed:ty, $scalar:ty, $width:expr) => {
+        impl From<$scalar> for $packed {
+            fn from(value: $scalar) -> Self {
+                Self([value; $width])
+            }
+        }
+
+        impl core::ops::Div for $packed {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self {
+                Self(($crate::PackedArray(self.0) / $crate::PackedArray(rhs.0)).0)
+            }
+        }
+
+        impl core::ops::AddAssign for $packed {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl core::ops::SubAssign for $packed {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl core::ops::MulAssign for $packed {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl core::iter::Sum for $packed {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.reduce(|x, y| x + y)
+                    .unwrap_or(<Self as $crate::AbstractField>::ZERO)
+            }
+        }
+
+        impl core::iter::Product for $packed {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.reduce(|x, y| x * y)
+                    .unwrap_or(<Self as $crate::AbstractField>::ONE)
+            }
+        }
+
+        impl $crate::AbstractField for $packed {
+            const ZERO: Self = Self([<$scalar as $crate::AbstractField>::ZERO; $width]);
+            const ONE: Self = Self([<$scalar as $crate::AbstractField>::ONE; $width]);
+            const TWO: Self = Self([<$scalar as $crate::AbstractField>::TWO; $width]);
+            const NEG_ONE: Self = Self([<$scalar as $crate::AbstractField>::NEG_ONE; $width]);
+
+            fn from_canonical_u8(n: u8) -> Self {
+                <$scalar as $crate::AbstractField>::from_canonical_u8(n).into()
+            }
+
+            fn from_canonical_u16(n: u8) -> Self {
+                <$scalar as $crate::AbstractField>::from_canonical_u16(n).into()
+            }
+
+            fn from_canonical_u32(n: u32) -> Self {
+                <$scalar as $crate::AbstractField>::from_canonical_u32(n).into()
+            }
+
+            fn from_canonical_u64(n: u64) -> Self {
+                <$scalar as $crate::AbstractField>::from_canonical_u64(n).into()
+            }
+
+            fn from_canonical_usize(n: usize) -> Self {
+                <$scalar as $crate::AbstractField>::from_canonical_usize(n).into()
+            }
+
+            fn from_wrapped_u32(n: u32) -> Self {
+                <$scalar as $crate::AbstractField>::from_wrapped_u32(n).into()
+            }
+
+            fn from_wrapped_u64(n: u64) -> Self {
+                <$scalar as $crate::AbstractField>::from_wrapped_u64(n).into()
+            }
+
+            fn multiplicative_group_generator() -> Self {
+                <$scalar as $crate::AbstractField>::multiplicative_group_generator().into()
+            }
+        }
+
+        impl core::ops::Add<$scalar> for $packed {
+            type Output = Self;
+
+            fn add(self, rhs: $scalar) -> Self {
+                self + Self::from(rhs)
+            }
+        }
+
+        impl core::ops::Sub<$scalar> for $packed {
+            type Output = Self;
+
+            fn sub(self, rhs: $scalar) -> Self {
+                self - Self::from(rhs)
+            }
+        }
+
+        impl core::ops::Mul<$scalar> for $packed {
+            type Output = Self;
+
+            fn mul(self, rhs: $scalar) -> Self {
+                self * Self::from(rhs)
+            }
+        }
+
+        impl core::ops::Div<$scalar> for $packed {
+            type Output = Self;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn div(self, rhs: $scalar) -> Self {
+                self * $crate::Field::inverse(&rhs)
+            }
+        }
+
+        impl core::ops::AddAssign<$scalar> for $packed {
+            fn add_assign(&mut self, rhs: $scalar) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl core::ops::SubAssign<$scalar> for $packed {
+            fn sub_assign(&mut self, rhs: $scalar) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl core::ops::MulAssign<$scalar> for $packed {
+            fn mul_assign(&mut self, rhs: $scalar) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl core::iter::Sum<$scalar> for $packed {
+            fn sum<I: Iterator<Item = $scalar>>(iter: I) -> Self {
+                iter.sum::<$scalar>().into()
+            }
+        }
+
+        impl core::iter::Product<$scalar> for $packed {
+            fn product<I: Iterator<Item = $scalar>>(iter: I) -> Self {
+                iter.product::<$scalar>().into()
+            }
+        }
+
+        impl $crate::AbstractionOf<$scalar> for $packed {}
+
+        impl core::ops::Add<$packed> for $scalar {
+            type Output = $packed;
+
+            fn add(self, rhs: $packed) -> $packed {
+                <$packed>::from(self) + rhs
+            }
+        }
+
+        impl core::ops::Sub<$packed> for $scalar {
+            type Output = $packed;
+
+            fn sub(self, rhs: $packed) -> $packed {
+                <$packed>::from(self) - rhs
+            }
+        }
+
+        impl core::ops::Mul<$packed> for $scalar {
+            type Output = $packed;
+
+            fn mul(self, rhs: $packed) -> $packed {
+                <$packed>::from(self) * rhs
+            }
+        }
+
+        impl $crate::AbstractExtensionField<$packed> for $packed {
+            const D: usize = 1;
+
+            fn from_base(b: Self) -> Self {
+                b
+            }
+
+            fn from_base_slice(bs: &[Self]) -> Self {
+                assert_eq!(bs.len(), 1);
+                bs[0]
+            }
+
+            fn as_base_slice(&self) -> &[Self] {
+                core::slice::from_ref(self)
+            }
+        }
+    };
+}
+
+/// Let `PackedArray<$scalar, N>` serve as a packing of `$scalar`, by implementing arithmetic with a
+/// scalar on the left, which the orphan rules keep `p3-field` from providing for every field.
+#[macro_export]
+macro_rules! impl_packed_array_ops {
+    ($scalar:ty) => {
+        impl<const N: usize> core::ops::Add<$crate::PackedArray<$scalar, N>> for $scalar {
+            type Output = $crate::PackedArray<$scalar, N>;
+
+            fn add(self, rhs: $crate::PackedArray<$scalar, N>) -> Self::Output {
+                rhs + self
+            }
+        }
+
+        impl<const N: usize> core::ops::Sub<$crate::PackedArray<$scalar, N>> for $scalar {
+            type Output = $crate::PackedArray<$scalar, N>;
+
+            fn sub(self, rhs: $crate::PackedArray<$scalar, N>) -> Self::Output {
+                $crate::PackedArray(rhs.0.map(|x| self - x))
+            }
+        }
+
+        impl<const N: usize> core::ops::Mul<$crate::PackedArray<$scalar, N>> for $scalar {
+            type Output = $crate::PackedArray<$scalar, N>;
+
+            fn mul(self, rhs: $crate::PackedArray<$scalar, N>) -> Self::Output {
+                rhs * self
+            }
+        }
+    };
+}
@@ -0,0 +1,29 @@
+use p3_field::TwoAdicField;
+use p3_goldilocks::Goldilocks;
+use p3_mersenne_31::{Mersenne31, Mersenne31Complex};
+
+/// Check that `primitive_root_of_unity(bits)` has order exactly `2^bits`, for every `bits` up to
+/// the field's two-adicity.
+fn test_primitive_roots_of_unity<F: TwoAdicField>() {
+    for bits in 0..=F::TWO_ADICITY {
+        let root = F::primitive_root_of_unity(bits);
+        assert_eq!(root.exp_power_of_2(bits), F::ONE, "order divides 2^{bits}");
+        if bits > 0 {
+            assert_ne!(
+                root.exp_power_of_2(bits - 1),
+                F::ONE,
+                "order is exactly 2^{bits}"
+            );
+        }
+    }
+}
+
+#[test]
+fn primitive_roots_of_unity_goldilocks() {
+    test_primitive_roots_of_unity::<Goldilocks>();
+}
+
+#[test]
+fn primitive_roots_of_unity_mersenne_31_complex() {
+    test_primitive_roots_of_unity::<Mersenne31Complex<Mersenne31>>();
+}
@@ -1,4 +1,49 @@
+use alloc::vec::Vec;
+
 pub struct FriConfig {
+    /// The log of the ratio between the length of each input codeword and its degree bound.
+    pub log_blowup: usize,
     pub num_queries: usize,
-    // TODO: grinding bits
+    /// The number of bits of proof-of-work the prover must perform before query indices are
+    /// sampled. Each bit roughly doubles the prover's grinding cost, and can replace some queries.
+    pub proof_of_work_bits: usize,
+    /// The log of the number of evaluations folded into one in each round, i.e. of the arity.
+    /// Rounds may fold by less when a smaller input codeword, or the final polynomial, is reached.
+    pub log_folding_factor: usize,
+    /// The log of the number of coefficients of the final polynomial, which is sent in the clear
+    /// once folding stops.
+    pub log_final_poly_len: usize,
+}
+
+impl FriConfig {
+    /// The log of the height of the final codeword, at which folding stops.
+    pub(crate) fn log_final_height(&self) -> usize {
+        self.log_final_poly_len + self.log_blowup
+    }
+
+    /// Given the log heights of the input codewords, in descending order, returns the log arity
+    /// of each folding round.
+    pub(crate) fn log_arities(&self, input_log_heights: &[usize]) -> Vec<usize> {
+        assert!(
+            self.log_folding_factor > 0,
+            "Folding factor must exceed one"
+        );
+        let log_final_height = self.log_final_height();
+        let mut log_height = input_log_heights[0];
+
+        let mut log_arities = Vec::new();
+        while log_height > log_final_height {
+            // Don't fold past the next input codeword, which must be mixed in at its own height.
+            let log_next_height = input_log_heights
+                .iter()
+                .copied()
+                .find(|&h| h < log_height)
+                .unwrap_or(0)
+                .max(log_final_height);
+            let log_arity = self.log_folding_factor.min(log_height - log_next_height);
+            log_arities.push(log_arity);
+            log_height -= log_arity;
+        }
+        log_arities
+    }
 }
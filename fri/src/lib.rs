@@ -7,11 +7,13 @@ extern crate alloc;
 use crate::proof::FriProof;
 use crate::prover::prove;
 use crate::verifier::verify;
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 use p3_challenger::Challenger;
-use p3_commit::{DirectMMCS, MMCS};
-use p3_field::{ExtensionField, Field};
-use p3_ldt::{LDTBasedPCS, LDT};
+use p3_commit::{Dimensions, DirectMMCS, MMCS};
+use p3_field::{ExtensionField, Field, PrimeField64, TwoAdicField};
+use p3_ldt::{LDTBasedPCS, LDTQueries, LDT};
+use p3_matrix::dense::RowMajorMatrix;
 
 mod config;
 mod proof;
@@ -20,6 +22,7 @@ mod verifier;
 
 pub use config::*;
 pub use proof::*;
+pub use verifier::FriError;
 
 pub struct FriLDT<F, Challenge, M, MC>
 where
@@ -29,39 +32,84 @@ where
     MC: DirectMMCS<F>,
 {
     config: FriConfig,
+    mmcs: MC,
     _phantom_f: PhantomData<F>,
     _phantom_fe: PhantomData<Challenge>,
     _phantom_m: PhantomData<M>,
-    _phantom_mc: PhantomData<MC>,
 }
 
-impl<F, Challenge, M, MC> LDT<F, M> for FriLDT<F, Challenge, M, MC>
+impl<F, Challenge, M, MC> FriLDT<F, Challenge, M, MC>
 where
     F: Field,
     Challenge: ExtensionField<F>,
     M: MMCS<F>,
     MC: DirectMMCS<F>,
+{
+    /// Create a FRI LDT, which will use `mmcs` to commit to each folded layer.
+    pub fn new(config: FriConfig, mmcs: MC) -> Self {
+        Self {
+            config,
+            mmcs,
+            _phantom_f: PhantomData,
+            _phantom_fe: PhantomData,
+            _phantom_m: PhantomData,
+        }
+    }
+}
+
+impl<F, Challenge, M, MC> LDT<F, M> for FriLDT<F, Challenge, M, MC>
+where
+    F: TwoAdicField + PrimeField64,
+    Challenge: ExtensionField<F>,
+    M: MMCS<F>,
+    MC: DirectMMCS<F, Mat = RowMajorMatrix<F>>,
+    MC::Commitment: AsRef<[F]>,
 {
     type Proof = FriProof<F, Challenge, M, MC>;
-    type Error = ();
+    type Error = FriError<M::Error, MC::Error>;
+
+    fn is_hiding(&self) -> bool {
+        self.mmcs.is_hiding()
+    }
 
-    fn prove<Chal>(&self, codewords: &[M::ProverData], challenger: &mut Chal) -> Self::Proof
+    fn prove<Chal>(
+        &self,
+        input_mmcs: &M,
+        codewords: &[M::ProverData],
+        challenger: &mut Chal,
+    ) -> (Self::Proof, Vec<usize>)
     where
-        Chal: Challenger<F>,
+        Chal: Challenger<F> + Clone + Sync,
     {
-        prove::<F, Challenge, M, MC, Chal>(codewords, &self.config, challenger)
+        prove::<F, Challenge, M, MC, Chal>(
+            &self.config,
+            input_mmcs,
+            &self.mmcs,
+            codewords,
+            challenger,
+        )
     }
 
     fn verify<Chal>(
         &self,
-        _codeword_commits: &[M::Commitment],
+        input_mmcs: &M,
+        input_dims: &[Vec<Dimensions>],
+        codeword_commits: &[M::Commitment],
         proof: &Self::Proof,
         challenger: &mut Chal,
-    ) -> Result<(), Self::Error>
+    ) -> Result<LDTQueries<F>, Self::Error>
     where
         Chal: Challenger<F>,
     {
-        verify::<F, Challenge, M, MC, Chal>(proof, challenger)
+        verify::<F, Challenge, M, MC, Chal>(
+            &self.config,
+            input_mmcs,
+            &self.mmcs,
+            input_dims,
+            codeword_commits,
+            proof,
+            challenger,
+        )
     }
 }
 
@@ -2,7 +2,6 @@ use alloc::vec::Vec;
 use p3_commit::{DirectMMCS, MMCS};
 use p3_field::{ExtensionField, Field};
 
-#[allow(dead_code)] // TODO: fields should be used soon
 pub struct FriProof<F, EF, M, MC>
 where
     F: Field,
@@ -10,10 +9,15 @@ where
     M: MMCS<F>,
     MC: DirectMMCS<F>,
 {
-    query_proofs: Vec<QueryProof<F, EF, M, MC>>,
+    /// A commitment to each folded layer, starting with the batched input codeword.
+    pub commit_phase_commits: Vec<MC::Commitment>,
+    pub query_proofs: Vec<QueryProof<F, EF, M, MC>>,
+    /// The coefficients of the final polynomial, which remains once folding stops.
+    pub final_poly: Vec<EF>,
+    /// The proof-of-work witness, which is observed just before query indices are sampled.
+    pub pow_witness: F,
 }
 
-#[allow(dead_code)] // TODO: fields should be used soon
 pub struct QueryProof<F, EF, M, MC>
 where
     F: Field,
@@ -22,19 +26,21 @@ where
     MC: DirectMMCS<F>,
 {
     /// An opened row of each matrix that was part of this batch-FRI proof.
-    leaves: Vec<Vec<F>>,
-    leaf_opening_proofs: Vec<M::Proof>,
-    steps: Vec<QueryStepProof<F, EF, MC>>,
+    pub leaves: Vec<Vec<F>>,
+    /// An opening proof for each batch of input matrices.
+    pub leaf_opening_proofs: Vec<M::Proof>,
+    /// An opening of each commit phase layer, in the order they were committed.
+    pub steps: Vec<QueryStepProof<F, EF, MC>>,
 }
 
-#[allow(dead_code)] // TODO: fields should be used soon
 pub struct QueryStepProof<F, EF, MC>
 where
     F: Field,
     EF: ExtensionField<F>,
     MC: DirectMMCS<F>,
 {
-    /// An opened row of each matrix that was part of this batch-FRI proof.
-    leaves: EF,
-    leaf_opening_proofs: MC::Proof,
+    /// The values folded together with the queried value in this layer, in order. The queried
+    /// value itself is not sent, since the verifier can derive it from the previous layer.
+    pub sibling_values: Vec<EF>,
+    pub opening_proof: MC::Proof,
 }
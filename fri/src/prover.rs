@@ -1,79 +1,286 @@
-use crate::{FriConfig, FriProof};
+use crate::{FriConfig, FriProof, QueryProof, QueryStepProof};
 use alloc::vec;
 use alloc::vec::Vec;
 use core::cmp::Reverse;
 use itertools::Itertools;
 use p3_challenger::Challenger;
 use p3_commit::{DirectMMCS, MMCS};
-use p3_field::{AbstractField, ExtensionField, Field};
+use p3_field::{AbstractExtensionField, ExtensionField, Field, PrimeField64, TwoAdicField};
+use p3_matrix::dense::RowMajorMatrix;
 use p3_matrix::{Matrix, MatrixRows};
-use p3_maybe_rayon::MaybeIntoParIter;
-use p3_maybe_rayon::ParallelIterator;
+use p3_util::{log2_strict_usize, reverse_slice_index_bits};
 
 pub(crate) fn prove<F, Challenge, M, MC, Chal>(
-    codewords: &[M::ProverData],
     config: &FriConfig,
+    input_mmcs: &M,
+    commit_mmcs: &MC,
+    codewords: &[M::ProverData],
     challenger: &mut Chal,
-) -> FriProof<F, Challenge, M, MC>
+) -> (FriProof<F, Challenge, M, MC>, Vec<usize>)
+where
+    F: TwoAdicField + PrimeField64,
+    Challenge: ExtensionField<F>,
+    M: MMCS<F>,
+    MC: DirectMMCS<F, Mat = RowMajorMatrix<F>>,
+    MC::Commitment: AsRef<[F]>,
+    Chal: Challenger<F> + Clone + Sync,
+{
+    let (log_min_height, log_max_height) = codewords
+        .iter()
+        .flat_map(|data| M::get_matrices(data))
+        .map(|mat| log2_strict_usize(mat.height()))
+        .minmax()
+        .into_option()
+        .expect("No matrices?");
+    assert!(
+        log_min_height >= config.log_final_height(),
+        "Every codeword must be at least as tall as the final codeword, of height 2^{}",
+        config.log_final_height()
+    );
+
+    let (commit_phase_commits, commit_phase_data, final_poly) =
+        commit_phase::<F, Challenge, M, MC, Chal>(config, commit_mmcs, codewords, challenger);
+
+    let pow_witness = challenger.grind(config.proof_of_work_bits);
+
+    let query_indices: Vec<usize> = (0..config.num_queries)
+        .map(|_| challenger.random_bits(log_max_height))
+        .collect();
+    let query_proofs = query_indices
+        .iter()
+        .map(|&index| {
+            answer_query::<F, Challenge, M, MC>(
+                input_mmcs,
+                commit_mmcs,
+                index,
+                codewords,
+                &commit_phase_data,
+            )
+        })
+        .collect();
+
+    let proof = FriProof {
+        commit_phase_commits,
+        query_proofs,
+        final_poly,
+        pow_witness,
+    };
+    (proof, query_indices)
+}
+
+fn answer_query<F, Challenge, M, MC>(
+    input_mmcs: &M,
+    commit_mmcs: &MC,
+    index: usize,
+    codewords: &[M::ProverData],
+    commit_phase_data: &[MC::ProverData],
+) -> QueryProof<F, Challenge, M, MC>
 where
     F: Field,
     Challenge: ExtensionField<F>,
     M: MMCS<F>,
     MC: DirectMMCS<F>,
-    Chal: Challenger<F>,
 {
-    let _commit_phase_commits = commit_phase::<F, Challenge, M, MC, Chal>(codewords, challenger);
-    let query_indices: Vec<Challenge> = (0..config.num_queries)
-        .map(|_| challenger.random_ext_element())
+    let (leaves, leaf_opening_proofs): (Vec<_>, Vec<_>) = codewords
+        .iter()
+        .map(|data| input_mmcs.open_batch(index, data))
+        .unzip();
+    let leaves = leaves.into_iter().flatten().collect();
+
+    let d = <Challenge as AbstractExtensionField<F>>::D;
+    let mut bits_folded = 0;
+    let steps = commit_phase_data
+        .iter()
+        .map(|data| {
+            let arity = MC::get_matrices(data)[0].width() / d;
+            let log_arity = log2_strict_usize(arity);
+            let index_i = index >> bits_folded;
+            let index_row = index_i >> log_arity;
+            bits_folded += log_arity;
+
+            let (mut opened_rows, opening_proof) = commit_mmcs.open_batch(index_row, data);
+            assert_eq!(opened_rows.len(), 1);
+            let opened_row = opened_rows.pop().unwrap();
+            let sibling_values = opened_row
+                .chunks_exact(d)
+                .enumerate()
+                .filter(|&(j, _)| j != index_i % arity)
+                .map(|(_, chunk)| <Challenge as AbstractExtensionField<F>>::from_base_slice(chunk))
+                .collect();
+
+            QueryStepProof {
+                sibling_values,
+                opening_proof,
+            }
+        })
         .collect();
-    let _query_proofs = query_indices.into_par_iter().map(|_query_index| todo!());
-    todo!()
+
+    QueryProof {
+        leaves,
+        leaf_opening_proofs,
+        steps,
+    }
 }
 
+/// Batch the input codewords, then repeatedly commit to and fold the result until it reaches the
+/// final height. Returns the commitment and prover data for each committed layer, along with the
+/// coefficients of the final polynomial.
+///
+/// Codewords are assumed to hold evaluations over the coset `g H`, where `g` is the multiplicative
+/// group generator, in bit-reversed order. Matrices of smaller heights are mixed in once the folded
+/// codeword reaches their height.
 pub(crate) fn commit_phase<F, Challenge, M, MC, Chal>(
+    config: &FriConfig,
+    commit_mmcs: &MC,
     codewords: &[M::ProverData],
     challenger: &mut Chal,
-) -> Vec<MC::ProverData>
+) -> (Vec<MC::Commitment>, Vec<MC::ProverData>, Vec<Challenge>)
 where
-    F: Field,
+    F: TwoAdicField,
     Challenge: ExtensionField<F>,
     M: MMCS<F>,
-    MC: DirectMMCS<F>,
+    MC: DirectMMCS<F, Mat = RowMajorMatrix<F>>,
+    MC::Commitment: AsRef<[F]>,
     Chal: Challenger<F>,
 {
     let alpha: Challenge = challenger.random_ext_element();
-    let matrices_by_desc_height = codewords
+    let mut matrices_by_desc_height = codewords
         .iter()
         .flat_map(|data| M::get_matrices(data))
         .sorted_by_key(|mat| Reverse(mat.height()))
-        .group_by(|mat| mat.height());
-    let mut matrices_by_desc_height = matrices_by_desc_height.into_iter();
-
-    let (max_height, largest_matrices_iter) = matrices_by_desc_height.next().expect("No matrices?");
-    let largest_matrices = largest_matrices_iter.collect_vec();
-    let zero_vec = vec![<Challenge as AbstractField>::ZERO; max_height];
-    let mut current = reduce_matrices(max_height, zero_vec, largest_matrices, alpha);
-    let mut committed = vec![current.clone()];
-
-    for (height, matrices) in matrices_by_desc_height {
-        while current.len() < height {
-            let beta = <Challenge as AbstractField>::ZERO; // TODO
-            current = fold_even_odd(&current, beta);
+        .peekable();
+
+    let input_log_heights = matrices_by_desc_height
+        .clone()
+        .map(|mat| log2_strict_usize(mat.height()))
+        .collect_vec();
+
+    let max_height = matrices_by_desc_height
+        .peek()
+        .expect("No matrices?")
+        .height();
+    let mut current = vec![Challenge::ZERO; max_height];
+    let mut shift = F::multiplicative_group_generator();
+    let mut commits = vec![];
+    let mut data = vec![];
+
+    for log_arity in config.log_arities(&input_log_heights) {
+        let height = current.len();
+        let matrices = matrices_by_desc_height
+            .peeking_take_while(|mat| mat.height() == height)
+            .collect_vec();
+        if !matrices.is_empty() {
+            current = reduce_matrices::<F, Challenge, M::Mat>(height, current, matrices, alpha);
         }
-        committed.push(current.clone());
-        current = reduce_matrices::<F, Challenge, M::Mat>(
-            height,
-            current.clone(),
-            matrices.collect(),
-            alpha,
+
+        // Each row holds the `arity` evaluations which are folded into one, so that they can be
+        // opened together.
+        let layer_values = current
+            .iter()
+            .flat_map(|x| {
+                AbstractExtensionField::<F>::as_base_slice(x)
+                    .iter()
+                    .copied()
+            })
+            .collect();
+        let layer = RowMajorMatrix::new(
+            layer_values,
+            <Challenge as AbstractExtensionField<F>>::D << log_arity,
         );
+        let (commit, prover_data) = commit_mmcs.commit(vec![layer]);
+        challenger.observe_elements(commit.as_ref());
+        commits.push(commit);
+        data.push(prover_data);
+
+        // Folding by `2^k` with `beta` is equivalent to folding by two `k` times, with challenges
+        // `beta, beta^2, beta^4, ...`.
+        let mut beta: Challenge = challenger.random_ext_element();
+        for _ in 0..log_arity {
+            current = fold_even_odd(&current, shift, beta);
+            beta = beta.square();
+            shift = shift.square();
+        }
+    }
+
+    let height = current.len();
+    let matrices = matrices_by_desc_height
+        .peeking_take_while(|mat| mat.height() == height)
+        .collect_vec();
+    if !matrices.is_empty() {
+        current = reduce_matrices::<F, Challenge, M::Mat>(height, current, matrices, alpha);
+    }
+    // Folding stops at each input height, and no input is shorter than the final codeword.
+    debug_assert!(
+        matrices_by_desc_height.next().is_none(),
+        "Every input matrix should have been mixed in"
+    );
+
+    let final_poly = interpolate_coset(&current, shift, 1 << config.log_final_poly_len);
+    for &coeff in &final_poly {
+        challenger.observe_ext_element(coeff);
     }
-    todo!()
+    (commits, data, final_poly)
 }
 
 /// Fold a polynomial `p(x) = p_even(x^2) + x p_odd(x^2)` into `p_even(x) + beta * p_odd(x)`.
-fn fold_even_odd<F: Field>(_poly: &[F], _beta: F) -> Vec<F> {
-    todo!()
+///
+/// `poly` holds evaluations of `p` over the coset `shift H`, in bit-reversed order, so each
+/// evaluation at some `x` is immediately followed by the evaluation at `-x`. The result holds
+/// evaluations over `shift^2 H^2`, also in bit-reversed order.
+fn fold_even_odd<F, EF>(poly: &[EF], shift: F, beta: EF) -> Vec<EF>
+where
+    F: TwoAdicField,
+    EF: ExtensionField<F>,
+{
+    // We use the identities
+    //     p_even(x^2) = (p(x) + p(-x)) / 2
+    //     p_odd(x^2) = (p(x) - p(-x)) / (2 x)
+    let n = poly.len();
+    let log_n = log2_strict_usize(n);
+    let g_inv = F::primitive_root_of_unity(log_n).inverse();
+    let one_half = F::TWO.inverse();
+    let half_beta = beta * one_half;
+
+    // The inverse of the first point in each pair, i.e. `1 / (shift g^rev(i))`.
+    let shift_inv = shift.inverse();
+    let mut xs_inv: Vec<F> = g_inv.powers().take(n / 2).map(|p| p * shift_inv).collect();
+    reverse_slice_index_bits(&mut xs_inv);
+
+    poly.chunks_exact(2)
+        .zip(xs_inv)
+        .map(|(pair, x_inv)| {
+            let (lo, hi) = (pair[0], pair[1]);
+            (lo + hi) * one_half + (lo - hi) * half_beta * x_inv
+        })
+        .collect()
+}
+
+/// Given evaluations over the coset `shift H`, in bit-reversed order, returns the first `len`
+/// coefficients of the interpolating polynomial.
+fn interpolate_coset<F, EF>(evals: &[EF], shift: F, len: usize) -> Vec<EF>
+where
+    F: TwoAdicField,
+    EF: ExtensionField<F>,
+{
+    let n = evals.len();
+    let mut evals = evals.to_vec();
+    reverse_slice_index_bits(&mut evals);
+
+    // c_j = shift^-j / n * sum_i evals[i] g^-ij
+    let g_inv = F::primitive_root_of_unity(log2_strict_usize(n)).inverse();
+    let n_inv = F::from_canonical_usize(n).inverse();
+    g_inv
+        .powers()
+        .zip(shift.inverse().powers())
+        .take(len)
+        .map(|(g_inv_j, shift_inv_j)| {
+            let sum = g_inv_j
+                .powers()
+                .zip(&evals)
+                .fold(EF::ZERO, |acc, (x, &eval)| acc + eval * x);
+            sum * (n_inv * shift_inv_j)
+        })
+        .collect()
 }
 
 #[allow(clippy::needless_pass_by_value)]
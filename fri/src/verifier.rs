@@ -1,18 +1,260 @@
-use crate::FriProof;
+use crate::{FriConfig, FriProof, QueryProof};
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use itertools::Itertools;
 use p3_challenger::Challenger;
-use p3_commit::{DirectMMCS, MMCS};
-use p3_field::{ExtensionField, Field};
+use p3_commit::{Dimensions, DirectMMCS, MMCS};
+use p3_field::{AbstractExtensionField, ExtensionField, PrimeField64, TwoAdicField};
+use p3_ldt::LDTQueries;
+use p3_util::reverse_bits_len;
 
-pub(crate) fn verify<F, EF, M, MC, Chal>(
-    _proof: &FriProof<F, EF, M, MC>,
-    _challenger: &mut Chal,
-) -> Result<(), ()>
+#[derive(Debug)]
+pub enum FriError<InputMmcsErr, CommitMmcsErr> {
+    InvalidProofShape,
+    InputMmcsError(InputMmcsErr),
+    CommitPhaseMmcsError(CommitMmcsErr),
+    FinalPolyMismatch,
+    InvalidPowWitness,
+}
+
+pub(crate) fn verify<F, Challenge, M, MC, Chal>(
+    config: &FriConfig,
+    input_mmcs: &M,
+    commit_mmcs: &MC,
+    input_dims: &[Vec<Dimensions>],
+    input_commits: &[M::Commitment],
+    proof: &FriProof<F, Challenge, M, MC>,
+    challenger: &mut Chal,
+) -> Result<LDTQueries<F>, FriError<M::Error, MC::Error>>
 where
-    F: Field,
-    EF: ExtensionField<F>,
+    F: TwoAdicField + PrimeField64,
+    Challenge: ExtensionField<F>,
     M: MMCS<F>,
     MC: DirectMMCS<F>,
+    MC::Commitment: AsRef<[F]>,
     Chal: Challenger<F>,
 {
-    todo!()
+    if input_dims.len() != input_commits.len() {
+        return Err(FriError::InvalidProofShape);
+    }
+    let input_log_heights = input_dims
+        .iter()
+        .flatten()
+        .map(|dims| dims.log2_height)
+        .sorted_by_key(|&log_height| Reverse(log_height))
+        .collect_vec();
+    match input_log_heights.last() {
+        Some(&log_min_height) if log_min_height >= config.log_final_height() => {}
+        _ => return Err(FriError::InvalidProofShape),
+    }
+    let log_max_height = input_log_heights[0];
+    let log_arities = config.log_arities(&input_log_heights);
+    if proof.commit_phase_commits.len() != log_arities.len()
+        || proof.final_poly.len() != 1 << config.log_final_poly_len
+        || proof.query_proofs.len() != config.num_queries
+    {
+        return Err(FriError::InvalidProofShape);
+    }
+
+    // Replay the prover's transcript.
+    let alpha: Challenge = challenger.random_ext_element();
+    let betas: Vec<Challenge> = proof
+        .commit_phase_commits
+        .iter()
+        .map(|commit| {
+            challenger.observe_elements(commit.as_ref());
+            challenger.random_ext_element()
+        })
+        .collect();
+    for &coeff in &proof.final_poly {
+        challenger.observe_ext_element(coeff);
+    }
+
+    if !challenger.check_witness(config.proof_of_work_bits, proof.pow_witness) {
+        return Err(FriError::InvalidPowWitness);
+    }
+
+    let mut queries = Vec::with_capacity(proof.query_proofs.len());
+    for query_proof in &proof.query_proofs {
+        let index = challenger.random_bits(log_max_height);
+        verify_query(
+            input_mmcs,
+            commit_mmcs,
+            input_dims,
+            input_commits,
+            &proof.commit_phase_commits,
+            query_proof,
+            index,
+            log_max_height,
+            &log_arities,
+            alpha,
+            &betas,
+            &proof.final_poly,
+        )?;
+        queries.push((index, query_proof.leaves.clone()));
+    }
+
+    Ok(queries)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn verify_query<F, Challenge, M, MC>(
+    input_mmcs: &M,
+    commit_mmcs: &MC,
+    input_dims: &[Vec<Dimensions>],
+    input_commits: &[M::Commitment],
+    commit_phase_commits: &[MC::Commitment],
+    query_proof: &QueryProof<F, Challenge, M, MC>,
+    index: usize,
+    log_max_height: usize,
+    log_arities: &[usize],
+    alpha: Challenge,
+    betas: &[Challenge],
+    final_poly: &[Challenge],
+) -> Result<(), FriError<M::Error, MC::Error>>
+where
+    F: TwoAdicField,
+    Challenge: ExtensionField<F>,
+    M: MMCS<F>,
+    MC: DirectMMCS<F>,
+{
+    let num_matrices = input_dims.iter().map(Vec::len).sum::<usize>();
+    if query_proof.leaves.len() != num_matrices
+        || query_proof.leaf_opening_proofs.len() != input_commits.len()
+        || query_proof.steps.len() != log_arities.len()
+        || query_proof
+            .steps
+            .iter()
+            .zip(log_arities)
+            .any(|(step, &log_arity)| step.sibling_values.len() != (1 << log_arity) - 1)
+    {
+        return Err(FriError::InvalidProofShape);
+    }
+
+    // Check the opened rows of each input batch.
+    let mut leaves_remaining = &query_proof.leaves[..];
+    for ((commit, dims), opening_proof) in input_commits
+        .iter()
+        .zip(input_dims)
+        .zip(&query_proof.leaf_opening_proofs)
+    {
+        let (batch_leaves, rest) = leaves_remaining.split_at(dims.len());
+        leaves_remaining = rest;
+        if batch_leaves
+            .iter()
+            .zip(dims)
+            .any(|(leaf, dims)| leaf.len() != dims.width)
+        {
+            return Err(FriError::InvalidProofShape);
+        }
+        input_mmcs
+            .verify_batch(commit, dims, index, batch_leaves, opening_proof)
+            .map_err(FriError::InputMmcsError)?;
+    }
+
+    // Leaves are mixed in as soon as the folded codeword reaches their height, as in the prover.
+    let mut leaves_by_desc_height = input_dims
+        .iter()
+        .flatten()
+        .zip(&query_proof.leaves)
+        .sorted_by_key(|(dims, _)| Reverse(dims.log2_height))
+        .peekable();
+    let mut reduce_leaves = |current: &mut Challenge, log_height: usize| {
+        for (_, leaf) in
+            leaves_by_desc_height.peeking_take_while(|(dims, _)| dims.log2_height == log_height)
+        {
+            for &col in leaf {
+                *current = *current * alpha + col;
+            }
+        }
+    };
+
+    let mut current = Challenge::ZERO;
+    let mut shift = F::multiplicative_group_generator();
+    let mut log_height = log_max_height;
+
+    for (((&log_arity, commit), step), &beta) in log_arities
+        .iter()
+        .zip(commit_phase_commits)
+        .zip(&query_proof.steps)
+        .zip(betas)
+    {
+        reduce_leaves(&mut current, log_height);
+
+        let index_i = index >> (log_max_height - log_height);
+        let index_row = index_i >> log_arity;
+
+        let mut evals = step.sibling_values.clone();
+        evals.insert(index_i % (1 << log_arity), current);
+        let row = evals
+            .iter()
+            .flat_map(|x| {
+                AbstractExtensionField::<F>::as_base_slice(x)
+                    .iter()
+                    .copied()
+            })
+            .collect();
+        let dims = Dimensions {
+            width: <Challenge as AbstractExtensionField<F>>::D << log_arity,
+            log2_height: log_height - log_arity,
+        };
+        commit_mmcs
+            .verify_batch(commit, &[dims], index_row, &[row], &step.opening_proof)
+            .map_err(FriError::CommitPhaseMmcsError)?;
+
+        current = fold_row(index_row, log_height, log_arity, shift, beta, evals);
+        shift = shift.exp_power_of_2(log_arity);
+        log_height -= log_arity;
+    }
+    reduce_leaves(&mut current, log_height);
+
+    // The final codeword holds evaluations of the final polynomial over `shift H`.
+    let index_final = index >> (log_max_height - log_height);
+    let g = F::primitive_root_of_unity(log_height);
+    let x = shift * g.exp_u64(reverse_bits_len(index_final, log_height) as u64);
+    let final_eval = final_poly
+        .iter()
+        .rev()
+        .fold(Challenge::ZERO, |acc, &coeff| acc * x + coeff);
+
+    if current == final_eval {
+        Ok(())
+    } else {
+        Err(FriError::FinalPolyMismatch)
+    }
+}
+
+/// Fold the `2^log_arity` evaluations in row `index_row` of a layer into a single evaluation, by
+/// folding by two `log_arity` times, as the prover does.
+fn fold_row<F, EF>(
+    index_row: usize,
+    mut log_height: usize,
+    log_arity: usize,
+    mut shift: F,
+    mut beta: EF,
+    mut evals: Vec<EF>,
+) -> EF
+where
+    F: TwoAdicField,
+    EF: ExtensionField<F>,
+{
+    let one_half = F::TWO.inverse();
+    for log_remaining in (0..log_arity).rev() {
+        // Each pair holds evaluations at `x` and `-x`, where `x = shift g^rev(pair_index)`.
+        let g = F::primitive_root_of_unity(log_height);
+        evals = evals
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(m, pair)| {
+                let pair_index = (index_row << log_remaining) + m;
+                let x = shift * g.exp_u64(reverse_bits_len(pair_index, log_height - 1) as u64);
+                let (lo, hi) = (pair[0], pair[1]);
+                (lo + hi) * one_half + (lo - hi) * beta * (x + x).inverse()
+            })
+            .collect();
+        beta = beta.square();
+        shift = shift.square();
+        log_height -= 1;
+    }
+    evals[0]
 }
@@ -0,0 +1,229 @@
+use p3_challenger::{Challenger, DuplexChallenger};
+use p3_commit::{Dimensions, DirectMMCS};
+use p3_field::{AbstractField, TwoAdicField};
+use p3_fri::{FriConfig, FriError, FriLDT};
+use p3_goldilocks::Goldilocks;
+use p3_ldt::LDT;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_merkle_tree::MerkleTreeMMCS;
+use p3_symmetric::compression::TruncatedPermutation;
+use p3_symmetric::mds::NaiveMDSMatrix;
+use p3_symmetric::sponge::PaddingFreeSponge;
+use p3_util::{log2_strict_usize, reverse_slice_index_bits};
+use rand::distributions::{Distribution, Standard};
+use rand::{thread_rng, Rng};
+
+type Val = Goldilocks;
+type Challenge = Goldilocks; // TODO
+type Perm = NaiveMDSMatrix<Val, 8>; // TODO: Use a real permutation.
+type H4 = PaddingFreeSponge<Val, Perm, 8>;
+type C = TruncatedPermutation<Val, Perm, 2, 4, 8>;
+#[allow(clippy::upper_case_acronyms)]
+type MMCS = MerkleTreeMMCS<Val, [Val; 4], H4, C, RowMajorMatrix<Val>>;
+type Fri = FriLDT<Val, Challenge, MMCS, MMCS>;
+type Proof = <Fri as LDT<Val, MMCS>>::Proof;
+type Error = <Fri as LDT<Val, MMCS>>::Error;
+
+/// Evaluate random polynomials of degree less than `2^log_degree` over the coset `g H`, in
+/// bit-reversed order, where `H` has size `2^(log_degree + log_blowup)`.
+fn random_codewords<R: Rng>(
+    rng: &mut R,
+    log_degree: usize,
+    log_blowup: usize,
+    width: usize,
+) -> RowMajorMatrix<Val> {
+    let coeffs: Vec<Vec<Val>> = (0..width)
+        .map(|_| {
+            Standard
+                .sample_iter(&mut *rng)
+                .take(1 << log_degree)
+                .collect()
+        })
+        .collect();
+
+    let log_height = log_degree + log_blowup;
+    let shift = Val::multiplicative_group_generator();
+    let mut points: Vec<Val> = Val::primitive_root_of_unity(log_height)
+        .powers()
+        .take(1 << log_height)
+        .map(|x| shift * x)
+        .collect();
+    reverse_slice_index_bits(&mut points);
+
+    let values = points
+        .into_iter()
+        .flat_map(|x| {
+            coeffs
+                .iter()
+                .map(move |poly| poly.iter().rev().fold(Val::ZERO, |acc, &c| acc * x + c))
+        })
+        .collect();
+    RowMajorMatrix::new(values, width)
+}
+
+/// Commit to `codewords` in a single batch and prove their low degree, then apply `tamper` to the
+/// proof before verifying it.
+fn prove_and_verify(
+    config: FriConfig,
+    codewords: Vec<RowMajorMatrix<Val>>,
+    tamper: impl FnOnce(&mut Proof),
+) -> Result<(), Error> {
+    let mut rng = thread_rng();
+    let perm = Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())));
+    let mmcs = MMCS::new(H4::new(perm.clone()), C::new(perm.clone()), 2);
+    let commit_mmcs = MMCS::new(H4::new(perm.clone()), C::new(perm.clone()), 2);
+    let fri = Fri::new(config, commit_mmcs);
+
+    let dims = vec![codewords
+        .iter()
+        .map(|mat| Dimensions {
+            width: mat.width(),
+            log2_height: log2_strict_usize(mat.height()),
+        })
+        .collect()];
+    let (commit, data) = mmcs.commit(codewords);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    challenger.observe_elements(commit.as_ref());
+    let (mut proof, query_indices) = fri.prove(&mmcs, &[data], &mut challenger);
+    tamper(&mut proof);
+
+    let mut challenger = DuplexChallenger::new(perm);
+    challenger.observe_elements(commit.as_ref());
+    let queries = fri.verify(&mmcs, &dims, &[commit], &proof, &mut challenger)?;
+
+    // The verifier should derive the same query indices as the prover.
+    let verifier_indices: Vec<usize> = queries.into_iter().map(|(index, _)| index).collect();
+    assert_eq!(verifier_indices, query_indices);
+    Ok(())
+}
+
+fn default_config() -> FriConfig {
+    FriConfig {
+        log_blowup: 2,
+        num_queries: 10,
+        proof_of_work_bits: 8,
+        log_folding_factor: 2,
+        log_final_poly_len: 1,
+    }
+}
+
+fn default_codewords() -> Vec<RowMajorMatrix<Val>> {
+    let mut rng = thread_rng();
+    vec![
+        random_codewords(&mut rng, 5, 2, 3),
+        random_codewords(&mut rng, 3, 2, 2),
+    ]
+}
+
+#[test]
+fn test_fri_goldilocks() {
+    prove_and_verify(default_config(), default_codewords(), |_| {})
+        .expect("FRI verification failed");
+}
+
+#[test]
+fn test_fri_rejects_tampered_leaf() {
+    let result = prove_and_verify(default_config(), default_codewords(), |proof| {
+        proof.query_proofs[0].leaves[0][0] += Val::ONE;
+    });
+    assert!(matches!(result, Err(FriError::InputMmcsError(_))));
+}
+
+#[test]
+fn test_fri_rejects_tampered_sibling() {
+    let result = prove_and_verify(default_config(), default_codewords(), |proof| {
+        proof.query_proofs[0].steps[0].sibling_values[0] += Challenge::ONE;
+    });
+    assert!(matches!(result, Err(FriError::CommitPhaseMmcsError(_))));
+}
+
+#[test]
+fn test_fri_rejects_tampered_final_poly() {
+    // Tampering with the final polynomial changes the transcript, and so the query indices. A
+    // constant codeword opens to the same values at every index, so the openings stay valid and
+    // only the final polynomial check can catch the change. Without proof-of-work, the old witness
+    // stays valid too.
+    let config = FriConfig {
+        proof_of_work_bits: 0,
+        ..default_config()
+    };
+    let codewords = vec![random_codewords(&mut thread_rng(), 0, 5, 2)];
+    let result = prove_and_verify(config, codewords, |proof| {
+        proof.final_poly[0] += Challenge::ONE;
+    });
+    assert!(matches!(result, Err(FriError::FinalPolyMismatch)));
+}
+
+#[test]
+fn test_fri_rejects_wrong_shape() {
+    let tampers: [fn(&mut Proof); 4] = [
+        |proof| {
+            proof.commit_phase_commits.pop();
+        },
+        |proof| {
+            proof.final_poly.push(Challenge::ZERO);
+        },
+        |proof| {
+            proof.query_proofs.pop();
+        },
+        |proof| {
+            proof.query_proofs[0].steps[0].sibling_values.pop();
+        },
+    ];
+    for tamper in tampers {
+        let result = prove_and_verify(default_config(), default_codewords(), tamper);
+        assert!(matches!(result, Err(FriError::InvalidProofShape)));
+    }
+}
+
+#[test]
+fn test_fri_rejects_bad_pow_witness() {
+    // With 16 bits of proof-of-work, another witness passes only with probability 2^-16.
+    let config = FriConfig {
+        proof_of_work_bits: 16,
+        ..default_config()
+    };
+    let result = prove_and_verify(config, default_codewords(), |proof| {
+        proof.pow_witness += Val::ONE;
+    });
+    assert!(matches!(result, Err(FriError::InvalidPowWitness)));
+}
+
+#[test]
+fn test_fri_folding_factors() {
+    // Inputs of log heights 10, 7 and 5, which don't line up with most arities, so rounds must
+    // fold by less to stop at each input height.
+    for log_folding_factor in 1..=4 {
+        for log_final_poly_len in [0, 1, 3] {
+            let config = FriConfig {
+                log_folding_factor,
+                log_final_poly_len,
+                ..default_config()
+            };
+            let mut rng = thread_rng();
+            let codewords = vec![
+                random_codewords(&mut rng, 8, 2, 2),
+                random_codewords(&mut rng, 5, 2, 1),
+                random_codewords(&mut rng, 3, 2, 3),
+            ];
+            prove_and_verify(config, codewords, |_| {}).unwrap_or_else(|_| {
+                panic!(
+                    "FRI verification failed with log_folding_factor = {log_folding_factor} \
+                     and log_final_poly_len = {log_final_poly_len}"
+                )
+            });
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "at least as tall as the final codeword")]
+fn test_fri_rejects_short_codeword() {
+    let config = FriConfig {
+        log_final_poly_len: 4,
+        ..default_config()
+    };
+    prove_and_verify(config, default_codewords(), |_| {}).ok();
+}
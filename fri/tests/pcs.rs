@@ -0,0 +1,157 @@
+use p3_challenger::{Challenger, DuplexChallenger};
+use p3_commit::{Dimensions, UnivariatePCS, PCS};
+use p3_field::{AbstractField, TwoAdicField};
+use p3_fri::{FRIBasedPCS, FriConfig, FriLDT};
+use p3_goldilocks::Goldilocks;
+use p3_lde::NaiveCosetLDE;
+use p3_ldt::LDTBasedPCSError;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_merkle_tree::MerkleTreeMMCS;
+use p3_symmetric::compression::TruncatedPermutation;
+use p3_symmetric::mds::NaiveMDSMatrix;
+use p3_symmetric::sponge::PaddingFreeSponge;
+use p3_util::log2_strict_usize;
+use rand::distributions::{Distribution, Standard};
+use rand::{thread_rng, Rng};
+
+type Val = Goldilocks;
+type Challenge = Goldilocks; // TODO
+type Perm = NaiveMDSMatrix<Val, 8>; // TODO: Use a real permutation.
+type H4 = PaddingFreeSponge<Val, Perm, 8>;
+type C = TruncatedPermutation<Val, Perm, 2, 4, 8>;
+#[allow(clippy::upper_case_acronyms)]
+type MMCS = MerkleTreeMMCS<Val, [Val; 4], H4, C, RowMajorMatrix<Val>>;
+type Pcs = FRIBasedPCS<Val, Val, Challenge, NaiveCosetLDE, MMCS, MMCS>;
+type Error = <Pcs as PCS<Val, RowMajorMatrix<Val>>>::Error;
+
+fn random_matrix<R: Rng>(rng: &mut R, log_height: usize, width: usize) -> RowMajorMatrix<Val> {
+    let values = Standard
+        .sample_iter(rng)
+        .take(width << log_height)
+        .collect();
+    RowMajorMatrix::new(values, width)
+}
+
+/// Evaluate each column of `mat`, which holds evaluations over the subgroup of its height, at `z`
+/// by Lagrange interpolation.
+fn evaluate_columns(mat: &RowMajorMatrix<Val>, z: Val) -> Vec<Val> {
+    let subgroup: Vec<Val> = Val::primitive_root_of_unity(log2_strict_usize(mat.height()))
+        .powers()
+        .take(mat.height())
+        .collect();
+    let mut evals = vec![Val::ZERO; mat.width()];
+    for (i, (row, &x_i)) in mat.rows().zip(&subgroup).enumerate() {
+        let basis: Val = subgroup
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, &x_j)| (z - x_j) / (x_i - x_j))
+            .product();
+        for (eval, &y) in evals.iter_mut().zip(row) {
+            *eval += basis * y;
+        }
+    }
+    evals
+}
+
+/// Commit to `polys` in a single batch and open them at `points`, then apply `tamper` to the
+/// claimed values before verifying them. Returns the claimed values along with the result.
+fn open_and_verify(
+    polys: Vec<RowMajorMatrix<Val>>,
+    points: &[Val],
+    proof_of_work_bits: usize,
+    tamper: impl FnOnce(&mut Vec<Vec<Vec<Val>>>),
+) -> (Vec<Vec<Vec<Val>>>, Result<(), Error>) {
+    let mut rng = thread_rng();
+    let perm = Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())));
+    let added_bits = 2;
+    let config = FriConfig {
+        log_blowup: added_bits,
+        num_queries: 10,
+        proof_of_work_bits,
+        log_folding_factor: 1,
+        log_final_poly_len: 0,
+    };
+    let fri = FriLDT::new(
+        config,
+        MMCS::new(H4::new(perm.clone()), C::new(perm.clone()), 2),
+    );
+    let mmcs = MMCS::new(H4::new(perm.clone()), C::new(perm.clone()), 2);
+    let pcs = Pcs::new(NaiveCosetLDE, added_bits, mmcs, fri);
+
+    let dims = vec![polys
+        .iter()
+        .map(|mat| Dimensions {
+            width: mat.width(),
+            log2_height: log2_strict_usize(mat.height()),
+        })
+        .collect()];
+    let (commit, data) = pcs.commit_batches(polys);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    challenger.observe_elements(commit.as_ref());
+    let (mut values, proof) = <Pcs as UnivariatePCS<Val, RowMajorMatrix<Val>>>::open_multi_batches(
+        &pcs,
+        &[&data],
+        points,
+        &mut challenger,
+    );
+    tamper(&mut values);
+
+    let mut challenger = DuplexChallenger::new(perm);
+    challenger.observe_elements(commit.as_ref());
+    let result = <Pcs as UnivariatePCS<Val, RowMajorMatrix<Val>>>::verify_multi_batches(
+        &pcs,
+        &[commit],
+        &dims,
+        points,
+        &values,
+        &proof,
+        &mut challenger,
+    );
+    (values, result)
+}
+
+#[test]
+fn test_pcs_open_verify_goldilocks() {
+    let mut rng = thread_rng();
+    let polys = vec![random_matrix(&mut rng, 4, 3), random_matrix(&mut rng, 2, 2)];
+    let points: Vec<Val> = vec![rng.gen(), rng.gen()];
+
+    let (values, result) = open_and_verify(polys.clone(), &points, 8, |_| {});
+    result.expect("PCS verification failed");
+
+    // The values of a batch at each point are the evaluations of its matrices, concatenated.
+    for (&z, point_values) in points.iter().zip(&values[0]) {
+        let expected: Vec<Val> = polys
+            .iter()
+            .flat_map(|mat| evaluate_columns(mat, z))
+            .collect();
+        assert_eq!(point_values, &expected);
+    }
+}
+
+#[test]
+fn test_pcs_rejects_wrong_value() {
+    let mut rng = thread_rng();
+    let polys = vec![random_matrix(&mut rng, 4, 3), random_matrix(&mut rng, 2, 2)];
+    let points: Vec<Val> = vec![rng.gen(), rng.gen()];
+    let (_, result) = open_and_verify(polys, &points, 8, |values| {
+        values[0][1][3] += Val::ONE;
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_pcs_rejects_wrong_value_by_quotient() {
+    // A changed value also changes the transcript, so in the test above the LDT catches it first. The
+    // quotients of constant polynomials are zero, and their openings are the same at every index,
+    // so without proof-of-work only the DEEP check itself can catch the change.
+    let polys = vec![RowMajorMatrix::new(vec![Val::from_canonical_u32(7); 16], 1)];
+    let points: Vec<Val> = vec![thread_rng().gen()];
+    let (_, result) = open_and_verify(polys, &points, 0, |values| {
+        values[0][0][0] += Val::ONE;
+    });
+    assert!(matches!(result, Err(LDTBasedPCSError::QuotientMismatch)));
+}
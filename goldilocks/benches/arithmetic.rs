@@ -0,0 +1,64 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use p3_field::Field;
+use p3_goldilocks::Goldilocks;
+use p3_mersenne_31::Mersenne31;
+use rand::distributions::{Distribution, Standard};
+use rand::{thread_rng, Rng};
+use std::any::type_name;
+
+const BATCH_SIZE: usize = 1 << 10;
+
+fn bench_arithmetic(c: &mut Criterion) {
+    arithmetic::<Goldilocks>(c);
+    // For comparison.
+    arithmetic::<Mersenne31>(c);
+}
+
+fn arithmetic<F: Field>(c: &mut Criterion)
+where
+    Standard: Distribution<F>,
+{
+    let mut group = c.benchmark_group(format!("arithmetic::<{}>", type_name::<F>()));
+    let mut rng = thread_rng();
+    let mut random_pairs =
+        || -> Vec<(F, F)> { (0..BATCH_SIZE).map(|_| (rng.gen(), rng.gen())).collect() };
+
+    group.bench_function("add", |b| {
+        b.iter_batched(
+            &mut random_pairs,
+            |pairs| pairs.into_iter().map(|(x, y)| x + y).collect::<Vec<_>>(),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("sub", |b| {
+        b.iter_batched(
+            &mut random_pairs,
+            |pairs| pairs.into_iter().map(|(x, y)| x - y).collect::<Vec<_>>(),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("mul", |b| {
+        b.iter_batched(
+            &mut random_pairs,
+            |pairs| pairs.into_iter().map(|(x, y)| x * y).collect::<Vec<_>>(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    // A dependent chain, to measure latency rather than throughput.
+    let x: F = rng.gen();
+    group.bench_function("mul_chain", |b| {
+        b.iter(|| {
+            let mut acc = black_box(x);
+            for _ in 0..BATCH_SIZE {
+                acc *= x;
+            }
+            acc
+        })
+    });
+
+    group.bench_function("inverse", |b| b.iter(|| black_box(x).inverse()));
+}
+
+criterion_group!(benches, bench_arithmetic);
+criterion_main!(benches);
@@ -0,0 +1,197 @@
+use crate::Goldilocks;
+use core::arch::aarch64::*;
+use core::mem::transmute;
+use core::ops::{Add, Mul, Neg, Sub};
+use p3_field::{impl_packed_field_arithmetic, AbstractField, PackedField, PrimeField64};
+
+const WIDTH: usize = 2;
+const ORDER: uint64x2_t = unsafe { transmute([Goldilocks::ORDER_U64; WIDTH]) };
+/// `2^64 - ORDER = 2^32 - 1`, which is congruent to `2^64`.
+const EPSILON: uint64x2_t = unsafe { transmute([Goldilocks::NEG_ORDER; WIDTH]) };
+
+/// Vectorized NEON implementation of `Goldilocks` arithmetic.
+///
+/// Like `Goldilocks`, lanes may hold any `u64`, not necessarily a canonical one. NEON has no 64-bit
+/// multiplication, so products are computed lane by lane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)] // This is needed to make `transmute`s safe.
+pub struct PackedGoldilocksNeon(pub [Goldilocks; WIDTH]);
+
+impl PackedGoldilocksNeon {
+    #[inline]
+    #[must_use]
+    fn to_vector(self) -> uint64x2_t {
+        // SAFETY: `Self` is `repr(transparent)` over `[Goldilocks; WIDTH]`, which has the same size
+        // as `uint64x2_t`, and `Goldilocks` is `repr(transparent)` over `u64`.
+        unsafe { transmute(self) }
+    }
+
+    #[inline]
+    #[must_use]
+    fn from_vector(vector: uint64x2_t) -> Self {
+        // SAFETY: Every `u64` is a valid, if not necessarily canonical, `Goldilocks`.
+        unsafe { transmute(vector) }
+    }
+}
+
+/// Reduce each lane to canonical form, with a single conditional subtraction since `2 * ORDER`
+/// doesn't fit in a `u64`.
+#[inline]
+#[must_use]
+fn canonicalize(x: uint64x2_t) -> uint64x2_t {
+    unsafe {
+        let is_canonical = vcltq_u64(x, ORDER);
+        vsubq_u64(x, vbicq_u64(ORDER, is_canonical))
+    }
+}
+
+#[inline]
+#[must_use]
+fn add(lhs: uint64x2_t, rhs: uint64x2_t) -> uint64x2_t {
+    // See the AVX2 implementation; with `rhs` canonical, at most one correction is needed.
+    unsafe {
+        let rhs = canonicalize(rhs);
+        let sum = vaddq_u64(lhs, rhs);
+        let carry = vcltq_u64(sum, lhs);
+        vaddq_u64(sum, vandq_u64(carry, EPSILON))
+    }
+}
+
+#[inline]
+#[must_use]
+fn sub(lhs: uint64x2_t, rhs: uint64x2_t) -> uint64x2_t {
+    unsafe {
+        let rhs = canonicalize(rhs);
+        let diff = vsubq_u64(lhs, rhs);
+        let borrow = vcltq_u64(lhs, rhs);
+        vsubq_u64(diff, vandq_u64(borrow, EPSILON))
+    }
+}
+
+#[inline]
+#[must_use]
+fn neg(val: uint64x2_t) -> uint64x2_t {
+    unsafe { vsubq_u64(ORDER, canonicalize(val)) }
+}
+
+impl Add for PackedGoldilocksNeon {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::from_vector(add(self.to_vector(), rhs.to_vector()))
+    }
+}
+
+impl Sub for PackedGoldilocksNeon {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_vector(sub(self.to_vector(), rhs.to_vector()))
+    }
+}
+
+impl Neg for PackedGoldilocksNeon {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self::from_vector(neg(self.to_vector()))
+    }
+}
+
+impl Mul for PackedGoldilocksNeon {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self(core::array::from_fn(|i| self.0[i] * rhs.0[i]))
+    }
+}
+
+impl_packed_field_arithmetic!(PackedGoldilocksNeon, Goldilocks, WIDTH);
+
+unsafe impl PackedField for PackedGoldilocksNeon {
+    type Scalar = Goldilocks;
+
+    const WIDTH: usize = WIDTH;
+    const ZEROS: Self = Self::ZERO;
+    const ONES: Self = Self::ONE;
+
+    #[inline]
+    fn from_slice(slice: &[Goldilocks]) -> &Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Goldilocks; WIDTH]`.
+        unsafe { &*slice.as_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_slice_mut(slice: &mut [Goldilocks]) -> &mut Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Goldilocks; WIDTH]`.
+        unsafe { &mut *slice.as_mut_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> Goldilocks>(f: F) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[Goldilocks] {
+        &self.0
+    }
+
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [Goldilocks] {
+        &mut self.0
+    }
+
+    #[inline]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (a, b) = (self.to_vector(), other.to_vector());
+        let (a, b) = match block_len {
+            1 => unsafe { (vtrn1q_u64(a, b), vtrn2q_u64(a, b)) },
+            WIDTH => (a, b),
+            _ => panic!("unsupported block length"),
+        };
+        (Self::from_vector(a), Self::from_vector(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedGoldilocksNeon;
+    use crate::Goldilocks;
+    use p3_field::PrimeField64;
+    use p3_field_testing::check_packed_field;
+
+    type P = PackedGoldilocksNeon;
+
+    #[test]
+    fn packed_field() {
+        check_packed_field::<P>();
+    }
+
+    #[test]
+    fn non_canonical_lanes() {
+        // Values from ORDER up to 2^64 are valid, non-canonical encodings.
+        let values = [
+            Goldilocks::ORDER_U64,
+            Goldilocks::ORDER_U64 + 1,
+            u64::MAX - 1,
+            u64::MAX,
+        ]
+        .map(Goldilocks::new);
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (P::from(a), P::from(b));
+                assert_eq!((x + y).0[0], a + b);
+                assert_eq!((x - y).0[0], a - b);
+                assert_eq!((x * y).0[0], a * b);
+                assert_eq!((-x).0[0], -a);
+            }
+        }
+    }
+}
@@ -2,18 +2,31 @@
 
 #![no_std]
 
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod aarch64_neon;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+mod x86_64_avx2;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+pub use aarch64_neon::*;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+pub use x86_64_avx2::*;
+
 use core::fmt;
 use core::fmt::{Debug, Display, Formatter};
 use core::hash::{Hash, Hasher};
 use core::iter::{Product, Sum};
 use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
-use p3_field::{AbstractField, Field, PrimeField, PrimeField64, TwoAdicField};
+use p3_field::{
+    impl_packed_array_ops, AbstractField, Field, PrimeField, PrimeField64, TwoAdicField,
+};
 use p3_util::{assume, branch_hint};
 use rand::distributions::{Distribution, Standard};
 use rand::Rng;
 
 /// The prime field known as Goldilocks, defined as `F_p` where `p = 2^64 - 2^32 + 1`.
 #[derive(Copy, Clone, Default)]
+#[repr(transparent)] // Packed field implementations rely on this.
 pub struct Goldilocks {
     /// Not necessarily canonical.
     value: u64,
@@ -56,13 +69,13 @@ impl PartialOrd for Goldilocks {
 
 impl Display for Goldilocks {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        Display::fmt(&self.value, f)
+        Display::fmt(&self.as_canonical_u64(), f)
     }
 }
 
 impl Debug for Goldilocks {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        Debug::fmt(&self.value, f)
+        Debug::fmt(&self.as_canonical_u64(), f)
     }
 }
 
@@ -123,18 +136,44 @@ impl AbstractField for Goldilocks {
 }
 
 impl Field for Goldilocks {
-    // TODO: Add cfg-guarded Packing for AVX2, NEON, etc.
-    type Packing = Self;
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    type Packing = PackedGoldilocksNeon;
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+    type Packing = PackedGoldilocksAVX2;
+    #[cfg(not(any(
+        all(target_arch = "aarch64", target_feature = "neon"),
+        all(target_arch = "x86_64", target_feature = "avx2"),
+    )))]
+    type Packing = p3_field::PackedArray<Self, 4>;
 
     fn is_zero(&self) -> bool {
         self.value == 0 || self.value == Self::ORDER_U64
     }
 
     fn try_inverse(&self) -> Option<Self> {
-        todo!()
+        if self.is_zero() {
+            return None;
+        }
+
+        // Compute x^(p - 2) = x^(2^64 - 2^32 - 1), whose exponent is 31 ones, a zero, then 32
+        // ones in binary, with an addition chain of 63 squarings and 9 multiplications. Its
+        // sequence of operations doesn't depend on x, so it runs in constant time.
+        // The names below refer to the number of ones in the binary exponent of each power.
+        let t2 = self.square() * *self;
+        let t3 = t2.square() * *self;
+        let t6 = exp_acc::<3>(t3, t3);
+        let t12 = exp_acc::<6>(t6, t6);
+        let t24 = exp_acc::<12>(t12, t12);
+        let t30 = exp_acc::<6>(t24, t6);
+        let t31 = t30.square() * *self;
+        let t63 = exp_acc::<32>(t31, t31);
+        let t64 = t63.square() * *self;
+        Some(t64)
     }
 }
 
+impl_packed_array_ops!(Goldilocks);
+
 impl PrimeField for Goldilocks {}
 
 impl PrimeField64 for Goldilocks {
@@ -257,6 +296,12 @@ impl Div for Goldilocks {
     }
 }
 
+/// Squares `base` `N` times, then multiplies by `tail`.
+#[inline(always)]
+fn exp_acc<const N: usize>(base: Goldilocks, tail: Goldilocks) -> Goldilocks {
+    base.exp_power_of_2(N) * tail
+}
+
 /// Reduces to a 64-bit value. The result might not be in canonical form; it could be in between the
 /// field order and `2^64`.
 #[inline]
@@ -320,3 +365,130 @@ unsafe fn add_no_canonicalize_trashing_input(x: u64, y: u64) -> u64 {
     // Below cannot overflow unless the assumption if x + y < 2**64 + ORDER is incorrect.
     res_wrapped + Goldilocks::NEG_ORDER * u64::from(carry)
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use crate::Goldilocks;
+    use alloc::format;
+    use p3_field::{AbstractField, Field, PackedArray, PrimeField64, TwoAdicField};
+    use p3_field_testing::check_packed_field;
+
+    type F = Goldilocks;
+
+    /// The non-canonical encoding of `x`, i.e. `x + ORDER`, which is only valid for
+    /// `x < 2^32 - 1`.
+    fn non_canonical(x: u64) -> F {
+        F::new(x + F::ORDER_U64)
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(F::ONE + F::ONE, F::TWO);
+        assert_eq!(F::NEG_ONE + F::ONE, F::ZERO);
+        assert_eq!(F::NEG_ONE + F::TWO, F::ONE);
+        assert_eq!(F::NEG_ONE + F::NEG_ONE, F::new(F::ORDER_U64 - 2));
+        // Both inputs at the top of the non-canonical range overflow twice.
+        let max = F::new(u64::MAX);
+        assert_eq!(
+            max + max,
+            F::from_canonical_u64(2 * (u64::MAX - F::ORDER_U64))
+        );
+        assert_eq!(
+            non_canonical(5) + non_canonical(7),
+            F::from_canonical_u64(12)
+        );
+    }
+
+    #[test]
+    fn sub() {
+        assert_eq!(F::ONE - F::ONE, F::ZERO);
+        assert_eq!(F::TWO - F::TWO, F::ZERO);
+        assert_eq!(F::NEG_ONE - F::NEG_ONE, F::ZERO);
+        assert_eq!(F::TWO - F::ONE, F::ONE);
+        assert_eq!(F::NEG_ONE - F::ZERO, F::NEG_ONE);
+        assert_eq!(F::ZERO - F::ONE, F::NEG_ONE);
+        // A small input minus one at the top of the non-canonical range underflows twice.
+        assert_eq!(
+            F::ZERO - F::new(u64::MAX),
+            -F::from_canonical_u64(u64::MAX - F::ORDER_U64)
+        );
+        assert_eq!(non_canonical(3) - non_canonical(5), -F::TWO);
+    }
+
+    #[test]
+    fn mul() {
+        assert_eq!(F::TWO * F::TWO, F::from_canonical_u64(4));
+        assert_eq!(F::NEG_ONE * F::NEG_ONE, F::ONE);
+        assert_eq!(F::NEG_ONE * F::TWO, -F::TWO);
+        assert_eq!(
+            non_canonical(3) * non_canonical(5),
+            F::from_canonical_u64(15)
+        );
+        // 2^32 squared is 2^64 = 2^32 - 1 modulo p.
+        let two_32 = F::from_canonical_u64(1 << 32);
+        assert_eq!(two_32 * two_32, F::from_canonical_u64((1 << 32) - 1));
+        let max = F::new(u64::MAX);
+        let max_canonical = u128::from(max.as_canonical_u64());
+        assert_eq!(
+            max * max,
+            F::from_canonical_u64(
+                (max_canonical * max_canonical % u128::from(F::ORDER_U64)) as u64
+            )
+        );
+    }
+
+    #[test]
+    fn neg() {
+        assert_eq!(-F::ZERO, F::ZERO);
+        assert_eq!(-F::ONE, F::NEG_ONE);
+        assert_eq!(-non_canonical(1), F::NEG_ONE);
+    }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(F::ZERO.try_inverse(), None);
+        assert_eq!(non_canonical(0).try_inverse(), None);
+        assert_eq!(F::ONE.inverse(), F::ONE);
+        assert_eq!(F::NEG_ONE.inverse(), F::NEG_ONE);
+        assert_eq!(F::TWO.inverse() * F::TWO, F::ONE);
+        assert_eq!(
+            non_canonical(172).inverse() * F::from_canonical_u64(172),
+            F::ONE
+        );
+
+        let mut x = F::from_canonical_u64(0x1234_5678_9abc_def0);
+        for _ in 0..100 {
+            assert_eq!(x.inverse() * x, F::ONE);
+            x = x.square() + F::ONE;
+        }
+    }
+
+    #[test]
+    fn div() {
+        let six = F::from_canonical_u64(6);
+        assert_eq!(six / F::TWO, F::from_canonical_u64(3));
+        assert_eq!(F::ONE / F::NEG_ONE, F::NEG_ONE);
+    }
+
+    #[test]
+    fn power_of_two_generator() {
+        let g = F::power_of_two_generator();
+        assert_eq!(g.exp_power_of_2(F::TWO_ADICITY), F::ONE);
+        assert_ne!(g.exp_power_of_2(F::TWO_ADICITY - 1), F::ONE);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", non_canonical(5)), "5");
+        assert_eq!(format!("{:?}", F::NEG_ONE), format!("{}", F::ORDER_U64 - 1));
+    }
+
+    #[test]
+    fn packed_array() {
+        check_packed_field::<PackedArray<F, 1>>();
+        check_packed_field::<PackedArray<F, 4>>();
+        check_packed_field::<PackedArray<F, 8>>();
+    }
+}
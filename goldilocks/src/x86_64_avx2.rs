@@ -0,0 +1,280 @@
+use crate::Goldilocks;
+use core::arch::x86_64::*;
+use core::mem::transmute;
+use core::ops::{Add, Mul, Neg, Sub};
+use p3_field::{impl_packed_field_arithmetic, AbstractField, PackedField, PrimeField64};
+
+const WIDTH: usize = 4;
+const ORDER: __m256i = unsafe { transmute([Goldilocks::ORDER_U64; WIDTH]) };
+/// `2^64 - ORDER = 2^32 - 1`, which is congruent to `2^64`.
+const EPSILON: __m256i = unsafe { transmute([Goldilocks::NEG_ORDER; WIDTH]) };
+const SIGN_BIT: __m256i = unsafe { transmute([1u64 << 63; WIDTH]) };
+
+/// Vectorized AVX2 implementation of `Goldilocks` arithmetic.
+///
+/// Like `Goldilocks`, lanes may hold any `u64`, not necessarily a canonical one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)] // This is needed to make `transmute`s safe.
+pub struct PackedGoldilocksAVX2(pub [Goldilocks; WIDTH]);
+
+impl PackedGoldilocksAVX2 {
+    #[inline]
+    #[must_use]
+    fn to_vector(self) -> __m256i {
+        // SAFETY: `Self` is `repr(transparent)` over `[Goldilocks; WIDTH]`, which has the same size
+        // as `__m256i`, and `Goldilocks` is `repr(transparent)` over `u64`.
+        unsafe { transmute(self) }
+    }
+
+    #[inline]
+    #[must_use]
+    fn from_vector(vector: __m256i) -> Self {
+        // SAFETY: Every `u64` is a valid, if not necessarily canonical, `Goldilocks`.
+        unsafe { transmute(vector) }
+    }
+}
+
+/// Lanewise `x < y` for unsigned 64-bit lanes. AVX2 only has a signed comparison, so we flip the
+/// sign bits first.
+#[inline]
+#[must_use]
+fn lt_u64(x: __m256i, y: __m256i) -> __m256i {
+    unsafe { _mm256_cmpgt_epi64(_mm256_xor_si256(y, SIGN_BIT), _mm256_xor_si256(x, SIGN_BIT)) }
+}
+
+/// Reduce each lane to canonical form, with a single conditional subtraction since `2 * ORDER`
+/// doesn't fit in a `u64`.
+#[inline]
+#[must_use]
+fn canonicalize(x: __m256i) -> __m256i {
+    unsafe {
+        let is_canonical = lt_u64(x, ORDER);
+        _mm256_sub_epi64(x, _mm256_andnot_si256(is_canonical, ORDER))
+    }
+}
+
+#[inline]
+#[must_use]
+fn add(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // With `rhs` canonical, the sum is less than 2^64 + ORDER. If it overflows, the wrapped sum is
+    // less than ORDER, so adding EPSILON to account for the lost 2^64 can't overflow again.
+    unsafe {
+        let rhs = canonicalize(rhs);
+        let sum = _mm256_add_epi64(lhs, rhs);
+        let carry = lt_u64(sum, lhs);
+        _mm256_add_epi64(sum, _mm256_and_si256(carry, EPSILON))
+    }
+}
+
+#[inline]
+#[must_use]
+fn sub(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // With `rhs` canonical, an underflowed difference is more than 2^64 - ORDER = EPSILON, so
+    // subtracting EPSILON to account for the extra 2^64 can't underflow again.
+    unsafe {
+        let rhs = canonicalize(rhs);
+        let diff = _mm256_sub_epi64(lhs, rhs);
+        let borrow = lt_u64(lhs, rhs);
+        _mm256_sub_epi64(diff, _mm256_and_si256(borrow, EPSILON))
+    }
+}
+
+#[inline]
+#[must_use]
+fn neg(val: __m256i) -> __m256i {
+    unsafe { _mm256_sub_epi64(ORDER, canonicalize(val)) }
+}
+
+/// Compute the full 128-bit products of each pair of lanes, returned as their high and low halves.
+#[inline]
+#[must_use]
+fn mul64_64(x: __m256i, y: __m256i) -> (__m256i, __m256i) {
+    unsafe {
+        let x_hi = _mm256_srli_epi64::<32>(x);
+        let y_hi = _mm256_srli_epi64::<32>(y);
+
+        // `_mm256_mul_epu32` multiplies the low 32 bits of each 64-bit lane.
+        let prod_lo_lo = _mm256_mul_epu32(x, y);
+        let prod_lo_hi = _mm256_mul_epu32(x, y_hi);
+        let prod_hi_lo = _mm256_mul_epu32(x_hi, y);
+        let prod_hi_hi = _mm256_mul_epu32(x_hi, y_hi);
+
+        // Sum the middle terms, each at most (2^32 - 1)^2, along with the carries into them. Neither
+        // sum can overflow.
+        let mid_0 = _mm256_add_epi64(prod_lo_hi, _mm256_srli_epi64::<32>(prod_lo_lo));
+        let mid_1 = _mm256_add_epi64(prod_hi_lo, _mm256_and_si256(mid_0, EPSILON));
+
+        let lo = _mm256_blend_epi32::<0b10101010>(prod_lo_lo, _mm256_slli_epi64::<32>(mid_1));
+        let hi = _mm256_add_epi64(
+            prod_hi_hi,
+            _mm256_add_epi64(
+                _mm256_srli_epi64::<32>(mid_0),
+                _mm256_srli_epi64::<32>(mid_1),
+            ),
+        );
+        (hi, lo)
+    }
+}
+
+/// The vectorized equivalent of `reduce128`. The result might not be in canonical form.
+#[inline]
+#[must_use]
+fn reduce128(hi: __m256i, lo: __m256i) -> __m256i {
+    unsafe {
+        let hi_hi = _mm256_srli_epi64::<32>(hi);
+        let hi_lo = _mm256_and_si256(hi, EPSILON);
+
+        let t0 = _mm256_sub_epi64(lo, hi_hi);
+        let borrow = lt_u64(lo, hi_hi);
+        let t0 = _mm256_sub_epi64(t0, _mm256_and_si256(borrow, EPSILON));
+
+        // `hi_lo` and `EPSILON` both fit in 32 bits, so this is their full product.
+        let t1 = _mm256_mul_epu32(hi_lo, EPSILON);
+        let t2 = _mm256_add_epi64(t0, t1);
+        let carry = lt_u64(t2, t1);
+        _mm256_add_epi64(t2, _mm256_and_si256(carry, EPSILON))
+    }
+}
+
+#[inline]
+#[must_use]
+fn mul(lhs: __m256i, rhs: __m256i) -> __m256i {
+    let (hi, lo) = mul64_64(lhs, rhs);
+    reduce128(hi, lo)
+}
+
+impl Add for PackedGoldilocksAVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::from_vector(add(self.to_vector(), rhs.to_vector()))
+    }
+}
+
+impl Sub for PackedGoldilocksAVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_vector(sub(self.to_vector(), rhs.to_vector()))
+    }
+}
+
+impl Neg for PackedGoldilocksAVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self::from_vector(neg(self.to_vector()))
+    }
+}
+
+impl Mul for PackedGoldilocksAVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self::from_vector(mul(self.to_vector(), rhs.to_vector()))
+    }
+}
+
+impl_packed_field_arithmetic!(PackedGoldilocksAVX2, Goldilocks, WIDTH);
+
+/// Interleave the lanes of `a` and `b` in blocks of `block_len`; see `PackedField::interleave`.
+#[inline]
+#[must_use]
+fn interleave(a: __m256i, b: __m256i, block_len: usize) -> (__m256i, __m256i) {
+    unsafe {
+        match block_len {
+            1 => (_mm256_unpacklo_epi64(a, b), _mm256_unpackhi_epi64(a, b)),
+            2 => (
+                _mm256_permute2x128_si256::<0x20>(a, b),
+                _mm256_permute2x128_si256::<0x31>(a, b),
+            ),
+            WIDTH => (a, b),
+            _ => panic!("unsupported block length"),
+        }
+    }
+}
+
+unsafe impl PackedField for PackedGoldilocksAVX2 {
+    type Scalar = Goldilocks;
+
+    const WIDTH: usize = WIDTH;
+    const ZEROS: Self = Self::ZERO;
+    const ONES: Self = Self::ONE;
+
+    #[inline]
+    fn from_slice(slice: &[Goldilocks]) -> &Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Goldilocks; WIDTH]`.
+        unsafe { &*slice.as_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_slice_mut(slice: &mut [Goldilocks]) -> &mut Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Goldilocks; WIDTH]`.
+        unsafe { &mut *slice.as_mut_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> Goldilocks>(f: F) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[Goldilocks] {
+        &self.0
+    }
+
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [Goldilocks] {
+        &mut self.0
+    }
+
+    #[inline]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (a, b) = interleave(self.to_vector(), other.to_vector(), block_len);
+        (Self::from_vector(a), Self::from_vector(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedGoldilocksAVX2;
+    use crate::Goldilocks;
+    use p3_field::{PackedField, PrimeField64};
+    use p3_field_testing::check_packed_field;
+
+    type P = PackedGoldilocksAVX2;
+
+    #[test]
+    fn packed_field() {
+        check_packed_field::<P>();
+    }
+
+    #[test]
+    fn non_canonical_lanes() {
+        // Values from ORDER up to 2^64 are valid, non-canonical encodings.
+        let values = [
+            Goldilocks::ORDER_U64,
+            Goldilocks::ORDER_U64 + 1,
+            u64::MAX - 1,
+            u64::MAX,
+        ]
+        .map(Goldilocks::new);
+        let x = P::from_fn(|i| values[i]);
+        for j in 0..values.len() {
+            let y = P::from_fn(|i| values[(i + j) % values.len()]);
+            for i in 0..values.len() {
+                let (a, b) = (x.0[i], y.0[i]);
+                assert_eq!((x + y).0[i], a + b);
+                assert_eq!((x - y).0[i], a - b);
+                assert_eq!((x * y).0[i], a * b);
+                assert_eq!((-x).0[i], -a);
+            }
+        }
+    }
+}
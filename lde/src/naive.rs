@@ -53,7 +53,8 @@ where
         let weights = barycentric_weights(&subgroup);
 
         let lde_bits = bits + added_bits;
-        let lde_subgroup = cyclic_subgroup_known_order::<Dom>(g, 1 << lde_bits);
+        let lde_g = Dom::primitive_root_of_unity(lde_bits);
+        let lde_subgroup = cyclic_subgroup_known_order::<Dom>(lde_g, 1 << lde_bits);
 
         let polys_fe = polys.map(|x| Dom::from_base(x));
         let values = lde_subgroup
@@ -75,8 +76,9 @@ where
         let weights = barycentric_weights(&subgroup);
 
         let lde_bits = bits + added_bits;
+        let lde_g = Dom::primitive_root_of_unity(lde_bits);
         let lde_subgroup =
-            cyclic_subgroup_coset_known_order(g, self.shift(lde_bits), 1 << lde_bits);
+            cyclic_subgroup_coset_known_order(lde_g, self.shift(lde_bits), 1 << lde_bits);
 
         let polys_fe = polys.map(|x| Dom::from_base(x));
         let values = lde_subgroup
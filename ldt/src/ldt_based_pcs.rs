@@ -0,0 +1,418 @@
+use crate::LDT;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::slice;
+use p3_challenger::Challenger;
+use p3_commit::{Dimensions, DirectMMCS, UnivariatePCS, MMCS, PCS};
+use p3_field::{
+    batch_multiplicative_inverse, AbstractExtensionField, ExtensionField, Field, PrimeField64,
+    TwoAdicField,
+};
+use p3_lde::{TwoAdicCosetLDE, TwoAdicLDE};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::{Matrix, MatrixRows};
+use p3_util::{log2_strict_usize, reverse_bits_len};
+
+/// A PCS which commits to low-degree extensions of polynomials, and opens them by running an LDT on
+/// DEEP quotients `(p(X) - p(z)) / (X - z)`.
+///
+/// Extensions are committed with their rows in bit-reversed order, which is the layout that LDTs
+/// such as FRI expect. The LDT must also agree with `LDE` on the coset they are evaluated over.
+pub struct LDTBasedPCS<Val, Dom, LDE, M, L> {
+    lde: LDE,
+    added_bits: usize,
+    mmcs: M,
+    ldt: L,
+    _phantom_val: PhantomData<Val>,
+    _phantom_dom: PhantomData<Dom>,
+}
+
+impl<Val, Dom, LDE, M, L> LDTBasedPCS<Val, Dom, LDE, M, L> {
+    pub fn new(lde: LDE, added_bits: usize, mmcs: M, ldt: L) -> Self {
+        Self {
+            lde,
+            added_bits,
+            mmcs,
+            ldt,
+            _phantom_val: PhantomData,
+            _phantom_dom: PhantomData,
+        }
+    }
+}
+
+pub struct LDTBasedPCSProof<F, M, L>
+where
+    F: Field,
+    M: MMCS<F>,
+    L: LDT<F, M>,
+{
+    /// A commitment to the quotients, with one matrix per committed polynomial matrix. Each row
+    /// holds, for each opening point and then each column, the coefficients of a quotient value.
+    pub quotient_commit: M::Commitment,
+    pub ldt_proof: L::Proof,
+    /// Openings at each of the LDT's query indices, used to check that the quotients the LDT opened
+    /// there are consistent with the claimed values.
+    pub query_openings: Vec<QueryOpening<F, M>>,
+}
+
+pub struct QueryOpening<F: Field, M: MMCS<F>> {
+    /// The opened rows of each committed batch, along with an opening proof for each batch.
+    pub batch_openings: Vec<(Vec<Vec<F>>, M::Proof)>,
+}
+
+#[derive(Debug)]
+pub enum LDTBasedPCSError<MmcsErr, LdtErr> {
+    InvalidProofShape,
+    MmcsError(MmcsErr),
+    LdtError(LdtErr),
+    QuotientMismatch,
+}
+
+impl<Val, Dom, In, LDE, M, L> PCS<Val, In> for LDTBasedPCS<Val, Dom, LDE, M, L>
+where
+    Val: Field,
+    Dom: ExtensionField<Val> + TwoAdicField,
+    In: for<'a> MatrixRows<'a, Val>,
+    LDE: TwoAdicLDE<Val, Dom>,
+    M: DirectMMCS<Dom, Mat = RowMajorMatrix<Dom>>,
+    L: LDT<Dom, M>,
+{
+    type Commitment = M::Commitment;
+    type ProverData = M::ProverData;
+    type Proof = LDTBasedPCSProof<Dom, M, L>;
+    type Error = LDTBasedPCSError<M::Error, L::Error>;
+
+    /// The PCS is hiding if both its MMCS, which also commits to the DEEP quotients, and its LDT
+    /// are.
+    fn is_hiding(&self) -> bool {
+        self.mmcs.is_hiding() && self.ldt.is_hiding()
+    }
+
+    fn commit_batches(&self, polynomials: Vec<In>) -> (Self::Commitment, Self::ProverData) {
+        // TODO: Streaming?
+        let ldes = polynomials
+            .into_iter()
+            .map(|poly| {
+                let lde = self
+                    .lde
+                    .lde_batch(poly.to_row_major_matrix(), self.added_bits);
+                reverse_row_index_bits(&lde)
+            })
+            .collect();
+        self.mmcs.commit(ldes)
+    }
+}
+
+/// Openings are only supported when the LDE domain is `Val` itself, i.e. `Dom = Val`. The opening
+/// points and values live in extensions of `Val`, and the challenger and the LDT both run over
+/// `Val`, so the quotients can be committed and tested as `Val` codewords.
+impl<Val, In, LDE, M, L> UnivariatePCS<Val, In> for LDTBasedPCS<Val, Val, LDE, M, L>
+where
+    Val: TwoAdicField + PrimeField64,
+    In: for<'a> MatrixRows<'a, Val>,
+    LDE: TwoAdicCosetLDE<Val, Val>,
+    M: DirectMMCS<Val, Mat = RowMajorMatrix<Val>>,
+    M::Commitment: AsRef<[Val]>,
+    L: LDT<Val, M>,
+{
+    fn log_blowup(&self) -> usize {
+        self.added_bits
+    }
+
+    fn open_multi_batches<EF, Chal>(
+        &self,
+        prover_data: &[&Self::ProverData],
+        points: &[EF],
+        challenger: &mut Chal,
+    ) -> (Vec<Vec<Vec<EF>>>, Self::Proof)
+    where
+        EF: ExtensionField<Val>,
+        Chal: Challenger<Val> + Clone + Sync,
+    {
+        // Evaluate each matrix at each point, and compute the corresponding quotients over the
+        // matrix's extended domain. The evaluations of each batch's matrices are concatenated.
+        let mut quotients = vec![];
+        let values: Vec<Vec<Vec<EF>>> = prover_data
+            .iter()
+            .map(|data| {
+                let mut batch_values = vec![vec![]; points.len()];
+                for lde in M::get_matrices(data) {
+                    let (evals, quotient) = self.open_matrix(lde, points);
+                    for (point_values, point_evals) in batch_values.iter_mut().zip(evals) {
+                        point_values.extend(point_evals);
+                    }
+                    quotients.push(quotient);
+                }
+                batch_values
+            })
+            .collect();
+        for &value in values.iter().flatten().flatten() {
+            challenger.observe_ext_element(value);
+        }
+
+        let (quotient_commit, quotient_data) = self.mmcs.commit(quotients);
+        challenger.observe_elements(quotient_commit.as_ref());
+        let (ldt_proof, query_indices) =
+            self.ldt
+                .prove(&self.mmcs, slice::from_ref(&quotient_data), challenger);
+
+        // The LDT has already opened the quotients at its query indices, so we only need to open
+        // the original polynomials there.
+        let query_openings = query_indices
+            .into_iter()
+            .map(|index| QueryOpening {
+                batch_openings: prover_data
+                    .iter()
+                    .map(|data| self.mmcs.open_batch(index, data))
+                    .collect(),
+            })
+            .collect();
+
+        let proof = LDTBasedPCSProof {
+            quotient_commit,
+            ldt_proof,
+            query_openings,
+        };
+        (values, proof)
+    }
+
+    fn verify_multi_batches<EF, Chal>(
+        &self,
+        commits: &[Self::Commitment],
+        dims: &[Vec<Dimensions>],
+        points: &[EF],
+        values: &[Vec<Vec<EF>>],
+        proof: &Self::Proof,
+        challenger: &mut Chal,
+    ) -> Result<(), Self::Error>
+    where
+        EF: ExtensionField<Val>,
+        Chal: Challenger<Val>,
+    {
+        let shape_ok = commits.len() == dims.len()
+            && values.len() == dims.len()
+            && values.iter().zip(dims).all(|(batch_values, batch_dims)| {
+                let width = batch_dims.iter().map(|d| d.width).sum::<usize>();
+                batch_values.len() == points.len()
+                    && batch_values.iter().all(|vals| vals.len() == width)
+            });
+        if !shape_ok {
+            return Err(LDTBasedPCSError::InvalidProofShape);
+        }
+
+        for &value in values.iter().flatten().flatten() {
+            challenger.observe_ext_element(value);
+        }
+
+        let lde_dims: Vec<Vec<Dimensions>> = dims
+            .iter()
+            .map(|batch_dims| {
+                batch_dims
+                    .iter()
+                    .map(|d| Dimensions {
+                        width: d.width,
+                        log2_height: d.log2_height + self.added_bits,
+                    })
+                    .collect()
+            })
+            .collect();
+        let quotient_dims: Vec<Dimensions> = lde_dims
+            .iter()
+            .flatten()
+            .map(|d| Dimensions {
+                width: d.width * points.len() * <EF as AbstractExtensionField<Val>>::D,
+                log2_height: d.log2_height,
+            })
+            .collect();
+
+        challenger.observe_elements(proof.quotient_commit.as_ref());
+        let queries = self
+            .ldt
+            .verify(
+                &self.mmcs,
+                slice::from_ref(&quotient_dims),
+                slice::from_ref(&proof.quotient_commit),
+                &proof.ldt_proof,
+                challenger,
+            )
+            .map_err(LDTBasedPCSError::LdtError)?;
+
+        if proof.query_openings.len() != queries.len() {
+            return Err(LDTBasedPCSError::InvalidProofShape);
+        }
+        let log_max_height = quotient_dims
+            .iter()
+            .map(|d| d.log2_height)
+            .max()
+            .ok_or(LDTBasedPCSError::InvalidProofShape)?;
+        for ((index, quotient_rows), opening) in queries.iter().zip(&proof.query_openings) {
+            self.verify_query(
+                commits,
+                &lde_dims,
+                points,
+                values,
+                opening,
+                *index,
+                quotient_rows,
+                log_max_height,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Val, LDE, M, L> LDTBasedPCS<Val, Val, LDE, M, L>
+where
+    Val: TwoAdicField,
+    LDE: TwoAdicCosetLDE<Val, Val>,
+    M: DirectMMCS<Val, Mat = RowMajorMatrix<Val>>,
+    L: LDT<Val, M>,
+{
+    /// Evaluate each column of `lde` at each point, and compute the corresponding quotients over
+    /// the extended domain.
+    fn open_matrix<EF>(
+        &self,
+        lde: &RowMajorMatrix<Val>,
+        points: &[EF],
+    ) -> (Vec<Vec<EF>>, RowMajorMatrix<Val>)
+    where
+        EF: ExtensionField<Val>,
+    {
+        let height = lde.height();
+        let log_height = log2_strict_usize(height);
+        let shift = self.lde.shift(log_height);
+        let xs = coset_points_bit_reversed(shift, log_height);
+        let height_inv = Val::from_canonical_usize(height).inverse();
+
+        // For each point `z`, the evaluations `p(z)` along with the inverses `1 / (z - x)`.
+        let (evals, diff_invs): (Vec<Vec<EF>>, Vec<Vec<EF>>) = points
+            .iter()
+            .map(|&z| {
+                let diffs: Vec<EF> = xs.iter().map(|&x| z - x).collect();
+                let diff_invs = batch_multiplicative_inverse(&diffs);
+
+                // Barycentric evaluation over the coset `shift H`:
+                //     p(z) = ((z / shift)^n - 1) / n * sum_i p(x_i) x_i / (z - x_i)
+                let scale =
+                    ((z * shift.inverse()).exp_power_of_2(log_height) - EF::ONE) * height_inv;
+                let mut sums = vec![EF::ZERO; lde.width()];
+                for ((row, &x), &diff_inv) in lde.rows().zip(&xs).zip(&diff_invs) {
+                    let weight = diff_inv * x;
+                    for (sum, &val) in sums.iter_mut().zip(row) {
+                        *sum += weight * val;
+                    }
+                }
+                let evals = sums.into_iter().map(|sum| sum * scale).collect();
+                (evals, diff_invs)
+            })
+            .unzip();
+
+        // The quotient (p(x) - p(z)) / (x - z), flattened into base field coefficients.
+        let quotient_values = lde
+            .rows()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                let evals = &evals;
+                let diff_invs = &diff_invs;
+                (0..points.len()).flat_map(move |j| {
+                    row.iter().zip(&evals[j]).flat_map(move |(&val, &eval)| {
+                        let quotient = (eval - val) * diff_invs[j][i];
+                        quotient.as_base_slice().to_vec()
+                    })
+                })
+            })
+            .collect();
+        let quotient_width = lde.width() * points.len() * <EF as AbstractExtensionField<Val>>::D;
+        let quotients = RowMajorMatrix::new(quotient_values, quotient_width);
+
+        (evals, quotients)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn verify_query<EF>(
+        &self,
+        commits: &[M::Commitment],
+        lde_dims: &[Vec<Dimensions>],
+        points: &[EF],
+        values: &[Vec<Vec<EF>>],
+        opening: &QueryOpening<Val, M>,
+        index: usize,
+        quotient_rows: &[Vec<Val>],
+        log_max_height: usize,
+    ) -> Result<(), LDTBasedPCSError<M::Error, L::Error>>
+    where
+        EF: ExtensionField<Val>,
+    {
+        // The quotient rows were already authenticated by the LDT, which also checked their count.
+        if opening.batch_openings.len() != commits.len() {
+            return Err(LDTBasedPCSError::InvalidProofShape);
+        }
+
+        let mut quotient_rows = quotient_rows.iter();
+        for (((commit, batch_dims), batch_values), (rows, batch_proof)) in commits
+            .iter()
+            .zip(lde_dims)
+            .zip(values)
+            .zip(&opening.batch_openings)
+        {
+            if rows.len() != batch_dims.len() {
+                return Err(LDTBasedPCSError::InvalidProofShape);
+            }
+            self.mmcs
+                .verify_batch(commit, batch_dims, index, rows, batch_proof)
+                .map_err(LDTBasedPCSError::MmcsError)?;
+
+            let mut col_offset = 0;
+            for (row, d) in rows.iter().zip(batch_dims) {
+                let quotient_row = quotient_rows.next().unwrap();
+                if row.len() != d.width
+                    || quotient_row.len()
+                        != d.width * points.len() * <EF as AbstractExtensionField<Val>>::D
+                {
+                    return Err(LDTBasedPCSError::InvalidProofShape);
+                }
+
+                let log_height = d.log2_height;
+                let row_index = index >> (log_max_height - log_height);
+                let g = Val::primitive_root_of_unity(log_height);
+                let x = self.lde.shift(log_height)
+                    * g.exp_u64(reverse_bits_len(row_index, log_height) as u64);
+
+                let mut quotients = quotient_row
+                    .chunks_exact(<EF as AbstractExtensionField<Val>>::D)
+                    .map(EF::from_base_slice);
+                for (&z, point_values) in points.iter().zip(batch_values) {
+                    for (&val, &eval) in row.iter().zip(&point_values[col_offset..]) {
+                        let quotient = quotients
+                            .next()
+                            .ok_or(LDTBasedPCSError::InvalidProofShape)?;
+                        if quotient * (z - x) != eval - val {
+                            return Err(LDTBasedPCSError::QuotientMismatch);
+                        }
+                    }
+                }
+                col_offset += d.width;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The points of the coset `shift H`, where `H` has order `2^log_n`, in bit-reversed order.
+fn coset_points_bit_reversed<F: TwoAdicField>(shift: F, log_n: usize) -> Vec<F> {
+    let g = F::primitive_root_of_unity(log_n);
+    let mut points: Vec<F> = g.powers().take(1 << log_n).map(|x| shift * x).collect();
+    p3_util::reverse_slice_index_bits(&mut points);
+    points
+}
+
+fn reverse_row_index_bits<F: Clone>(mat: &RowMajorMatrix<F>) -> RowMajorMatrix<F> {
+    let log_height = log2_strict_usize(mat.height());
+    let values = (0..mat.height())
+        .flat_map(|r| mat.row(reverse_bits_len(r, log_height)).iter().cloned())
+        .collect();
+    RowMajorMatrix::new(values, mat.width())
+}
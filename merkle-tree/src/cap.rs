@@ -0,0 +1,43 @@
+use alloc::vec::Vec;
+use core::ops::Index;
+
+/// The digests of one layer of a Merkle tree, which together serve as a commitment in place of the
+/// tree's root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleCap<D> {
+    digests: Vec<D>,
+}
+
+impl<D> MerkleCap<D> {
+    pub fn new(digests: Vec<D>) -> Self {
+        Self { digests }
+    }
+
+    pub fn len(&self) -> usize {
+        self.digests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digests.is_empty()
+    }
+
+    pub fn digests(&self) -> &[D] {
+        &self.digests
+    }
+}
+
+impl<D> Index<usize> for MerkleCap<D> {
+    type Output = D;
+
+    fn index(&self, index: usize) -> &D {
+        &self.digests[index]
+    }
+}
+
+/// Caps of array digests can be viewed as a flat slice of their elements, e.g. for observing them
+/// with a challenger.
+impl<T, const N: usize> AsRef<[T]> for MerkleCap<[T; N]> {
+    fn as_ref(&self) -> &[T] {
+        self.digests.as_flattened()
+    }
+}
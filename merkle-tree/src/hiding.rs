@@ -0,0 +1,361 @@
+use crate::{
+    compression_layer, first_digest_layer, MerkleCap, MerkleTree, MerkleTreeError, MerkleTreeMMCS,
+};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::cmp::Reverse;
+use itertools::Itertools;
+use p3_commit::{Dimensions, DirectMMCS, MMCS};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::{Matrix, MatrixRows};
+use p3_symmetric::compression::PseudoCompressionFunction;
+use p3_symmetric::hasher::CryptographicHasher;
+use p3_util::log2_ceil_usize;
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+/// A hiding variant of `MerkleTreeMMCS`, for use in zero-knowledge protocols.
+///
+/// When committing, `SALT_ELEMS` fresh random elements are appended to each leaf, i.e. to the
+/// concatenated rows of the matrices of each height, before it is hashed. Openings include the
+/// salts, which the verifier hashes along with the opened rows. Without them, digests in an
+/// authentication path could be used to test guesses of unopened rows.
+///
+/// Salts are sampled from `rng`, which is injected so that tests can use a seeded RNG.
+pub struct HidingMerkleTreeMMCS<L, D, H, C, R, Mat, const SALT_ELEMS: usize> {
+    inner: MerkleTreeMMCS<L, D, H, C, Mat>,
+    rng: RefCell<R>,
+}
+
+impl<L, D, H, C, R, Mat, const SALT_ELEMS: usize>
+    HidingMerkleTreeMMCS<L, D, H, C, R, Mat, SALT_ELEMS>
+{
+    pub fn new(hash: H, compress: C, cap_height: usize, rng: R) -> Self {
+        assert!(
+            SALT_ELEMS > 0,
+            "A hiding MMCS needs at least one salt element"
+        );
+        Self {
+            inner: MerkleTreeMMCS::new(hash, compress, cap_height),
+            rng: RefCell::new(rng),
+        }
+    }
+}
+
+/// A Merkle tree whose leaves were salted, along with the salts.
+pub struct HidingMerkleTree<L, D, Mat>
+where
+    Mat: for<'a> MatrixRows<'a, L>,
+{
+    tree: MerkleTree<L, D, Mat>,
+    /// One matrix of salts per distinct matrix height, tallest first. Each has the same height as
+    /// the matrices it salts.
+    salts: Vec<RowMajorMatrix<L>>,
+}
+
+impl<L, D, Mat> HidingMerkleTree<L, D, Mat>
+where
+    L: Clone,
+    Mat: for<'a> MatrixRows<'a, L>,
+{
+    /// Open the salts of the leaves on the path of `index`, tallest first.
+    fn open_salts(&self, index: usize) -> Vec<Vec<L>> {
+        let log_max_height = self.tree.log_max_height();
+        self.salts
+            .iter()
+            .map(|salts| {
+                let log_height = log2_ceil_usize(salts.height());
+                salts.row(index >> (log_max_height - log_height)).to_vec()
+            })
+            .collect()
+    }
+}
+
+/// The dimensions of the salt matrices, for a batch of matrices with the given dimensions.
+///
+/// When verifying, these are appended to the matrices' dimensions, and the opened salts to their
+/// opened rows, so that the inner MMCS hashes each salt after the rows of the matrices it salts,
+/// just as they were hashed when committing.
+fn salt_dimensions(dimensions: &[Dimensions], salt_elems: usize) -> Vec<Dimensions> {
+    dimensions
+        .iter()
+        .map(|dims| dims.log2_height)
+        .sorted_by_key(|&log_height| Reverse(log_height))
+        .dedup()
+        .map(|log2_height| Dimensions {
+            width: salt_elems,
+            log2_height,
+        })
+        .collect()
+}
+
+/// Append the opened salts to the opened rows; see `salt_dimensions`.
+fn with_salts<L>(
+    opened_values: &[Vec<L>],
+    salts: &[Vec<L>],
+    salt_dims: &[Dimensions],
+) -> Result<Vec<Vec<L>>, MerkleTreeError>
+where
+    L: Clone,
+{
+    if salts.len() != salt_dims.len() {
+        return Err(MerkleTreeError::WrongBatchSize);
+    }
+    Ok(opened_values.iter().chain(salts).cloned().collect())
+}
+
+impl<L, D, H, C, R, Mat, const SALT_ELEMS: usize> MMCS<L>
+    for HidingMerkleTreeMMCS<L, D, H, C, R, Mat, SALT_ELEMS>
+where
+    L: Clone,
+    D: Copy + PartialEq,
+    H: CryptographicHasher<L, D>,
+    C: PseudoCompressionFunction<D, 2>,
+    Mat: for<'a> MatrixRows<'a, L>,
+{
+    type ProverData = HidingMerkleTree<L, D, Mat>;
+    type Commitment = MerkleCap<D>;
+    /// The opened salts, tallest first, along with the authentication path.
+    type Proof = (Vec<Vec<L>>, Vec<D>);
+    /// The opened salts for each index, along with the deduplicated siblings.
+    type MultiProof = (Vec<Vec<Vec<L>>>, Vec<D>);
+    type Error = MerkleTreeError;
+    type Mat = Mat;
+
+    fn is_hiding(&self) -> bool {
+        true
+    }
+
+    fn open_batch(
+        &self,
+        index: usize,
+        prover_data: &HidingMerkleTree<L, D, Mat>,
+    ) -> (Vec<Vec<L>>, Self::Proof) {
+        let (opened_values, path) = self.inner.open_batch(index, &prover_data.tree);
+        (opened_values, (prover_data.open_salts(index), path))
+    }
+
+    fn open_batch_multi(
+        &self,
+        indices: &[usize],
+        prover_data: &HidingMerkleTree<L, D, Mat>,
+    ) -> (Vec<Vec<Vec<L>>>, Self::MultiProof) {
+        let (opened_values, siblings) = self.inner.open_batch_multi(indices, &prover_data.tree);
+        let salts = indices
+            .iter()
+            .map(|&index| prover_data.open_salts(index))
+            .collect();
+        (opened_values, (salts, siblings))
+    }
+
+    fn get_matrices(prover_data: &Self::ProverData) -> &[Mat] {
+        &prover_data.tree.leaves
+    }
+
+    fn verify_batch(
+        &self,
+        commit: &MerkleCap<D>,
+        dimensions: &[Dimensions],
+        index: usize,
+        opened_values: &[Vec<L>],
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error> {
+        let (salts, path) = proof;
+        let salt_dims = salt_dimensions(dimensions, SALT_ELEMS);
+        let opened_values = with_salts(opened_values, salts, &salt_dims)?;
+        let dimensions = [dimensions, &salt_dims].concat();
+        self.inner
+            .verify_batch(commit, &dimensions, index, &opened_values, path)
+    }
+
+    fn verify_batch_multi(
+        &self,
+        commit: &MerkleCap<D>,
+        dimensions: &[Dimensions],
+        indices: &[usize],
+        opened_values: &[Vec<Vec<L>>],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error> {
+        let (salts, siblings) = proof;
+        if salts.len() != opened_values.len() {
+            return Err(MerkleTreeError::WrongBatchSize);
+        }
+
+        let salt_dims = salt_dimensions(dimensions, SALT_ELEMS);
+        let opened_values = opened_values
+            .iter()
+            .zip(salts)
+            .map(|(opened, salts)| with_salts(opened, salts, &salt_dims))
+            .collect::<Result<Vec<_>, _>>()?;
+        let dimensions = [dimensions, &salt_dims].concat();
+        self.inner
+            .verify_batch_multi(commit, &dimensions, indices, &opened_values, siblings)
+    }
+}
+
+impl<L, D, H, C, R, Mat, const SALT_ELEMS: usize> DirectMMCS<L>
+    for HidingMerkleTreeMMCS<L, D, H, C, R, Mat, SALT_ELEMS>
+where
+    L: Copy + Sync,
+    D: Copy + Default + PartialEq + Send + Sync,
+    H: CryptographicHasher<L, D> + Sync,
+    C: PseudoCompressionFunction<D, 2> + Sync,
+    R: Rng,
+    Mat: for<'a> MatrixRows<'a, L> + Sync,
+    Standard: Distribution<L>,
+{
+    fn commit(&self, inputs: Vec<Mat>) -> (Self::Commitment, Self::ProverData) {
+        let salts = {
+            let mut rng = self.rng.borrow_mut();
+            inputs
+                .iter()
+                .map(|m| m.height())
+                .sorted_by_key(|&height| Reverse(height))
+                .dedup()
+                .map(|height| RowMajorMatrix::rand(&mut *rng, height, SALT_ELEMS))
+                .collect_vec()
+        };
+
+        let hash = &self.inner.hash;
+        let hash_row = |matrices: &[&Mat], r: usize| {
+            let height = matrices[0].height();
+            let salts = salts.iter().find(|s| s.height() == height).unwrap();
+            hash.hash_iter(
+                matrices
+                    .iter()
+                    .flat_map(|m| m.row(r).into_iter().copied())
+                    .chain(salts.row(r).iter().copied()),
+            )
+        };
+        let tree = MerkleTree::new_with_layers(
+            inputs,
+            self.inner.cap_height,
+            |tallest_matrices, height_padded| {
+                first_digest_layer(tallest_matrices, height_padded, &hash_row)
+            },
+            |prev_layer, tallest_matrices| {
+                compression_layer(
+                    prev_layer,
+                    tallest_matrices,
+                    &self.inner.compress,
+                    &hash_row,
+                )
+            },
+        );
+
+        (tree.cap(), HidingMerkleTree { tree, salts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::HidingMerkleTreeMMCS;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use p3_commit::{Dimensions, DirectMMCS, MMCS};
+    use p3_keccak::{Keccak256Hash, KeccakF};
+    use p3_matrix::dense::RowMajorMatrix;
+    use p3_matrix::Matrix;
+    use p3_symmetric::compression::TruncatedPermutation;
+    use p3_util::log2_ceil_usize;
+    use rand::rngs::StdRng;
+    use rand::{thread_rng, SeedableRng};
+
+    type C = TruncatedPermutation<u8, KeccakF, 2, 32, 200>;
+    type Mmcs = HidingMerkleTreeMMCS<u8, [u8; 32], Keccak256Hash, C, StdRng, RowMajorMatrix<u8>, 4>;
+
+    fn mmcs(seed: u64, cap_height: usize) -> Mmcs {
+        Mmcs::new(
+            Keccak256Hash,
+            C::new(KeccakF),
+            cap_height,
+            StdRng::seed_from_u64(seed),
+        )
+    }
+
+    fn dimensions(mats: &[RowMajorMatrix<u8>]) -> Vec<Dimensions> {
+        mats.iter()
+            .map(|mat| Dimensions {
+                width: mat.width(),
+                log2_height: log2_ceil_usize(mat.height()),
+            })
+            .collect()
+    }
+
+    fn random_matrices() -> Vec<RowMajorMatrix<u8>> {
+        let mut rng = thread_rng();
+        vec![
+            RowMajorMatrix::rand(&mut rng, 16, 3),
+            RowMajorMatrix::rand(&mut rng, 4, 5),
+            RowMajorMatrix::rand(&mut rng, 16, 1),
+            RowMajorMatrix::rand(&mut rng, 2, 4),
+        ]
+    }
+
+    #[test]
+    fn salts_are_deterministic_given_the_rng() {
+        let mats = random_matrices();
+        let (commit_1, _) = mmcs(1, 0).commit(mats.clone());
+        let (commit_2, _) = mmcs(1, 0).commit(mats.clone());
+        let (commit_3, _) = mmcs(2, 0).commit(mats.clone());
+        assert_eq!(commit_1, commit_2);
+        assert_ne!(commit_1, commit_3);
+
+        // Committing to the same matrices again uses fresh salts.
+        let mmcs = mmcs(1, 0);
+        let (commit_4, _) = mmcs.commit(mats.clone());
+        let (commit_5, _) = mmcs.commit(mats);
+        assert_ne!(commit_4, commit_5);
+    }
+
+    #[test]
+    fn open_and_verify() {
+        let mats = random_matrices();
+        let dims = dimensions(&mats);
+        let mmcs = mmcs(0, 1);
+        let (commit, prover_data) = mmcs.commit(mats);
+
+        for index in 0..16 {
+            let (opened_values, proof) = mmcs.open_batch(index, &prover_data);
+            assert_eq!(proof.0.len(), 3);
+            mmcs.verify_batch(&commit, &dims, index, &opened_values, &proof)
+                .expect("Merkle verification failed");
+        }
+
+        let indices = [3, 0, 9, 3, 15];
+        let (opened_values, proof) = mmcs.open_batch_multi(&indices, &prover_data);
+        mmcs.verify_batch_multi(&commit, &dims, &indices, &opened_values, &proof)
+            .expect("Merkle multi-opening verification failed");
+    }
+
+    #[test]
+    fn reject_bad_salt() {
+        let mats = random_matrices();
+        let dims = dimensions(&mats);
+        let mmcs = mmcs(0, 0);
+        let (commit, prover_data) = mmcs.commit(mats);
+        let index = 6;
+        let (opened_values, proof) = mmcs.open_batch(index, &prover_data);
+
+        for i in 0..proof.0.len() {
+            let mut bad_proof = proof.clone();
+            bad_proof.0[i][0] ^= 1;
+            assert!(mmcs
+                .verify_batch(&commit, &dims, index, &opened_values, &bad_proof)
+                .is_err());
+        }
+
+        let mut bad_proof = proof.clone();
+        bad_proof.0.pop();
+        assert!(mmcs
+            .verify_batch(&commit, &dims, index, &opened_values, &bad_proof)
+            .is_err());
+
+        let indices = [1, 6];
+        let (opened_values, mut proof) = mmcs.open_batch_multi(&indices, &prover_data);
+        proof.0[1][2][0] ^= 1;
+        assert!(mmcs
+            .verify_batch_multi(&commit, &dims, &indices, &opened_values, &proof)
+            .is_err());
+    }
+}
@@ -2,20 +2,28 @@
 
 extern crate alloc;
 
+mod cap;
+mod hiding;
+mod multi_proof;
+mod packed;
+
+pub use cap::*;
+pub use hiding::*;
+pub use packed::*;
+
 use alloc::vec;
 use alloc::vec::Vec;
 use core::cmp::Reverse;
-use core::iter;
 use core::marker::PhantomData;
 use itertools::Itertools;
 use p3_commit::{Dimensions, DirectMMCS, MMCS};
 use p3_matrix::{Matrix, MatrixRows};
+use p3_maybe_rayon::{
+    IndexedParallelIterator, MaybeIntoParIter, MaybeParIterMut, ParallelIterator,
+};
 use p3_symmetric::compression::PseudoCompressionFunction;
 use p3_symmetric::hasher::CryptographicHasher;
-use p3_util::log2_ceil_usize;
-
-// TODO: Add a variant that supports pruning overlapping paths?
-// How would we keep track of previously-seen paths - make the MMCS methods take &mut self?
+use p3_util::{log2_ceil_usize, log2_strict_usize};
 
 /// A binary Merkle tree, with leaves of type `L` and digests of type `D`.
 ///
@@ -33,12 +41,48 @@ where
 {
     /// Matrix heights need not be powers of two. However, if the heights of two given matrices
     /// round up to the same power of two, they must be equal.
-    pub fn new<H, C>(h: &H, c: &C, leaves: Vec<Mat>) -> Self
+    ///
+    /// Each layer of digests is padded up to a power of two, and matrices are hashed into the layer
+    /// matching their padded height. Leaf hashing and each compression layer run in parallel.
+    ///
+    /// Compression stops at the layer of `2^cap_height` digests, which form the tree's cap. The cap
+    /// height is reduced if needed so that every matrix is hashed into a layer below the cap.
+    pub fn new<H, C>(h: &H, c: &C, leaves: Vec<Mat>, cap_height: usize) -> Self
+    where
+        L: Copy + Sync,
+        D: Copy + Default + Send + Sync,
+        H: CryptographicHasher<L, D> + Sync,
+        C: PseudoCompressionFunction<D, 2> + Sync,
+        Mat: Sync,
+    {
+        Self::new_with_layers(
+            leaves,
+            cap_height,
+            |tallest_matrices, height_padded| {
+                first_digest_layer(tallest_matrices, height_padded, &|matrices, r| {
+                    hash_rows(h, matrices, r)
+                })
+            },
+            |prev_layer, tallest_matrices| {
+                compression_layer(prev_layer, tallest_matrices, c, &|matrices, r| {
+                    hash_rows(h, matrices, r)
+                })
+            },
+        )
+    }
+
+    /// Build a tree, using `hash_leaves` to compute the first digest layer from the tallest
+    /// matrices, and `compress_layer` to compute each subsequent layer from the previous one and
+    /// the matrices injected at that layer.
+    pub(crate) fn new_with_layers<HL, CL>(
+        leaves: Vec<Mat>,
+        cap_height: usize,
+        hash_leaves: HL,
+        compress_layer: CL,
+    ) -> Self
     where
-        L: Copy,
-        D: Copy + Default,
-        H: CryptographicHasher<L, D>,
-        C: PseudoCompressionFunction<D, 2>,
+        HL: FnOnce(&[&Mat], usize) -> Vec<D>,
+        CL: Fn(&[D], &[&Mat]) -> Vec<D>,
     {
         assert!(!leaves.is_empty(), "No matrices given?");
 
@@ -49,38 +93,29 @@ where
             .sorted_by_key(|l| Reverse(l.height()))
             .peekable();
         let max_height = leaves_largest_first.peek().unwrap().height();
-        let max_height_padded = log2_ceil_usize(max_height);
+        let max_height_padded = max_height.next_power_of_two();
+        let min_height = leaves.iter().map(|m| m.height()).min().unwrap();
+        let cap_len = 1 << effective_cap_height(cap_height, log2_ceil_usize(min_height));
 
         let tallest_matrices = leaves_largest_first
             .peeking_take_while(|m| m.height() == max_height)
             .collect_vec();
 
-        let first_digest_layer = (0..max_height)
-            .map(|i| {
-                h.hash_iter(
-                    tallest_matrices
-                        .iter()
-                        .map(|m| m.row(i).into_iter().copied())
-                        .flatten(),
-                )
-            })
-            .chain(iter::repeat(D::default()))
-            .take(max_height_padded)
-            .collect_vec();
+        let first_digest_layer = hash_leaves(&tallest_matrices, max_height_padded);
 
         let mut digest_layers = vec![first_digest_layer];
         loop {
             let prev_layer = digest_layers.last().map(Vec::as_slice).unwrap_or_default();
-            if prev_layer.len() == 1 {
+            if prev_layer.len() == cap_len {
                 break;
             }
 
             // The matrices that get inserted at this layer.
             let tallest_matrices = leaves_largest_first
-                .peeking_take_while(|m| log2_ceil_usize(m.height()) == prev_layer.len())
+                .peeking_take_while(|m| m.height().next_power_of_two() == prev_layer.len() >> 1)
                 .collect_vec();
 
-            let next_digests = compression_layer(prev_layer, tallest_matrices, h, c);
+            let next_digests = compress_layer(prev_layer, &tallest_matrices);
             digest_layers.push(next_digests);
         }
 
@@ -92,69 +127,147 @@ where
     }
 
     #[must_use]
-    pub fn root(&self) -> D
+    pub fn cap(&self) -> MerkleCap<D>
     where
         D: Clone,
     {
-        self.digest_layers.last().unwrap()[0].clone()
+        MerkleCap::new(self.digest_layers.last().unwrap().clone())
+    }
+
+    /// The log of the height of the tallest matrix, rounded up to a power of two.
+    fn log_max_height(&self) -> usize {
+        log2_strict_usize(self.digest_layers[0].len())
     }
+
+    /// Open the row at `index` of each matrix, following the `MMCS` conventions for matrices of
+    /// different heights.
+    fn open_rows(&self, index: usize) -> Vec<Vec<L>>
+    where
+        L: Clone,
+    {
+        let log_max_height = self.log_max_height();
+        self.leaves
+            .iter()
+            .map(|matrix| {
+                let log_height = log2_ceil_usize(matrix.height());
+                let row = index >> (log_max_height - log_height);
+                matrix.row(row).into_iter().cloned().collect()
+            })
+            .collect()
+    }
+
+    /// The number of compression layers between the leaves and the cap.
+    fn num_compression_layers(&self) -> usize {
+        self.digest_layers.len() - 1
+    }
+}
+
+/// Hash the rows of the tallest matrices with `hash_row`, padding the result with default digests
+/// up to `height_padded`.
+pub(crate) fn first_digest_layer<L, D, Mat, HR>(
+    tallest_matrices: &[&Mat],
+    height_padded: usize,
+    hash_row: &HR,
+) -> Vec<D>
+where
+    D: Copy + Default + Send + Sync,
+    Mat: for<'a> MatrixRows<'a, L> + Sync,
+    HR: Fn(&[&Mat], usize) -> D + Sync,
+{
+    let height = tallest_matrices[0].height();
+    let mut digests = vec![D::default(); height_padded];
+    digests[..height]
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, digest)| *digest = hash_row(tallest_matrices, i));
+    digests
 }
 
 /// Compress `n` digests from the previous layer into `n/2` digests, while potentially mixing in
 /// some leaf data, if there are input matrices with (padded) height `n/2`.
-fn compression_layer<L, D, H, C, Mat>(
+pub(crate) fn compression_layer<L, D, C, Mat, HR>(
     prev_layer: &[D],
-    tallest_matrices: Vec<&Mat>,
-    h: &H,
+    tallest_matrices: &[&Mat],
     c: &C,
+    hash_row: &HR,
 ) -> Vec<D>
 where
-    L: Copy,
+    D: Copy + Default + Send + Sync,
+    C: PseudoCompressionFunction<D, 2> + Sync,
+    Mat: for<'a> MatrixRows<'a, L> + Sync,
+    HR: Fn(&[&Mat], usize) -> D + Sync,
+{
+    let next_len_padded = prev_layer.len() >> 1;
+    (0..next_len_padded)
+        .into_par_iter()
+        .map(|i| compress_node(prev_layer, tallest_matrices, i, c, hash_row))
+        .collect()
+}
+
+/// Compute node `i` of the next layer from its two children in `prev_layer`, mixing in row `i` of
+/// any matrices injected at that layer, as hashed by `hash_row`.
+fn compress_node<L, D, C, Mat, HR>(
+    prev_layer: &[D],
+    tallest_matrices: &[&Mat],
+    i: usize,
+    c: &C,
+    hash_row: &HR,
+) -> D
+where
     D: Copy + Default,
-    H: CryptographicHasher<L, D>,
     C: PseudoCompressionFunction<D, 2>,
     Mat: for<'a> MatrixRows<'a, L>,
+    HR: Fn(&[&Mat], usize) -> D,
 {
-    let next_len_padded = prev_layer.len() >> 1;
-    let mut next_digests = Vec::with_capacity(next_len_padded);
-
-    if tallest_matrices.is_empty() {
-        for i in 0..next_len_padded {
-            let left = prev_layer[2 * i];
-            let right = prev_layer[2 * i + 1];
-            let digest = c.compress([left, right]);
-            next_digests.push(digest);
-        }
-        return next_digests;
-    }
+    let left = prev_layer[2 * i];
+    let right = prev_layer[2 * i + 1];
+    let digest = c.compress([left, right]);
+    let Some(first_matrix) = tallest_matrices.first() else {
+        return digest;
+    };
+    let tallest_digest = if i < first_matrix.height() {
+        hash_row(tallest_matrices, i)
+    } else {
+        D::default()
+    };
+    c.compress([digest, tallest_digest])
+}
 
-    let next_len = tallest_matrices[0].height();
-    for i in 0..next_len {
-        let left = prev_layer[2 * i];
-        let right = prev_layer[2 * i + 1];
-        let mut digest = c.compress([left, right]);
-        let tallest_digest = h.hash_iter(
-            tallest_matrices
-                .iter()
-                .map(|m| m.row(i).into_iter().copied())
-                .flatten(),
-        );
-        digest = c.compress([digest, tallest_digest]);
-        next_digests.push(digest);
-    }
-    for i in next_len..next_len_padded {
-        let left = prev_layer[2 * i];
-        let right = prev_layer[2 * i + 1];
-        let mut digest = c.compress([left, right]);
-        let tallest_digest = D::default();
-        digest = c.compress([digest, tallest_digest]);
-        next_digests.push(digest);
-    }
-    next_digests
+/// The height of the cap actually used for a tree whose shortest matrix has (padded) height
+/// `2^log_min_height`. The cap can't be any higher, since every matrix must be hashed into a layer
+/// below it.
+fn effective_cap_height(cap_height: usize, log_min_height: usize) -> usize {
+    cap_height.min(log_min_height)
+}
+
+/// Hash row `r` of each of the given matrices, concatenated.
+fn hash_rows<L, D, H, Mat>(h: &H, matrices: &[&Mat], r: usize) -> D
+where
+    L: Copy,
+    H: CryptographicHasher<L, D>,
+    Mat: for<'a> MatrixRows<'a, L>,
+{
+    h.hash_iter(matrices.iter().flat_map(|m| m.row(r).into_iter().copied()))
+}
+
+#[derive(Debug)]
+pub enum MerkleTreeError {
+    WrongBatchSize,
+    WrongWidth,
+    /// The given heights are not valid for a Merkle tree, or don't match the proof.
+    WrongHeight,
+    IndexOutOfBounds,
+    RootMismatch,
+    /// Openings at two indices which share a row of some matrix disagree on that row.
+    InconsistentOpenings,
 }
 
 /// A vector commitment scheme backed by a Merkle tree.
 ///
+/// Rather than a single root, commitments are caps holding the `2^cap_height` digests of some layer
+/// below the root, which shortens each authentication path by `cap_height` digests. See
+/// `MerkleTree::new` for how the cap height is adjusted for short matrices.
+///
 /// Generics:
 /// - `L`: a leaf value
 /// - `D`: a digest
@@ -163,16 +276,18 @@ where
 pub struct MerkleTreeMMCS<L, D, H, C, Mat> {
     hash: H,
     compress: C,
+    cap_height: usize,
     _phantom_l: PhantomData<L>,
     _phantom_d: PhantomData<D>,
     _phantom_mat: PhantomData<Mat>,
 }
 
 impl<L, D, H, C, Mat> MerkleTreeMMCS<L, D, H, C, Mat> {
-    pub fn new(hash: H, compress: C) -> Self {
+    pub fn new(hash: H, compress: C, cap_height: usize) -> Self {
         Self {
             hash,
             compress,
+            cap_height,
             _phantom_l: PhantomData,
             _phantom_d: PhantomData,
             _phantom_mat: PhantomData,
@@ -180,27 +295,100 @@ impl<L, D, H, C, Mat> MerkleTreeMMCS<L, D, H, C, Mat> {
     }
 }
 
+impl<L, D, H, C, Mat> MerkleTreeMMCS<L, D, H, C, Mat>
+where
+    L: Clone,
+    H: CryptographicHasher<L, D>,
+{
+    /// Check that the opened rows match `dimensions`, and that `commit` is a cap of the expected
+    /// size. Returns the log of the tree's (padded) height, along with its cap height.
+    fn check_shape(
+        &self,
+        commit: &MerkleCap<D>,
+        dimensions: &[Dimensions],
+        opened_values: &[Vec<L>],
+    ) -> Result<(usize, usize), MerkleTreeError> {
+        if dimensions.is_empty() || dimensions.len() != opened_values.len() {
+            return Err(MerkleTreeError::WrongBatchSize);
+        }
+        if dimensions
+            .iter()
+            .zip(opened_values)
+            .any(|(dims, opened)| dims.width != opened.len())
+        {
+            return Err(MerkleTreeError::WrongWidth);
+        }
+
+        let (log_min_height, log_max_height) = dimensions
+            .iter()
+            .map(|dims| dims.log2_height)
+            .minmax()
+            .into_option()
+            .unwrap();
+        let cap_height = effective_cap_height(self.cap_height, log_min_height);
+        if commit.len() != 1 << cap_height {
+            return Err(MerkleTreeError::WrongHeight);
+        }
+        Ok((log_max_height, cap_height))
+    }
+
+    /// Hash the opened rows of the matrices with (padded) height `2^log_height`, if there are any.
+    fn hash_openings_at(
+        &self,
+        dimensions: &[Dimensions],
+        opened_values: &[Vec<L>],
+        log_height: usize,
+    ) -> Option<D> {
+        let mut openings = dimensions
+            .iter()
+            .zip(opened_values)
+            .filter(|(dims, _)| dims.log2_height == log_height)
+            .peekable();
+        openings.peek().is_some().then(|| {
+            self.hash
+                .hash_iter(openings.flat_map(|(_, opened)| opened.iter().cloned()))
+        })
+    }
+}
+
 impl<L, D, H, C, Mat> MMCS<L> for MerkleTreeMMCS<L, D, H, C, Mat>
 where
     L: Clone,
+    D: Copy + PartialEq,
     H: CryptographicHasher<L, D>,
     C: PseudoCompressionFunction<D, 2>,
     Mat: for<'a> MatrixRows<'a, L>,
 {
     type ProverData = MerkleTree<L, D, Mat>;
-    type Commitment = D;
+    type Commitment = MerkleCap<D>;
     type Proof = Vec<D>;
-    type Error = ();
+    type MultiProof = Vec<D>;
+    type Error = MerkleTreeError;
     type Mat = Mat;
 
-    fn open_batch(row: usize, prover_data: &MerkleTree<L, D, Mat>) -> (Vec<Vec<L>>, Vec<D>) {
-        let leaf = prover_data
-            .leaves
+    fn open_batch(
+        &self,
+        index: usize,
+        prover_data: &MerkleTree<L, D, Mat>,
+    ) -> (Vec<Vec<L>>, Vec<D>) {
+        // The sibling of the current node in each layer, from the leaves up to the cap.
+        let proof = (0..prover_data.num_compression_layers())
+            .map(|i| prover_data.digest_layers[i][(index >> i) ^ 1])
+            .collect();
+
+        (prover_data.open_rows(index), proof)
+    }
+
+    fn open_batch_multi(
+        &self,
+        indices: &[usize],
+        prover_data: &MerkleTree<L, D, Mat>,
+    ) -> (Vec<Vec<Vec<L>>>, Vec<D>) {
+        let openings = indices
             .iter()
-            .map(|matrix| matrix.row(row).into_iter().cloned().collect())
+            .map(|&index| prover_data.open_rows(index))
             .collect();
-        let proof = vec![]; // TODO
-        (leaf, proof)
+        (openings, prover_data.multi_proof(indices))
     }
 
     fn get_matrices(prover_data: &Self::ProverData) -> &[Mat] {
@@ -208,28 +396,71 @@ where
     }
 
     fn verify_batch(
-        _commit: &D,
-        _dimensions: &[Dimensions],
-        _index: usize,
-        _item: Vec<L>,
-        _proof: &Vec<D>,
+        &self,
+        commit: &MerkleCap<D>,
+        dimensions: &[Dimensions],
+        index: usize,
+        opened_values: &[Vec<L>],
+        proof: &Vec<D>,
     ) -> Result<(), Self::Error> {
-        todo!()
+        let (log_max_height, cap_height) = self.check_shape(commit, dimensions, opened_values)?;
+        if proof.len() != log_max_height - cap_height {
+            return Err(MerkleTreeError::WrongHeight);
+        }
+        if index >> log_max_height != 0 {
+            return Err(MerkleTreeError::IndexOutOfBounds);
+        }
+
+        let mut digest = self
+            .hash_openings_at(dimensions, opened_values, log_max_height)
+            .unwrap();
+        for (i, &sibling) in proof.iter().enumerate() {
+            let pair = if (index >> i) & 1 == 0 {
+                [digest, sibling]
+            } else {
+                [sibling, digest]
+            };
+            digest = self.compress.compress(pair);
+
+            // Inject the openings of any matrices whose padded height matches this layer.
+            let log_height = log_max_height - i - 1;
+            if let Some(opened_digest) =
+                self.hash_openings_at(dimensions, opened_values, log_height)
+            {
+                digest = self.compress.compress([digest, opened_digest]);
+            }
+        }
+
+        if digest == commit[index >> proof.len()] {
+            Ok(())
+        } else {
+            Err(MerkleTreeError::RootMismatch)
+        }
+    }
+
+    fn verify_batch_multi(
+        &self,
+        commit: &MerkleCap<D>,
+        dimensions: &[Dimensions],
+        indices: &[usize],
+        opened_values: &[Vec<Vec<L>>],
+        proof: &Vec<D>,
+    ) -> Result<(), Self::Error> {
+        self.verify_multi_proof(commit, dimensions, indices, opened_values, proof)
     }
 }
 
 impl<L, D, H, C, Mat> DirectMMCS<L> for MerkleTreeMMCS<L, D, H, C, Mat>
 where
-    L: Copy,
-    D: Copy + Default,
-    H: CryptographicHasher<L, D>,
-    C: PseudoCompressionFunction<D, 2>,
-    Mat: for<'a> MatrixRows<'a, L>,
+    L: Copy + Sync,
+    D: Copy + Default + PartialEq + Send + Sync,
+    H: CryptographicHasher<L, D> + Sync,
+    C: PseudoCompressionFunction<D, 2> + Sync,
+    Mat: for<'a> MatrixRows<'a, L> + Sync,
 {
     fn commit(&self, inputs: Vec<Mat>) -> (Self::Commitment, Self::ProverData) {
-        let tree = MerkleTree::new(&self.hash, &self.compress, inputs);
-        let root = tree.root();
-        (root, tree)
+        let tree = MerkleTree::new(&self.hash, &self.compress, inputs, self.cap_height);
+        (tree.cap(), tree)
     }
 }
 
@@ -237,21 +468,30 @@ where
 mod tests {
     use crate::MerkleTreeMMCS;
     use alloc::vec;
-    use p3_commit::DirectMMCS;
-    use p3_keccak::Keccak256Hash;
+    use alloc::vec::Vec;
+    use p3_commit::{Dimensions, DirectMMCS, MMCS};
+    use p3_keccak::{Keccak256Hash, KeccakF};
     use p3_matrix::dense::RowMajorMatrix;
+    use p3_matrix::{Matrix, MatrixRows};
     use p3_symmetric::compression::TruncatedPermutation;
+    use p3_util::log2_ceil_usize;
     use rand::thread_rng;
 
-    #[test]
-    fn commit() {
-        use p3_keccak::KeccakF;
+    type C = TruncatedPermutation<u8, KeccakF, 2, 32, 200>;
+    type Mmcs = MerkleTreeMMCS<u8, [u8; 32], Keccak256Hash, C, RowMajorMatrix<u8>>;
 
-        type C = TruncatedPermutation<u8, KeccakF, 2, 32, 200>;
-        let compress = C::new(KeccakF);
+    fn dimensions(mats: &[RowMajorMatrix<u8>]) -> Vec<Dimensions> {
+        mats.iter()
+            .map(|mat| Dimensions {
+                width: mat.width(),
+                log2_height: log2_ceil_usize(mat.height()),
+            })
+            .collect()
+    }
 
-        type Mmcs = MerkleTreeMMCS<u8, [u8; 32], Keccak256Hash, C, RowMajorMatrix<u8>>;
-        let mmcs = Mmcs::new(Keccak256Hash, compress);
+    #[test]
+    fn commit() {
+        let mmcs = Mmcs::new(Keccak256Hash, C::new(KeccakF), 0);
 
         let mut rng = thread_rng();
 
@@ -263,4 +503,87 @@ mod tests {
         let mat = RowMajorMatrix::rand(&mut rng, 200, 13);
         mmcs.commit(vec![mat]);
     }
+
+    #[test]
+    fn open_and_verify() {
+        let mmcs = Mmcs::new(Keccak256Hash, C::new(KeccakF), 0);
+        let mut rng = thread_rng();
+
+        // Matrices of several heights, including two which share a height.
+        let mats = vec![
+            RowMajorMatrix::rand(&mut rng, 16, 3),
+            RowMajorMatrix::rand(&mut rng, 4, 5),
+            RowMajorMatrix::rand(&mut rng, 64, 2),
+            RowMajorMatrix::rand(&mut rng, 16, 1),
+            RowMajorMatrix::rand(&mut rng, 1, 4),
+        ];
+        let dims = dimensions(&mats);
+        let (commit, prover_data) = mmcs.commit(mats);
+
+        for index in 0..64 {
+            let (opened_values, proof) = mmcs.open_batch(index, &prover_data);
+            assert_eq!(opened_values[0], prover_data.leaves[0].row(index >> 2));
+            assert_eq!(opened_values[1], prover_data.leaves[1].row(index >> 4));
+            mmcs.verify_batch(&commit, &dims, index, &opened_values, &proof)
+                .expect("Merkle verification failed");
+        }
+    }
+
+    #[test]
+    fn reject_bad_opening() {
+        let mmcs = Mmcs::new(Keccak256Hash, C::new(KeccakF), 1);
+        let mut rng = thread_rng();
+
+        let mats = vec![
+            RowMajorMatrix::rand(&mut rng, 32, 3),
+            RowMajorMatrix::rand(&mut rng, 8, 2),
+        ];
+        let dims = dimensions(&mats);
+        let (commit, prover_data) = mmcs.commit(mats);
+        let index = 13;
+        let (opened_values, proof) = mmcs.open_batch(index, &prover_data);
+
+        let mut bad_values = opened_values.clone();
+        bad_values[1][0] ^= 1;
+        assert!(mmcs
+            .verify_batch(&commit, &dims, index, &bad_values, &proof)
+            .is_err());
+
+        let mut bad_proof = proof.clone();
+        bad_proof[2][0] ^= 1;
+        assert!(mmcs
+            .verify_batch(&commit, &dims, index, &opened_values, &bad_proof)
+            .is_err());
+
+        assert!(mmcs
+            .verify_batch(&commit, &dims, index ^ 1, &opened_values, &proof)
+            .is_err());
+        assert!(mmcs
+            .verify_batch(&commit, &dims[..1], index, &opened_values[..1], &proof)
+            .is_err());
+    }
+
+    #[test]
+    fn caps() {
+        let mut rng = thread_rng();
+        let mats = vec![
+            RowMajorMatrix::rand(&mut rng, 64, 3),
+            RowMajorMatrix::rand(&mut rng, 16, 2),
+        ];
+        let dims = dimensions(&mats);
+
+        // The cap can't be higher than the shorter matrix.
+        for (cap_height, expected_cap_len) in [(0, 1), (1, 2), (3, 8), (4, 16), (6, 16)] {
+            let mmcs = Mmcs::new(Keccak256Hash, C::new(KeccakF), cap_height);
+            let (commit, prover_data) = mmcs.commit(mats.clone());
+            assert_eq!(commit.len(), expected_cap_len);
+
+            for index in 0..64 {
+                let (opened_values, proof) = mmcs.open_batch(index, &prover_data);
+                assert_eq!(1 << proof.len(), 64 / expected_cap_len);
+                mmcs.verify_batch(&commit, &dims, index, &opened_values, &proof)
+                    .expect("Merkle verification failed");
+            }
+        }
+    }
 }
@@ -0,0 +1,248 @@
+use crate::{MerkleCap, MerkleTree, MerkleTreeError, MerkleTreeMMCS};
+use alloc::collections::btree_map::Entry;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use itertools::Itertools;
+use p3_commit::Dimensions;
+use p3_matrix::MatrixRows;
+use p3_symmetric::compression::PseudoCompressionFunction;
+use p3_symmetric::hasher::CryptographicHasher;
+
+impl<L, D, Mat> MerkleTree<L, D, Mat>
+where
+    D: Copy,
+    Mat: for<'a> MatrixRows<'a, L>,
+{
+    /// The siblings needed to authenticate the leaves at all of `indices` together, layer by layer
+    /// from the leaves up to the cap, and in increasing order of position within each layer.
+    ///
+    /// A sibling is omitted if it lies on the path of another index, since the verifier computes it
+    /// anyway.
+    pub(crate) fn multi_proof(&self, indices: &[usize]) -> Vec<D> {
+        let mut proof = vec![];
+        let mut positions = indices.iter().copied().sorted().dedup().collect_vec();
+        for layer in &self.digest_layers[..self.num_compression_layers()] {
+            let mut i = 0;
+            while i < positions.len() {
+                let pos = positions[i];
+                if pos & 1 == 0 && positions.get(i + 1) == Some(&(pos + 1)) {
+                    i += 2;
+                } else {
+                    proof.push(layer[pos ^ 1]);
+                    i += 1;
+                }
+            }
+            positions = positions.into_iter().map(|pos| pos >> 1).dedup().collect();
+        }
+        proof
+    }
+}
+
+impl<L, D, H, C, Mat> MerkleTreeMMCS<L, D, H, C, Mat>
+where
+    L: Clone,
+    D: Copy + PartialEq,
+    H: CryptographicHasher<L, D>,
+    C: PseudoCompressionFunction<D, 2>,
+{
+    pub(crate) fn verify_multi_proof(
+        &self,
+        commit: &MerkleCap<D>,
+        dimensions: &[Dimensions],
+        indices: &[usize],
+        opened_values: &[Vec<Vec<L>>],
+        proof: &[D],
+    ) -> Result<(), MerkleTreeError> {
+        if indices.is_empty() || indices.len() != opened_values.len() {
+            return Err(MerkleTreeError::WrongBatchSize);
+        }
+        let mut shape = (0, 0);
+        for opened in opened_values {
+            shape = self.check_shape(commit, dimensions, opened)?;
+        }
+        let (log_max_height, cap_height) = shape;
+        if indices.iter().any(|&index| index >> log_max_height != 0) {
+            return Err(MerkleTreeError::IndexOutOfBounds);
+        }
+
+        // Hash the openings of matrices with height `2^log_height`, keyed by their position in that
+        // layer. Indices which share a position must agree on the opened rows.
+        let hash_openings_at = |log_height: usize| {
+            let mut digests = BTreeMap::new();
+            for (&index, opened) in indices.iter().zip(opened_values) {
+                let Some(digest) = self.hash_openings_at(dimensions, opened, log_height) else {
+                    continue;
+                };
+                match digests.entry(index >> (log_max_height - log_height)) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(digest);
+                    }
+                    Entry::Occupied(entry) if *entry.get() != digest => {
+                        return Err(MerkleTreeError::InconsistentOpenings);
+                    }
+                    Entry::Occupied(_) => {}
+                }
+            }
+            Ok(digests)
+        };
+
+        // The known digests of the current layer, by position.
+        let mut layer = hash_openings_at(log_max_height)?;
+        let mut siblings = proof.iter().copied();
+        for log_height in (cap_height..log_max_height).rev() {
+            let mut next_layer = BTreeMap::new();
+            let mut nodes = layer.into_iter().peekable();
+            while let Some((pos, digest)) = nodes.next() {
+                let pair = if pos & 1 == 0 {
+                    let right = match nodes.next_if(|&(next_pos, _)| next_pos == pos + 1) {
+                        Some((_, right)) => right,
+                        None => siblings.next().ok_or(MerkleTreeError::WrongHeight)?,
+                    };
+                    [digest, right]
+                } else {
+                    let left = siblings.next().ok_or(MerkleTreeError::WrongHeight)?;
+                    [left, digest]
+                };
+                next_layer.insert(pos >> 1, self.compress.compress(pair));
+            }
+
+            // Inject the openings of any matrices whose padded height matches this layer.
+            for (pos, opened_digest) in hash_openings_at(log_height)? {
+                let digest = next_layer.get_mut(&pos).unwrap();
+                *digest = self.compress.compress([*digest, opened_digest]);
+            }
+            layer = next_layer;
+        }
+
+        if siblings.next().is_some() {
+            return Err(MerkleTreeError::WrongHeight);
+        }
+        if layer.into_iter().all(|(pos, digest)| digest == commit[pos]) {
+            Ok(())
+        } else {
+            Err(MerkleTreeError::RootMismatch)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MerkleTreeMMCS;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use p3_commit::{Dimensions, DirectMMCS, MMCS};
+    use p3_keccak::{Keccak256Hash, KeccakF};
+    use p3_matrix::dense::RowMajorMatrix;
+    use p3_matrix::Matrix;
+    use p3_symmetric::compression::TruncatedPermutation;
+    use p3_util::log2_ceil_usize;
+    use rand::{thread_rng, Rng};
+
+    type C = TruncatedPermutation<u8, KeccakF, 2, 32, 200>;
+    type Mmcs = MerkleTreeMMCS<u8, [u8; 32], Keccak256Hash, C, RowMajorMatrix<u8>>;
+
+    fn commit_random(
+        mmcs: &Mmcs,
+    ) -> (
+        <Mmcs as MMCS<u8>>::Commitment,
+        <Mmcs as MMCS<u8>>::ProverData,
+        Vec<Dimensions>,
+    ) {
+        let mut rng = thread_rng();
+        let mats = vec![
+            RowMajorMatrix::rand(&mut rng, 64, 3),
+            RowMajorMatrix::rand(&mut rng, 8, 2),
+            RowMajorMatrix::rand(&mut rng, 64, 1),
+            RowMajorMatrix::rand(&mut rng, 16, 4),
+        ];
+        let dims = mats
+            .iter()
+            .map(|mat| Dimensions {
+                width: mat.width(),
+                log2_height: log2_ceil_usize(mat.height()),
+            })
+            .collect();
+        let (commit, prover_data) = mmcs.commit(mats);
+        (commit, prover_data, dims)
+    }
+
+    #[test]
+    fn open_and_verify_multi() {
+        let mut rng = thread_rng();
+        for cap_height in [0, 2] {
+            let mmcs = Mmcs::new(Keccak256Hash, C::new(KeccakF), cap_height);
+            let (commit, prover_data, dims) = commit_random(&mmcs);
+
+            for num_indices in [1, 2, 10, 40] {
+                let indices: Vec<usize> = (0..num_indices).map(|_| rng.gen_range(0..64)).collect();
+                let (opened_values, proof) = mmcs.open_batch_multi(&indices, &prover_data);
+                for (&index, opened) in indices.iter().zip(&opened_values) {
+                    assert_eq!(*opened, mmcs.open_batch(index, &prover_data).0);
+                }
+                let separate_proof_len = indices.len() * (6 - cap_height);
+                assert!(proof.len() <= separate_proof_len);
+
+                mmcs.verify_batch_multi(&commit, &dims, &indices, &opened_values, &proof)
+                    .expect("Merkle verification failed");
+            }
+        }
+    }
+
+    #[test]
+    fn shared_siblings_are_sent_once() {
+        let mmcs = Mmcs::new(Keccak256Hash, C::new(KeccakF), 0);
+        let (commit, prover_data, dims) = commit_random(&mmcs);
+
+        // Two neighbouring leaves share every node above the first layer.
+        let indices = [6, 7];
+        let (opened_values, proof) = mmcs.open_batch_multi(&indices, &prover_data);
+        assert_eq!(proof.len(), 5);
+        mmcs.verify_batch_multi(&commit, &dims, &indices, &opened_values, &proof)
+            .expect("Merkle verification failed");
+    }
+
+    #[test]
+    fn reject_bad_multi_opening() {
+        let mmcs = Mmcs::new(Keccak256Hash, C::new(KeccakF), 1);
+        let (commit, prover_data, dims) = commit_random(&mmcs);
+        let indices = [3, 17, 19, 60];
+        let (opened_values, proof) = mmcs.open_batch_multi(&indices, &prover_data);
+
+        let mut bad_values = opened_values.clone();
+        bad_values[2][3][1] ^= 1;
+        assert!(mmcs
+            .verify_batch_multi(&commit, &dims, &indices, &bad_values, &proof)
+            .is_err());
+
+        // Indices 17 and 19 share a row of the shortest matrix, so their openings must agree.
+        let mut bad_values = opened_values.clone();
+        bad_values[1][1][0] ^= 1;
+        assert!(mmcs
+            .verify_batch_multi(&commit, &dims, &indices, &bad_values, &proof)
+            .is_err());
+
+        let mut bad_proof = proof.clone();
+        bad_proof[0][0] ^= 1;
+        assert!(mmcs
+            .verify_batch_multi(&commit, &dims, &indices, &opened_values, &bad_proof)
+            .is_err());
+
+        let short_proof = &proof[1..];
+        assert!(mmcs
+            .verify_batch_multi(
+                &commit,
+                &dims,
+                &indices,
+                &opened_values,
+                &short_proof.to_vec()
+            )
+            .is_err());
+
+        let mut long_proof = proof.clone();
+        long_proof.push(proof[0]);
+        assert!(mmcs
+            .verify_batch_multi(&commit, &dims, &indices, &opened_values, &long_proof)
+            .is_err());
+    }
+}
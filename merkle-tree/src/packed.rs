@@ -0,0 +1,382 @@
+use crate::{compress_node, hash_rows, MerkleCap, MerkleTree, MerkleTreeError, MerkleTreeMMCS};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::array;
+use core::marker::PhantomData;
+use itertools::Itertools;
+use p3_commit::{Dimensions, DirectMMCS, MMCS};
+use p3_field::{Field, PackedField};
+use p3_matrix::MatrixRows;
+use p3_maybe_rayon::{
+    IndexedParallelIterator, MaybeParChunksMut, MaybeParIterMut, ParallelIterator,
+};
+use p3_symmetric::compression::PseudoCompressionFunction;
+use p3_symmetric::hasher::CryptographicHasher;
+
+impl<F, Mat, const DIGEST_ELEMS: usize> MerkleTree<F, [F; DIGEST_ELEMS], Mat>
+where
+    F: Field,
+    [F; DIGEST_ELEMS]: Default,
+    Mat: for<'a> MatrixRows<'a, F> + Sync,
+{
+    /// Like `new`, but hashes and compresses `P::WIDTH` nodes at a time using `packed_h` and
+    /// `packed_c`. These must act on each lane exactly as `h` and `c` do, so that the resulting
+    /// tree is identical to the one built by `new`. Nodes which don't fill a whole packed vector
+    /// are handled by `h` and `c`.
+    pub fn new_packed<P, H, C, PH, PC>(
+        h: &H,
+        c: &C,
+        packed_h: &PH,
+        packed_c: &PC,
+        leaves: Vec<Mat>,
+        cap_height: usize,
+    ) -> Self
+    where
+        P: PackedField<Scalar = F>,
+        H: CryptographicHasher<F, [F; DIGEST_ELEMS]> + Sync,
+        C: PseudoCompressionFunction<[F; DIGEST_ELEMS], 2> + Sync,
+        PH: CryptographicHasher<P, [P; DIGEST_ELEMS]> + Sync,
+        PC: PseudoCompressionFunction<[P; DIGEST_ELEMS], 2> + Sync,
+    {
+        Self::new_with_layers(
+            leaves,
+            cap_height,
+            |tallest_matrices, height_padded| {
+                first_digest_layer_packed(h, packed_h, tallest_matrices, height_padded)
+            },
+            |prev_layer, tallest_matrices| {
+                compression_layer_packed(prev_layer, tallest_matrices, h, c, packed_h, packed_c)
+            },
+        )
+    }
+}
+
+fn first_digest_layer_packed<P, H, PH, Mat, const DIGEST_ELEMS: usize>(
+    h: &H,
+    packed_h: &PH,
+    tallest_matrices: &[&Mat],
+    height_padded: usize,
+) -> Vec<[P::Scalar; DIGEST_ELEMS]>
+where
+    P: PackedField,
+    [P::Scalar; DIGEST_ELEMS]: Default,
+    H: CryptographicHasher<P::Scalar, [P::Scalar; DIGEST_ELEMS]> + Sync,
+    PH: CryptographicHasher<P, [P; DIGEST_ELEMS]> + Sync,
+    Mat: for<'a> MatrixRows<'a, P::Scalar> + Sync,
+{
+    let height = tallest_matrices[0].height();
+    let packed_height = height - height % P::WIDTH;
+
+    let mut digests = vec![<[P::Scalar; DIGEST_ELEMS]>::default(); height_padded];
+    digests[..packed_height]
+        .par_chunks_exact_mut(P::WIDTH)
+        .enumerate()
+        .for_each(|(i, chunk)| {
+            let packed_digest = hash_rows_packed(packed_h, tallest_matrices, i * P::WIDTH);
+            unpack_digests(packed_digest, chunk);
+        });
+    digests[packed_height..height]
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, digest)| *digest = hash_rows(h, tallest_matrices, packed_height + i));
+    digests
+}
+
+fn compression_layer_packed<P, H, C, PH, PC, Mat, const DIGEST_ELEMS: usize>(
+    prev_layer: &[[P::Scalar; DIGEST_ELEMS]],
+    tallest_matrices: &[&Mat],
+    h: &H,
+    c: &C,
+    packed_h: &PH,
+    packed_c: &PC,
+) -> Vec<[P::Scalar; DIGEST_ELEMS]>
+where
+    P: PackedField,
+    [P::Scalar; DIGEST_ELEMS]: Default,
+    H: CryptographicHasher<P::Scalar, [P::Scalar; DIGEST_ELEMS]> + Sync,
+    C: PseudoCompressionFunction<[P::Scalar; DIGEST_ELEMS], 2> + Sync,
+    PH: CryptographicHasher<P, [P; DIGEST_ELEMS]> + Sync,
+    PC: PseudoCompressionFunction<[P; DIGEST_ELEMS], 2> + Sync,
+    Mat: for<'a> MatrixRows<'a, P::Scalar> + Sync,
+{
+    let hash_row = |matrices: &[&Mat], r| hash_rows(h, matrices, r);
+    let width = P::WIDTH;
+    let next_len_padded = prev_layer.len() >> 1;
+    let next_len = tallest_matrices.first().map_or(0, |m| m.height());
+    let packed_len = next_len_padded - next_len_padded % width;
+
+    let mut next_digests = vec![<[P::Scalar; DIGEST_ELEMS]>::default(); next_len_padded];
+    next_digests[..packed_len]
+        .par_chunks_exact_mut(width)
+        .enumerate()
+        .for_each(|(chunk_index, chunk)| {
+            let first = chunk_index * width;
+            // Chunks which are only partly covered by the injected matrices are handled one node
+            // at a time.
+            if !tallest_matrices.is_empty() && first + width > next_len {
+                for (k, digest) in chunk.iter_mut().enumerate() {
+                    *digest = compress_node(prev_layer, tallest_matrices, first + k, c, &hash_row);
+                }
+                return;
+            }
+
+            let left = pack_digests(|k| prev_layer[2 * (first + k)]);
+            let right = pack_digests(|k| prev_layer[2 * (first + k) + 1]);
+            let mut digest = packed_c.compress([left, right]);
+            if !tallest_matrices.is_empty() {
+                let tallest_digest = hash_rows_packed(packed_h, tallest_matrices, first);
+                digest = packed_c.compress([digest, tallest_digest]);
+            }
+            unpack_digests(digest, chunk);
+        });
+    next_digests[packed_len..]
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, digest)| {
+            *digest = compress_node(prev_layer, tallest_matrices, packed_len + i, c, &hash_row);
+        });
+    next_digests
+}
+
+/// Hash rows `first_row..first_row + P::WIDTH` of the given matrices, with one row per lane.
+fn hash_rows_packed<P, PH, Mat, const DIGEST_ELEMS: usize>(
+    packed_h: &PH,
+    matrices: &[&Mat],
+    first_row: usize,
+) -> [P; DIGEST_ELEMS]
+where
+    P: PackedField,
+    PH: CryptographicHasher<P, [P; DIGEST_ELEMS]>,
+    Mat: for<'a> MatrixRows<'a, P::Scalar>,
+{
+    packed_h.hash_iter(matrices.iter().flat_map(|&m| {
+        let mut rows = (0..P::WIDTH)
+            .map(|k| m.row(first_row + k).into_iter())
+            .collect_vec();
+        (0..m.width()).map(move |_| P::from_fn(|k| *rows[k].next().unwrap()))
+    }))
+}
+
+/// Pack `P::WIDTH` digests, where lane `k` holds `digest(k)`.
+fn pack_digests<P, const DIGEST_ELEMS: usize>(
+    digest: impl Fn(usize) -> [P::Scalar; DIGEST_ELEMS],
+) -> [P; DIGEST_ELEMS]
+where
+    P: PackedField,
+{
+    let digests = (0..P::WIDTH).map(digest).collect_vec();
+    array::from_fn(|j| P::from_fn(|k| digests[k][j]))
+}
+
+fn unpack_digests<P, const DIGEST_ELEMS: usize>(
+    packed_digest: [P; DIGEST_ELEMS],
+    digests: &mut [[P::Scalar; DIGEST_ELEMS]],
+) where
+    P: PackedField,
+{
+    for (k, digest) in digests.iter_mut().enumerate() {
+        *digest = packed_digest.map(|p| p.as_slice()[k]);
+    }
+}
+
+/// A `MerkleTreeMMCS` over field elements, which builds its trees with packed hashing and
+/// compression where possible.
+///
+/// `PH` and `PC` must act on each lane of `P` exactly as `H` and `C` act on scalars; see
+/// `MerkleTree::new_packed`.
+pub struct FieldMerkleTreeMMCS<P, H, C, PH, PC, Mat, const DIGEST_ELEMS: usize>
+where
+    P: PackedField,
+{
+    inner: MerkleTreeMMCS<P::Scalar, [P::Scalar; DIGEST_ELEMS], H, C, Mat>,
+    packed_hash: PH,
+    packed_compress: PC,
+    _phantom_p: PhantomData<P>,
+}
+
+impl<P, H, C, PH, PC, Mat, const DIGEST_ELEMS: usize>
+    FieldMerkleTreeMMCS<P, H, C, PH, PC, Mat, DIGEST_ELEMS>
+where
+    P: PackedField,
+{
+    pub fn new(
+        hash: H,
+        compress: C,
+        packed_hash: PH,
+        packed_compress: PC,
+        cap_height: usize,
+    ) -> Self {
+        Self {
+            inner: MerkleTreeMMCS::new(hash, compress, cap_height),
+            packed_hash,
+            packed_compress,
+            _phantom_p: PhantomData,
+        }
+    }
+}
+
+impl<P, H, C, PH, PC, Mat, const DIGEST_ELEMS: usize> MMCS<P::Scalar>
+    for FieldMerkleTreeMMCS<P, H, C, PH, PC, Mat, DIGEST_ELEMS>
+where
+    P: PackedField,
+    H: CryptographicHasher<P::Scalar, [P::Scalar; DIGEST_ELEMS]>,
+    C: PseudoCompressionFunction<[P::Scalar; DIGEST_ELEMS], 2>,
+    Mat: for<'a> MatrixRows<'a, P::Scalar>,
+{
+    type ProverData = MerkleTree<P::Scalar, [P::Scalar; DIGEST_ELEMS], Mat>;
+    type Commitment = MerkleCap<[P::Scalar; DIGEST_ELEMS]>;
+    type Proof = Vec<[P::Scalar; DIGEST_ELEMS]>;
+    type MultiProof = Vec<[P::Scalar; DIGEST_ELEMS]>;
+    type Error = MerkleTreeError;
+    type Mat = Mat;
+
+    fn open_batch(
+        &self,
+        index: usize,
+        prover_data: &Self::ProverData,
+    ) -> (Vec<Vec<P::Scalar>>, Self::Proof) {
+        self.inner.open_batch(index, prover_data)
+    }
+
+    fn open_batch_multi(
+        &self,
+        indices: &[usize],
+        prover_data: &Self::ProverData,
+    ) -> (Vec<Vec<Vec<P::Scalar>>>, Self::MultiProof) {
+        self.inner.open_batch_multi(indices, prover_data)
+    }
+
+    fn get_matrices(prover_data: &Self::ProverData) -> &[Mat] {
+        &prover_data.leaves
+    }
+
+    fn verify_batch(
+        &self,
+        commit: &Self::Commitment,
+        dimensions: &[Dimensions],
+        index: usize,
+        opened_values: &[Vec<P::Scalar>],
+        proof: &Self::Proof,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .verify_batch(commit, dimensions, index, opened_values, proof)
+    }
+
+    fn verify_batch_multi(
+        &self,
+        commit: &Self::Commitment,
+        dimensions: &[Dimensions],
+        indices: &[usize],
+        opened_values: &[Vec<Vec<P::Scalar>>],
+        proof: &Self::MultiProof,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .verify_batch_multi(commit, dimensions, indices, opened_values, proof)
+    }
+}
+
+impl<P, H, C, PH, PC, Mat, const DIGEST_ELEMS: usize> DirectMMCS<P::Scalar>
+    for FieldMerkleTreeMMCS<P, H, C, PH, PC, Mat, DIGEST_ELEMS>
+where
+    P: PackedField,
+    [P::Scalar; DIGEST_ELEMS]: Default,
+    H: CryptographicHasher<P::Scalar, [P::Scalar; DIGEST_ELEMS]> + Sync,
+    C: PseudoCompressionFunction<[P::Scalar; DIGEST_ELEMS], 2> + Sync,
+    PH: CryptographicHasher<P, [P; DIGEST_ELEMS]> + Sync,
+    PC: PseudoCompressionFunction<[P; DIGEST_ELEMS], 2> + Sync,
+    Mat: for<'a> MatrixRows<'a, P::Scalar> + Sync,
+{
+    fn commit(&self, inputs: Vec<Mat>) -> (Self::Commitment, Self::ProverData) {
+        let tree = MerkleTree::new_packed(
+            &self.inner.hash,
+            &self.inner.compress,
+            &self.packed_hash,
+            &self.packed_compress,
+            inputs,
+            self.inner.cap_height,
+        );
+        (tree.cap(), tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FieldMerkleTreeMMCS, MerkleTree};
+    use alloc::vec::Vec;
+    use p3_commit::{Dimensions, DirectMMCS, MMCS};
+    use p3_field::Field;
+    use p3_goldilocks::Goldilocks;
+    use p3_matrix::dense::RowMajorMatrix;
+    use p3_matrix::Matrix;
+    use p3_symmetric::compression::TruncatedPermutation;
+    use p3_symmetric::mds::NaiveMDSMatrix;
+    use p3_symmetric::sponge::PaddingFreeSponge;
+    use p3_util::log2_ceil_usize;
+    use rand::{thread_rng, Rng};
+
+    type F = Goldilocks;
+    type P = <F as Field>::Packing;
+    type Perm = NaiveMDSMatrix<F, 8>;
+    type H = PaddingFreeSponge<F, Perm, 8>;
+    type C = TruncatedPermutation<F, Perm, 2, 4, 8>;
+    type PH = PaddingFreeSponge<P, Perm, 8>;
+    type PC = TruncatedPermutation<P, Perm, 2, 4, 8>;
+    type Mmcs = FieldMerkleTreeMMCS<P, H, C, PH, PC, RowMajorMatrix<F>, 4>;
+
+    fn random_perm() -> Perm {
+        let mut rng = thread_rng();
+        Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())))
+    }
+
+    fn random_matrices(shapes: &[(usize, usize)]) -> Vec<RowMajorMatrix<F>> {
+        let mut rng = thread_rng();
+        shapes
+            .iter()
+            .map(|&(height, width)| RowMajorMatrix::rand(&mut rng, height, width))
+            .collect()
+    }
+
+    #[test]
+    fn packed_matches_scalar() {
+        let perm = random_perm();
+        let (h, c) = (H::new(perm.clone()), C::new(perm.clone()));
+        let (ph, pc) = (PH::new(perm.clone()), PC::new(perm));
+
+        let shapes: [&[(usize, usize)]; 3] =
+            [&[(64, 3)], &[(37, 2), (16, 5), (7, 1), (3, 4)], &[(1, 7)]];
+        for shapes in shapes {
+            let mats = random_matrices(shapes);
+            let scalar_tree = MerkleTree::new(&h, &c, mats.clone(), 0);
+            let packed_tree = MerkleTree::new_packed(&h, &c, &ph, &pc, mats, 0);
+            assert_eq!(scalar_tree.digest_layers, packed_tree.digest_layers);
+        }
+    }
+
+    #[test]
+    fn open_and_verify() {
+        let perm = random_perm();
+        let mmcs = Mmcs::new(
+            H::new(perm.clone()),
+            C::new(perm.clone()),
+            PH::new(perm.clone()),
+            PC::new(perm),
+            2,
+        );
+
+        let mats = random_matrices(&[(32, 3), (8, 2), (8, 1)]);
+        let dims: Vec<Dimensions> = mats
+            .iter()
+            .map(|mat| Dimensions {
+                width: mat.width(),
+                log2_height: log2_ceil_usize(mat.height()),
+            })
+            .collect();
+        let (commit, prover_data) = mmcs.commit(mats);
+
+        for index in 0..32 {
+            let (opened_values, proof) = mmcs.open_batch(index, &prover_data);
+            mmcs.verify_batch(&commit, &dims, index, &opened_values, &proof)
+                .expect("Merkle verification failed");
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::Mersenne31;
+use core::arch::aarch64::*;
+use core::mem::transmute;
+use core::ops::{Add, Mul, Neg, Sub};
+use p3_field::{impl_packed_field_arithmetic, AbstractField, PackedField, PrimeField32};
+
+const WIDTH: usize = 4;
+const P: uint32x4_t = unsafe { transmute::<[u32; WIDTH], _>([Mersenne31::ORDER_U32; WIDTH]) };
+
+/// Vectorized NEON implementation of `Mersenne31` arithmetic.
+///
+/// Like `Mersenne31`, each lane holds a value of at most `P`, which need not be canonical.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)] // This is needed to make `transmute`s safe.
+pub struct PackedMersenne31Neon(pub [Mersenne31; WIDTH]);
+
+impl PackedMersenne31Neon {
+    #[inline]
+    #[must_use]
+    fn to_vector(self) -> uint32x4_t {
+        // SAFETY: `Self` is `repr(transparent)` over `[Mersenne31; WIDTH]`, which has the same size
+        // as `uint32x4_t`, and `Mersenne31` is `repr(transparent)` over `u32`.
+        unsafe { transmute(self) }
+    }
+
+    /// # Safety
+    /// Each lane of `vector` must be at most `P`.
+    #[inline]
+    #[must_use]
+    unsafe fn from_vector(vector: uint32x4_t) -> Self {
+        transmute(vector)
+    }
+}
+
+/// Add two vectors of values at most `P`.
+#[inline]
+#[must_use]
+fn add(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+    // See the AVX2 implementation; the smaller of t and t - P is the reduced sum.
+    unsafe {
+        let t = vaddq_u32(lhs, rhs);
+        let u = vsubq_u32(t, P);
+        vminq_u32(t, u)
+    }
+}
+
+/// Subtract two vectors of values at most `P`.
+#[inline]
+#[must_use]
+fn sub(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+    unsafe {
+        let t = vsubq_u32(lhs, rhs);
+        let u = vaddq_u32(t, P);
+        vminq_u32(t, u)
+    }
+}
+
+/// Negate a vector of values at most `P`.
+#[inline]
+#[must_use]
+fn neg(val: uint32x4_t) -> uint32x4_t {
+    unsafe { vsubq_u32(P, val) }
+}
+
+/// Multiply two vectors of values at most `P`.
+#[inline]
+#[must_use]
+fn mul(lhs: uint32x4_t, rhs: uint32x4_t) -> uint32x4_t {
+    // Compute the 62-bit products of the low and high pairs of lanes, then narrow each to its low 31
+    // bits and the rest, whose sum is the reduced product.
+    unsafe {
+        let prod_lo = vmull_u32(vget_low_u32(lhs), vget_low_u32(rhs));
+        let prod_hi = vmull_high_u32(lhs, rhs);
+
+        let lo = vandq_u32(vcombine_u32(vmovn_u64(prod_lo), vmovn_u64(prod_hi)), P);
+        let hi = vcombine_u32(vshrn_n_u64::<31>(prod_lo), vshrn_n_u64::<31>(prod_hi));
+        add(lo, hi)
+    }
+}
+
+impl Add for PackedMersenne31Neon {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(add(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Sub for PackedMersenne31Neon {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(sub(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Neg for PackedMersenne31Neon {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        unsafe { Self::from_vector(neg(self.to_vector())) }
+    }
+}
+
+impl Mul for PackedMersenne31Neon {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(mul(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl_packed_field_arithmetic!(PackedMersenne31Neon, Mersenne31, WIDTH);
+
+/// Interleave the lanes of `a` and `b` in blocks of `block_len`; see `PackedField::interleave`.
+#[inline]
+#[must_use]
+fn interleave(a: uint32x4_t, b: uint32x4_t, block_len: usize) -> (uint32x4_t, uint32x4_t) {
+    unsafe {
+        match block_len {
+            1 => (vtrn1q_u32(a, b), vtrn2q_u32(a, b)),
+            2 => {
+                let (a, b) = (vreinterpretq_u64_u32(a), vreinterpretq_u64_u32(b));
+                (
+                    vreinterpretq_u32_u64(vtrn1q_u64(a, b)),
+                    vreinterpretq_u32_u64(vtrn2q_u64(a, b)),
+                )
+            }
+            WIDTH => (a, b),
+            _ => panic!("unsupported block length"),
+        }
+    }
+}
+
+unsafe impl PackedField for PackedMersenne31Neon {
+    type Scalar = Mersenne31;
+
+    const WIDTH: usize = WIDTH;
+    const ZEROS: Self = Self::ZERO;
+    const ONES: Self = Self::ONE;
+
+    #[inline]
+    fn from_slice(slice: &[Mersenne31]) -> &Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Mersenne31; WIDTH]`.
+        unsafe { &*slice.as_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_slice_mut(slice: &mut [Mersenne31]) -> &mut Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Mersenne31; WIDTH]`.
+        unsafe { &mut *slice.as_mut_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> Mersenne31>(f: F) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[Mersenne31] {
+        &self.0
+    }
+
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [Mersenne31] {
+        &mut self.0
+    }
+
+    #[inline]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (a, b) = interleave(self.to_vector(), other.to_vector(), block_len);
+        unsafe { (Self::from_vector(a), Self::from_vector(b)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedMersenne31Neon;
+    use crate::Mersenne31;
+    use p3_field::{AbstractField, PackedField, PrimeField32};
+    use p3_field_testing::check_packed_field;
+
+    type P = PackedMersenne31Neon;
+
+    #[test]
+    fn packed_field() {
+        check_packed_field::<P>();
+    }
+
+    #[test]
+    fn non_canonical_lanes() {
+        // P itself is a valid, non-canonical encoding of zero.
+        let p = P::from(Mersenne31::new(Mersenne31::ORDER_U32));
+        let x = P::from_fn(|i| Mersenne31::from_canonical_usize(i + 1));
+        assert_eq!(p + x, x);
+        assert_eq!(x - p, x);
+        assert_eq!(p - x, -x);
+        assert_eq!(p * x, P::ZERO);
+        assert_eq!(-p, P::ZERO);
+        assert_eq!(p + p, P::ZERO);
+        assert!((p * p).0.iter().all(|v| v.value <= Mersenne31::ORDER_U32));
+    }
+}
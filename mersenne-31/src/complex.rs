@@ -3,6 +3,8 @@ use core::fmt::{Display, Formatter};
 use core::iter::{Product, Sum};
 use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
 use p3_field::{AbstractExtensionField, AbstractField, AbstractionOf, Field, TwoAdicField};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
 pub struct Mersenne31Complex<AF: AbstractionOf<Mersenne31>> {
@@ -31,6 +33,16 @@ impl<AF: AbstractionOf<Mersenne31>> Mersenne31Complex<AF> {
     pub fn imag(&self) -> AF {
         self.parts[1].clone()
     }
+
+    /// The complex conjugate `a - b i` of `a + b i`.
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.real(), -self.imag())
+    }
+
+    /// The norm `a^2 + b^2` of `a + b i`, i.e. its product with its conjugate.
+    pub fn norm(&self) -> AF {
+        self.real().square() + self.imag().square()
+    }
 }
 
 impl<AF: AbstractionOf<Mersenne31>> Add for Mersenne31Complex<AF> {
@@ -213,7 +225,11 @@ impl Field for Mersenne31Complex<Mersenne31> {
     type Packing = Self;
 
     fn try_inverse(&self) -> Option<Self> {
-        todo!()
+        // (a + b i)^-1 = (a - b i) / (a^2 + b^2). Since p = 3 mod 4, -1 isn't a square mod p, so
+        // the norm a^2 + b^2 is only zero if a and b both are.
+        self.norm()
+            .try_inverse()
+            .map(|norm_inv| self.conjugate() * norm_inv)
     }
 }
 
@@ -253,3 +269,45 @@ impl<AF: AbstractField + AbstractionOf<Mersenne31>> AbstractExtensionField<AF>
         &self.parts
     }
 }
+
+impl Distribution<Mersenne31Complex<Mersenne31>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Mersenne31Complex<Mersenne31> {
+        Mersenne31Complex::new(rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Mersenne31, Mersenne31Complex};
+    use p3_field::{AbstractField, Field, TwoAdicField};
+    use p3_field_testing::check_field_axioms;
+
+    type F = Mersenne31Complex<Mersenne31>;
+
+    #[test]
+    fn inverse() {
+        assert_eq!(F::ZERO.try_inverse(), None);
+        assert_eq!(F::ONE.inverse(), F::ONE);
+
+        // i^-1 = -i.
+        let i = F::new_imag(Mersenne31::ONE);
+        assert_eq!(i.inverse(), -i);
+
+        let x = F::new(Mersenne31::new(3), Mersenne31::new(4));
+        assert_eq!(x.norm(), Mersenne31::new(25));
+        assert_eq!(x * x.inverse(), F::ONE);
+    }
+
+    #[test]
+    fn field_axioms() {
+        check_field_axioms::<F>();
+    }
+
+    #[test]
+    fn power_of_two_generator() {
+        let g = F::power_of_two_generator();
+        assert_eq!(g.exp_power_of_2(F::TWO_ADICITY), F::ONE);
+        // The order is exactly 2^32, so g^(2^31) is the other square root of one.
+        assert_eq!(g.exp_power_of_2(F::TWO_ADICITY - 1), F::NEG_ONE);
+    }
+}
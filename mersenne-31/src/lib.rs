@@ -2,21 +2,34 @@
 
 #![no_std]
 
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod aarch64_neon;
 mod complex;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+mod x86_64_avx2;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+mod x86_64_avx512;
 
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+pub use aarch64_neon::*;
 pub use complex::*;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+pub use x86_64_avx2::*;
+#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+pub use x86_64_avx512::*;
 
 use core::fmt;
 use core::fmt::{Debug, Display, Formatter};
 use core::hash::{Hash, Hasher};
 use core::iter::{Product, Sum};
 use core::ops::{Add, AddAssign, BitXorAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
-use p3_field::{AbstractField, Field, PrimeField, PrimeField32};
+use p3_field::{impl_packed_array_ops, AbstractField, Field, PrimeField, PrimeField32};
 use rand::distributions::{Distribution, Standard};
 use rand::Rng;
 
 /// The prime field `F_p` where `p = 2^31 - 1`.
 #[derive(Copy, Clone, Default)]
+#[repr(transparent)] // Packed field implementations rely on this.
 pub struct Mersenne31 {
     /// Not necessarily canonical, but must fit in 31 bits.
     value: u32,
@@ -120,8 +133,14 @@ impl AbstractField for Mersenne31 {
         Self::new(n ^ msb) + Self::new(msb_reduced)
     }
 
-    fn from_wrapped_u64(_n: u64) -> Self {
-        todo!()
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_wrapped_u64(n: u64) -> Self {
+        // Since 2^31 = 1 mod p, we can split `n` into 31-bit limbs, plus a final 2-bit limb, and
+        // add them up.
+        let lo = (n as u32) & Self::ORDER_U32;
+        let mid = ((n >> 31) as u32) & Self::ORDER_U32;
+        let hi = (n >> 62) as u32;
+        Self::new(lo) + Self::new(mid) + Self::new(hi)
     }
 
     // Sage: GF(2^31 - 1).multiplicative_generator()
@@ -131,8 +150,21 @@ impl AbstractField for Mersenne31 {
 }
 
 impl Field for Mersenne31 {
-    // TODO: Add cfg-guarded Packing for AVX2, NEON, etc.
-    type Packing = Self;
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    type Packing = PackedMersenne31Neon;
+    #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        not(target_feature = "avx512f")
+    ))]
+    type Packing = PackedMersenne31AVX2;
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+    type Packing = PackedMersenne31AVX512;
+    #[cfg(not(any(
+        all(target_arch = "aarch64", target_feature = "neon"),
+        all(target_arch = "x86_64", target_feature = "avx2"),
+    )))]
+    type Packing = p3_field::PackedArray<Self, 8>;
 
     fn is_zero(&self) -> bool {
         self.value == 0 || self.value == Self::ORDER_U32
@@ -186,6 +218,8 @@ impl Field for Mersenne31 {
     }
 }
 
+impl_packed_array_ops!(Mersenne31);
+
 impl PrimeField for Mersenne31 {}
 
 impl PrimeField32 for Mersenne31 {
@@ -289,7 +323,9 @@ impl Div for Mersenne31 {
 #[cfg(test)]
 mod tests {
     use crate::Mersenne31;
-    use p3_field::{AbstractField, Field, PrimeField32};
+    use p3_field::{AbstractField, Field, PackedArray, PrimeField32};
+    use p3_field_testing::{check_field_axioms, check_packed_field};
+    use rand::{thread_rng, Rng};
 
     type F = Mersenne31;
 
@@ -334,4 +370,34 @@ mod tests {
     fn inverse() {
         assert_eq!(F::new(172).inverse() * F::new(172), F::ONE);
     }
+
+    #[test]
+    fn from_wrapped_u64() {
+        assert_eq!(F::from_wrapped_u64(0), F::ZERO);
+        assert_eq!(F::from_wrapped_u64(u64::from(F::ORDER_U32)), F::ZERO);
+        // 2^31 = 1 and 2^62 = 1.
+        assert_eq!(F::from_wrapped_u64(1 << 31), F::ONE);
+        assert_eq!(F::from_wrapped_u64(1 << 62), F::ONE);
+        // 2^64 - 1 = 2^2 * 2^62 - 1 = 3.
+        assert_eq!(F::from_wrapped_u64(u64::MAX), F::new(3));
+
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let n: u64 = rng.gen();
+            let expected = (n % u64::from(F::ORDER_U32)) as u32;
+            assert_eq!(F::from_wrapped_u64(n).as_canonical_u32(), expected);
+        }
+    }
+
+    #[test]
+    fn field_axioms() {
+        check_field_axioms::<F>();
+    }
+
+    #[test]
+    fn packed_array() {
+        check_packed_field::<PackedArray<F, 1>>();
+        check_packed_field::<PackedArray<F, 4>>();
+        check_packed_field::<PackedArray<F, 8>>();
+    }
 }
@@ -0,0 +1,222 @@
+use crate::Mersenne31;
+use core::arch::x86_64::*;
+use core::mem::transmute;
+use core::ops::{Add, Mul, Neg, Sub};
+use p3_field::{impl_packed_field_arithmetic, AbstractField, PackedField, PrimeField32};
+
+const WIDTH: usize = 8;
+const P: __m256i = unsafe { transmute::<[u32; WIDTH], _>([Mersenne31::ORDER_U32; WIDTH]) };
+
+/// Vectorized AVX2 implementation of `Mersenne31` arithmetic.
+///
+/// Like `Mersenne31`, each lane holds a value of at most `P`, which need not be canonical.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)] // This is needed to make `transmute`s safe.
+pub struct PackedMersenne31AVX2(pub [Mersenne31; WIDTH]);
+
+impl PackedMersenne31AVX2 {
+    #[inline]
+    #[must_use]
+    fn to_vector(self) -> __m256i {
+        // SAFETY: `Self` is `repr(transparent)` over `[Mersenne31; WIDTH]`, which has the same size
+        // as `__m256i`, and `Mersenne31` is `repr(transparent)` over `u32`.
+        unsafe { transmute(self) }
+    }
+
+    /// # Safety
+    /// Each lane of `vector` must be at most `P`.
+    #[inline]
+    #[must_use]
+    unsafe fn from_vector(vector: __m256i) -> Self {
+        transmute(vector)
+    }
+}
+
+/// Add two vectors of values at most `P`.
+#[inline]
+#[must_use]
+fn add(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // The sum t is at most 2P < 2^32, so it doesn't overflow. If t >= P, then t - P is at most P,
+    // and less than t; otherwise t - P wraps around to more than t. Either way, the smaller of t
+    // and t - P is the reduced sum.
+    unsafe {
+        let t = _mm256_add_epi32(lhs, rhs);
+        let u = _mm256_sub_epi32(t, P);
+        _mm256_min_epu32(t, u)
+    }
+}
+
+/// Subtract two vectors of values at most `P`.
+#[inline]
+#[must_use]
+fn sub(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // If lhs >= rhs, then t = lhs - rhs is at most P, and t + P is more than t; otherwise t wraps
+    // around, and t + P wraps back to the reduced difference, which is less than t.
+    unsafe {
+        let t = _mm256_sub_epi32(lhs, rhs);
+        let u = _mm256_add_epi32(t, P);
+        _mm256_min_epu32(t, u)
+    }
+}
+
+/// Negate a vector of values at most `P`.
+#[inline]
+#[must_use]
+fn neg(val: __m256i) -> __m256i {
+    unsafe { _mm256_sub_epi32(P, val) }
+}
+
+/// Multiply two vectors of values at most `P`.
+#[inline]
+#[must_use]
+fn mul(lhs: __m256i, rhs: __m256i) -> __m256i {
+    // `_mm256_mul_epu32` multiplies the even 32-bit lanes into 64-bit products, so we shift the odd
+    // lanes down to get their products too. Each product is less than 2^62, and since 2^31 = 1 mod
+    // P, it reduces to the sum of its low 31 bits and the rest, which fit in 31 bits.
+    unsafe {
+        let lhs_odd = _mm256_srli_epi64::<32>(lhs);
+        let rhs_odd = _mm256_srli_epi64::<32>(rhs);
+        let prod_even = _mm256_mul_epu32(lhs, rhs);
+        let prod_odd = _mm256_mul_epu32(lhs_odd, rhs_odd);
+
+        // Move the odd products' low halves, and their bits from 31 up, into the odd lanes.
+        let lo = _mm256_blend_epi32::<0b10101010>(prod_even, _mm256_slli_epi64::<32>(prod_odd));
+        let lo = _mm256_and_si256(lo, P);
+        let hi = _mm256_blend_epi32::<0b10101010>(
+            _mm256_srli_epi64::<31>(prod_even),
+            _mm256_slli_epi64::<1>(prod_odd),
+        );
+        add(lo, hi)
+    }
+}
+
+impl Add for PackedMersenne31AVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(add(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Sub for PackedMersenne31AVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(sub(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Neg for PackedMersenne31AVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        unsafe { Self::from_vector(neg(self.to_vector())) }
+    }
+}
+
+impl Mul for PackedMersenne31AVX2 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(mul(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl_packed_field_arithmetic!(PackedMersenne31AVX2, Mersenne31, WIDTH);
+
+/// Interleave the lanes of `a` and `b` in blocks of `block_len`; see `PackedField::interleave`.
+#[inline]
+#[must_use]
+fn interleave(a: __m256i, b: __m256i, block_len: usize) -> (__m256i, __m256i) {
+    unsafe {
+        match block_len {
+            1 => (
+                _mm256_blend_epi32::<0b10101010>(a, _mm256_slli_epi64::<32>(b)),
+                _mm256_blend_epi32::<0b10101010>(_mm256_srli_epi64::<32>(a), b),
+            ),
+            2 => (_mm256_unpacklo_epi64(a, b), _mm256_unpackhi_epi64(a, b)),
+            4 => (
+                _mm256_permute2x128_si256::<0x20>(a, b),
+                _mm256_permute2x128_si256::<0x31>(a, b),
+            ),
+            WIDTH => (a, b),
+            _ => panic!("unsupported block length"),
+        }
+    }
+}
+
+unsafe impl PackedField for PackedMersenne31AVX2 {
+    type Scalar = Mersenne31;
+
+    const WIDTH: usize = WIDTH;
+    const ZEROS: Self = Self::ZERO;
+    const ONES: Self = Self::ONE;
+
+    #[inline]
+    fn from_slice(slice: &[Mersenne31]) -> &Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Mersenne31; WIDTH]`.
+        unsafe { &*slice.as_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_slice_mut(slice: &mut [Mersenne31]) -> &mut Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Mersenne31; WIDTH]`.
+        unsafe { &mut *slice.as_mut_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> Mersenne31>(f: F) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[Mersenne31] {
+        &self.0
+    }
+
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [Mersenne31] {
+        &mut self.0
+    }
+
+    #[inline]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (a, b) = interleave(self.to_vector(), other.to_vector(), block_len);
+        unsafe { (Self::from_vector(a), Self::from_vector(b)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedMersenne31AVX2;
+    use crate::Mersenne31;
+    use p3_field::{AbstractField, PackedField, PrimeField32};
+    use p3_field_testing::check_packed_field;
+
+    type P = PackedMersenne31AVX2;
+
+    #[test]
+    fn packed_field() {
+        check_packed_field::<P>();
+    }
+
+    #[test]
+    fn non_canonical_lanes() {
+        // P itself is a valid, non-canonical encoding of zero.
+        let p = P::from(Mersenne31::new(Mersenne31::ORDER_U32));
+        let x = P::from_fn(|i| Mersenne31::from_canonical_usize(i + 1));
+        assert_eq!(p + x, x);
+        assert_eq!(x - p, x);
+        assert_eq!(p - x, -x);
+        assert_eq!(p * x, P::ZERO);
+        assert_eq!(-p, P::ZERO);
+        assert_eq!(p + p, P::ZERO);
+        assert!((p * p).0.iter().all(|v| v.value <= Mersenne31::ORDER_U32));
+    }
+}
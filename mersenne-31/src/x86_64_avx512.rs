@@ -0,0 +1,243 @@
+use crate::Mersenne31;
+use core::arch::x86_64::*;
+use core::mem::transmute;
+use core::ops::{Add, Mul, Neg, Sub};
+use p3_field::{impl_packed_field_arithmetic, AbstractField, PackedField, PrimeField32};
+
+const WIDTH: usize = 16;
+const P: __m512i = unsafe { transmute::<[u32; WIDTH], _>([Mersenne31::ORDER_U32; WIDTH]) };
+const ODD: __mmask16 = 0b1010101010101010;
+
+/// Vectorized AVX-512 implementation of `Mersenne31` arithmetic.
+///
+/// Like `Mersenne31`, each lane holds a value of at most `P`, which need not be canonical.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)] // This is needed to make `transmute`s safe.
+pub struct PackedMersenne31AVX512(pub [Mersenne31; WIDTH]);
+
+impl PackedMersenne31AVX512 {
+    #[inline]
+    #[must_use]
+    fn to_vector(self) -> __m512i {
+        // SAFETY: `Self` is `repr(transparent)` over `[Mersenne31; WIDTH]`, which has the same size
+        // as `__m512i`, and `Mersenne31` is `repr(transparent)` over `u32`.
+        unsafe { transmute(self) }
+    }
+
+    /// # Safety
+    /// Each lane of `vector` must be at most `P`.
+    #[inline]
+    #[must_use]
+    unsafe fn from_vector(vector: __m512i) -> Self {
+        transmute(vector)
+    }
+}
+
+/// Add two vectors of values at most `P`.
+#[inline]
+#[must_use]
+fn add(lhs: __m512i, rhs: __m512i) -> __m512i {
+    // See the AVX2 implementation; the smaller of t and t - P is the reduced sum.
+    unsafe {
+        let t = _mm512_add_epi32(lhs, rhs);
+        let u = _mm512_sub_epi32(t, P);
+        _mm512_min_epu32(t, u)
+    }
+}
+
+/// Subtract two vectors of values at most `P`.
+#[inline]
+#[must_use]
+fn sub(lhs: __m512i, rhs: __m512i) -> __m512i {
+    unsafe {
+        let t = _mm512_sub_epi32(lhs, rhs);
+        let u = _mm512_add_epi32(t, P);
+        _mm512_min_epu32(t, u)
+    }
+}
+
+/// Negate a vector of values at most `P`.
+#[inline]
+#[must_use]
+fn neg(val: __m512i) -> __m512i {
+    unsafe { _mm512_sub_epi32(P, val) }
+}
+
+/// Multiply two vectors of values at most `P`.
+#[inline]
+#[must_use]
+fn mul(lhs: __m512i, rhs: __m512i) -> __m512i {
+    // As in the AVX2 implementation, compute the even and odd 62-bit products separately, then
+    // reduce each to the sum of its low 31 bits and the rest.
+    unsafe {
+        let lhs_odd = _mm512_srli_epi64::<32>(lhs);
+        let rhs_odd = _mm512_srli_epi64::<32>(rhs);
+        let prod_even = _mm512_mul_epu32(lhs, rhs);
+        let prod_odd = _mm512_mul_epu32(lhs_odd, rhs_odd);
+
+        let lo = _mm512_mask_blend_epi32(ODD, prod_even, _mm512_slli_epi64::<32>(prod_odd));
+        let lo = _mm512_and_si512(lo, P);
+        let hi = _mm512_mask_blend_epi32(
+            ODD,
+            _mm512_srli_epi64::<31>(prod_even),
+            _mm512_slli_epi64::<1>(prod_odd),
+        );
+        add(lo, hi)
+    }
+}
+
+impl Add for PackedMersenne31AVX512 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(add(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Sub for PackedMersenne31AVX512 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(sub(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl Neg for PackedMersenne31AVX512 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        unsafe { Self::from_vector(neg(self.to_vector())) }
+    }
+}
+
+impl Mul for PackedMersenne31AVX512 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        unsafe { Self::from_vector(mul(self.to_vector(), rhs.to_vector())) }
+    }
+}
+
+impl_packed_field_arithmetic!(PackedMersenne31AVX512, Mersenne31, WIDTH);
+
+/// For each block length, the indices passed to `_mm512_permutex2var_epi32` to build the first and
+/// second outputs of `interleave`. Indices from 16 up select lanes of the second operand.
+const fn interleave_indices(block_len: usize) -> ([u32; WIDTH], [u32; WIDTH]) {
+    let mut lo = [0; WIDTH];
+    let mut hi = [0; WIDTH];
+    let mut i = 0;
+    while i < WIDTH {
+        // Lane i sits at offset j within a group of 2 * block_len lanes.
+        let group = i / (2 * block_len) * (2 * block_len);
+        let j = i % (2 * block_len);
+        let (from_other, offset) = (j >= block_len, j % block_len);
+        let src = (group + offset) as u32 + if from_other { WIDTH as u32 } else { 0 };
+        lo[i] = src;
+        hi[i] = src + block_len as u32;
+        i += 1;
+    }
+    (lo, hi)
+}
+
+const INTERLEAVE_1: ([u32; WIDTH], [u32; WIDTH]) = interleave_indices(1);
+const INTERLEAVE_2: ([u32; WIDTH], [u32; WIDTH]) = interleave_indices(2);
+const INTERLEAVE_4: ([u32; WIDTH], [u32; WIDTH]) = interleave_indices(4);
+const INTERLEAVE_8: ([u32; WIDTH], [u32; WIDTH]) = interleave_indices(8);
+
+/// Interleave the lanes of `a` and `b` in blocks of `block_len`; see `PackedField::interleave`.
+#[inline]
+#[must_use]
+fn interleave(a: __m512i, b: __m512i, block_len: usize) -> (__m512i, __m512i) {
+    let (lo, hi) = match block_len {
+        1 => INTERLEAVE_1,
+        2 => INTERLEAVE_2,
+        4 => INTERLEAVE_4,
+        8 => INTERLEAVE_8,
+        WIDTH => return (a, b),
+        _ => panic!("unsupported block length"),
+    };
+    unsafe {
+        let lo: __m512i = transmute(lo);
+        let hi: __m512i = transmute(hi);
+        (
+            _mm512_permutex2var_epi32(a, lo, b),
+            _mm512_permutex2var_epi32(a, hi, b),
+        )
+    }
+}
+
+unsafe impl PackedField for PackedMersenne31AVX512 {
+    type Scalar = Mersenne31;
+
+    const WIDTH: usize = WIDTH;
+    const ZEROS: Self = Self::ZERO;
+    const ONES: Self = Self::ONE;
+
+    #[inline]
+    fn from_slice(slice: &[Mersenne31]) -> &Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Mersenne31; WIDTH]`.
+        unsafe { &*slice.as_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_slice_mut(slice: &mut [Mersenne31]) -> &mut Self {
+        assert_eq!(slice.len(), WIDTH);
+        // SAFETY: `Self` is `repr(transparent)` over `[Mersenne31; WIDTH]`.
+        unsafe { &mut *slice.as_mut_ptr().cast() }
+    }
+
+    #[inline]
+    fn from_fn<F: FnMut(usize) -> Mersenne31>(f: F) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[Mersenne31] {
+        &self.0
+    }
+
+    #[inline]
+    fn as_slice_mut(&mut self) -> &mut [Mersenne31] {
+        &mut self.0
+    }
+
+    #[inline]
+    fn interleave(&self, other: Self, block_len: usize) -> (Self, Self) {
+        let (a, b) = interleave(self.to_vector(), other.to_vector(), block_len);
+        unsafe { (Self::from_vector(a), Self::from_vector(b)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedMersenne31AVX512;
+    use crate::Mersenne31;
+    use p3_field::{AbstractField, PackedField, PrimeField32};
+    use p3_field_testing::check_packed_field;
+
+    type P = PackedMersenne31AVX512;
+
+    #[test]
+    fn packed_field() {
+        check_packed_field::<P>();
+    }
+
+    #[test]
+    fn non_canonical_lanes() {
+        // P itself is a valid, non-canonical encoding of zero.
+        let p = P::from(Mersenne31::new(Mersenne31::ORDER_U32));
+        let x = P::from_fn(|i| Mersenne31::from_canonical_usize(i + 1));
+        assert_eq!(p + x, x);
+        assert_eq!(x - p, x);
+        assert_eq!(p - x, -x);
+        assert_eq!(p * x, P::ZERO);
+        assert_eq!(-p, P::ZERO);
+        assert_eq!(p + p, P::ZERO);
+        assert!((p * p).0.iter().all(|v| v.value <= Mersenne31::ORDER_U32));
+    }
+}
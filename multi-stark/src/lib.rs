@@ -4,12 +4,18 @@
 
 extern crate alloc;
 
-mod config;
-mod folder;
+mod proof;
 mod prover;
 mod sym_var;
+mod table;
+mod verifier;
 
-pub use config::*;
-pub use folder::*;
+pub use p3_uni_stark::{
+    Com, ConstraintFolder, PcsError, PcsProof, PcsProverData, StarkConfig, StarkConfigImpl,
+    VerifierConstraintFolder,
+};
+pub use proof::*;
 pub use prover::*;
 pub use sym_var::*;
+pub use table::*;
+pub use verifier::*;
@@ -0,0 +1,27 @@
+use crate::{Com, PcsProof, StarkConfig};
+use alloc::vec::Vec;
+use p3_uni_stark::OpenedValues;
+
+/// A proof of several tables at once. The tables share all of their challenges, and may interact
+/// with one another over buses.
+pub struct Proof<SC: StarkConfig> {
+    /// One proof per table, in the order the tables were given.
+    pub table_proofs: Vec<TableProof<SC>>,
+}
+
+pub struct TableProof<SC: StarkConfig> {
+    /// The log of this table's height.
+    pub degree_bits: usize,
+    pub trace_commit: Com<SC>,
+    /// A commitment to the table's LogUp permutation trace, if it has any interactions.
+    pub permutation_commit: Option<Com<SC>>,
+    /// The table's share of the LogUp sums, if it has any interactions. These must sum to zero
+    /// across all tables.
+    pub cumulative_sum: Option<SC::Challenge>,
+    /// A commitment to the table's quotient chunks; see `OpenedValues::quotient_chunks`.
+    pub quotient_commit: Com<SC>,
+    pub opened_values: OpenedValues<SC::Challenge>,
+    /// Each table is opened at its own points, `zeta` and `g zeta`, where `g` generates its trace
+    /// domain, so each has its own opening proof.
+    pub opening_proof: PcsProof<SC>,
+}
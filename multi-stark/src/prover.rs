@@ -1,20 +1,262 @@
-use crate::{ConstraintFolder, StarkConfig};
-use p3_air::Air;
+use crate::{Com, MultiTableAir, PcsProverData, Proof, StarkConfig, TableProof};
+use alloc::vec::Vec;
+use itertools::Itertools;
+use p3_air::{
+    cumulative_sum, generate_permutation_trace, Air, Interaction, NUM_PERMUTATION_CHALLENGES,
+};
 use p3_challenger::Challenger;
-use p3_commit::PCS;
+use p3_commit::{UnivariatePCS, PCS};
+use p3_field::{AbstractExtensionField, AbstractField, TwoAdicField};
+use p3_lde::{TwoAdicCosetLDE, TwoAdicLDE};
 use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_uni_stark::{
+    decompose_quotient, flatten_to_base, get_log_quotient_degree, quotient_values, OpenedValues,
+    SymbolicAirBuilder, TraceLdes,
+};
+use p3_util::log2_strict_usize;
 
-pub fn prove<SC, A, Chal>(
+/// Prove several tables at once, given one trace per AIR. Each trace may have its own height.
+pub fn prove<SC, Chal>(
     config: &SC,
-    _air: &A,
-    _challenger: &mut Chal,
-    trace: RowMajorMatrix<SC::Val>,
-) where
+    airs: &[&dyn MultiTableAir<SC>],
+    challenger: &mut Chal,
+    traces: Vec<RowMajorMatrix<SC::Val>>,
+    public_values: &[SC::Val],
+) -> Proof<SC>
+where
     SC: StarkConfig,
-    A: for<'a> Air<ConstraintFolder<'a, SC::Val, SC::Challenge, SC::PackedChallenge>>,
-    Chal: Challenger<SC::Val>,
+    Chal: Challenger<SC::Val> + Clone + Sync,
+    Com<SC>: AsRef<[SC::Val]>,
 {
-    let (_trace_commit, _trace_data) = config.pcs().commit_batch(trace.as_view());
+    assert_eq!(airs.len(), traces.len(), "expected one trace per table");
+    assert!(
+        !config.is_zk(),
+        "multi-stark doesn't support zero-knowledge mode"
+    );
+    let public_values_dom: Vec<SC::Domain> = public_values
+        .iter()
+        .map(|&v| SC::Domain::from_base(v))
+        .collect();
+    challenger.observe_elements(public_values);
 
-    // challenger.observe_ext_element(trace_commit); // TODO
+    // Commit to each table's main trace, at its own height.
+    let mut tables = airs
+        .iter()
+        .zip(traces)
+        .map(|(&air, trace)| {
+            let sends = <dyn MultiTableAir<SC> as Air<SymbolicAirBuilder<SC::Domain>>>::sends(air);
+            let receives =
+                <dyn MultiTableAir<SC> as Air<SymbolicAirBuilder<SC::Domain>>>::receives(air);
+            let window_size =
+                <dyn MultiTableAir<SC> as Air<SymbolicAirBuilder<SC::Domain>>>::window_size(air);
+            assert!(
+                window_size >= 2,
+                "an AIR's window must span at least two rows"
+            );
+            assert_eq!(
+                trace.width(),
+                <dyn MultiTableAir<SC> as Air<SymbolicAirBuilder<SC::Domain>>>::width(air),
+                "each trace must have its AIR's width"
+            );
+            let degree_bits = log2_strict_usize(trace.height());
+            let quotient_degree_bits = get_log_quotient_degree::<SC::Domain, _>(
+                air,
+                0,
+                trace.width(),
+                public_values.len(),
+                false,
+            );
+            let has_permutation = !sends.is_empty() || !receives.is_empty();
+            let trace_dom = has_permutation.then(|| trace.map(SC::Domain::from_base));
+            let trace_lde = config.lde().lde_batch(trace.clone(), quotient_degree_bits);
+            let (trace_commit, trace_data) = config.pcs().commit_batch(trace);
+            challenger.observe_element(SC::Val::from_canonical_usize(degree_bits));
+            challenger.observe_elements(trace_commit.as_ref());
+            TableProver {
+                air,
+                sends,
+                receives,
+                window_size,
+                degree_bits,
+                quotient_degree_bits,
+                trace_dom,
+                trace_lde,
+                trace_commit,
+                trace_data,
+                permutation: None,
+            }
+        })
+        .collect_vec();
+
+    // The tables share their LogUp randomness, so that the tuples one table sends can match those
+    // another receives.
+    let has_permutation = tables.iter().any(|table| table.trace_dom.is_some());
+    let permutation_challenges: Vec<SC::Challenge> = if has_permutation {
+        (0..NUM_PERMUTATION_CHALLENGES)
+            .map(|_| challenger.random_ext_element())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    for table in &mut tables {
+        let Some(trace_dom) = table.trace_dom.take() else {
+            continue;
+        };
+        let permutation_trace = generate_permutation_trace(
+            &table.sends,
+            &table.receives,
+            None,
+            &trace_dom,
+            &permutation_challenges,
+        );
+        let cumulative_sum = cumulative_sum(&permutation_trace);
+        let permutation_trace = flatten_to_base::<SC::Val, SC::Challenge>(&permutation_trace);
+        let lde = config
+            .lde()
+            .lde_batch(permutation_trace.clone(), table.quotient_degree_bits);
+        let (commit, data) = config.pcs().commit_batch(permutation_trace);
+        challenger.observe_elements(commit.as_ref());
+        challenger.observe_ext_element(cumulative_sum);
+        table.permutation = Some(PermutationPhase {
+            lde,
+            commit,
+            data,
+            cumulative_sum,
+        });
+    }
+
+    let alpha = challenger.random_ext_element::<SC::Challenge>();
+    let challenge_d = <SC::Challenge as AbstractExtensionField<SC::Val>>::D;
+    let quotient_commitments = tables
+        .iter()
+        .map(|table| {
+            let coset_shift = config
+                .lde()
+                .shift(table.degree_bits + table.quotient_degree_bits);
+            let quotient_values = quotient_values::<
+                SC::Val,
+                SC::Domain,
+                SC::Challenge,
+                SC::PackedChallenge,
+                dyn MultiTableAir<SC>,
+            >(
+                table.air,
+                table.degree_bits,
+                table.quotient_degree_bits,
+                coset_shift,
+                TraceLdes {
+                    preprocessed: None,
+                    main: &table.trace_lde,
+                    permutation: table.permutation.as_ref().map(|p| &p.lde),
+                },
+                &public_values_dom,
+                &permutation_challenges,
+                table
+                    .permutation
+                    .as_ref()
+                    .map_or(SC::Challenge::ZERO, |p| p.cumulative_sum),
+                alpha,
+            );
+            let quotient_chunks =
+                decompose_quotient(quotient_values, coset_shift, table.degree_bits);
+
+            // Commit to the chunks as a single matrix, flattening each value into its base field
+            // coefficients.
+            let quotient_chunk_values = (0..1 << table.degree_bits)
+                .flat_map(|row| {
+                    quotient_chunks.iter().flat_map(move |chunk| {
+                        <SC::Challenge as AbstractExtensionField<SC::Val>>::as_base_slice(
+                            &chunk[row],
+                        )
+                        .to_vec()
+                    })
+                })
+                .collect();
+            let quotient_chunks_matrix =
+                RowMajorMatrix::new(quotient_chunk_values, quotient_chunks.len() * challenge_d);
+            let (quotient_commit, quotient_data) =
+                config.pcs().commit_batch(quotient_chunks_matrix);
+            challenger.observe_elements(quotient_commit.as_ref());
+            (quotient_commit, quotient_data)
+        })
+        .collect_vec();
+
+    let zeta = challenger.random_ext_element::<SC::Challenge>();
+    let table_proofs = tables
+        .into_iter()
+        .zip(quotient_commitments)
+        .map(|(table, (quotient_commit, quotient_data))| {
+            let g_subgroup = SC::Domain::primitive_root_of_unity(table.degree_bits);
+            let prover_data = [&table.trace_data]
+                .into_iter()
+                .chain(table.permutation.as_ref().map(|p| &p.data))
+                .chain([&quotient_data])
+                .collect_vec();
+            // Open every batch at zeta and its shifts by each further row of the table's window.
+            let opening_points = g_subgroup
+                .powers()
+                .take(table.window_size)
+                .map(|g_i| zeta * g_i)
+                .collect_vec();
+            let (opened_values, opening_proof) =
+                config
+                    .pcs()
+                    .open_multi_batches(&prover_data, &opening_points, challenger);
+            let mut opened_values = opened_values.into_iter();
+            let trace = opened_values.next().unwrap();
+            let permutation = table
+                .permutation
+                .as_ref()
+                .map(|_| opened_values.next().unwrap())
+                .unwrap_or_default();
+            let quotient_chunks = opened_values.next().unwrap();
+
+            let (permutation_commit, cumulative_sum) = match table.permutation {
+                Some(p) => (Some(p.commit), Some(p.cumulative_sum)),
+                None => (None, None),
+            };
+            TableProof {
+                degree_bits: table.degree_bits,
+                trace_commit: table.trace_commit,
+                permutation_commit,
+                cumulative_sum,
+                quotient_commit,
+                opened_values: OpenedValues {
+                    preprocessed: Vec::new(),
+                    trace,
+                    permutation,
+                    quotient_chunks,
+                },
+                opening_proof,
+            }
+        })
+        .collect();
+
+    Proof { table_proofs }
+}
+
+/// The prover's state for one table.
+struct TableProver<'a, SC: StarkConfig> {
+    air: &'a dyn MultiTableAir<SC>,
+    sends: Vec<Interaction<SC::Domain>>,
+    receives: Vec<Interaction<SC::Domain>>,
+    window_size: usize,
+    degree_bits: usize,
+    quotient_degree_bits: usize,
+    /// The main trace, over the domain field. Only kept until the permutation trace, which is
+    /// generated from it, has been committed, and only if the table has interactions.
+    trace_dom: Option<RowMajorMatrix<SC::Domain>>,
+    trace_lde: RowMajorMatrix<SC::Domain>,
+    trace_commit: Com<SC>,
+    trace_data: PcsProverData<SC>,
+    permutation: Option<PermutationPhase<SC>>,
+}
+
+/// A table's committed LogUp permutation trace.
+struct PermutationPhase<SC: StarkConfig> {
+    lde: RowMajorMatrix<SC::Domain>,
+    commit: Com<SC>,
+    data: PcsProverData<SC>,
+    cumulative_sum: SC::Challenge,
 }
@@ -0,0 +1,23 @@
+use crate::{ConstraintFolder, StarkConfig, VerifierConstraintFolder};
+use p3_air::Air;
+use p3_uni_stark::SymbolicAirBuilder;
+
+/// An AIR which can be proven as one table of a multi-table proof.
+///
+/// This is implemented for any AIR which can be evaluated with each of the builders a multi-table
+/// proof uses, and lets tables of different types be passed together as trait objects.
+pub trait MultiTableAir<SC: StarkConfig>:
+    Air<SymbolicAirBuilder<SC::Domain>>
+    + for<'a> Air<ConstraintFolder<'a, SC::Domain, SC::Challenge, SC::PackedChallenge>>
+    + for<'a> Air<VerifierConstraintFolder<'a, SC::Domain, SC::Challenge>>
+{
+}
+
+impl<SC, A> MultiTableAir<SC> for A
+where
+    SC: StarkConfig,
+    A: Air<SymbolicAirBuilder<SC::Domain>>
+        + for<'a> Air<ConstraintFolder<'a, SC::Domain, SC::Challenge, SC::PackedChallenge>>
+        + for<'a> Air<VerifierConstraintFolder<'a, SC::Domain, SC::Challenge>>,
+{
+}
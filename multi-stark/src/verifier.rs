@@ -0,0 +1,291 @@
+use crate::{Com, MultiTableAir, PcsError, Proof, StarkConfig, VerifierConstraintFolder};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use itertools::Itertools;
+use p3_air::{
+    eval_permutation_constraints, permutation_trace_width, Air, Interaction, TwoRowMatrixView,
+    WindowMatrixView, NUM_PERMUTATION_CHALLENGES,
+};
+use p3_challenger::Challenger;
+use p3_commit::{Dimensions, UnivariatePCS};
+use p3_field::{AbstractExtensionField, AbstractField, AbstractionOf, Field, TwoAdicField};
+use p3_uni_stark::{from_base_coefficients, get_log_quotient_degree, SymbolicAirBuilder};
+
+pub fn verify<SC, Chal>(
+    config: &SC,
+    airs: &[&dyn MultiTableAir<SC>],
+    challenger: &mut Chal,
+    proof: &Proof<SC>,
+    public_values: &[SC::Val],
+) -> Result<(), VerificationError<PcsError<SC>>>
+where
+    SC: StarkConfig,
+    SC::Challenge: AbstractionOf<SC::Domain>,
+    Chal: Challenger<SC::Val>,
+    Com<SC>: AsRef<[SC::Val]> + Clone,
+{
+    let table_proofs = &proof.table_proofs;
+    if table_proofs.len() != airs.len() {
+        return Err(VerificationError::InvalidProofShape);
+    }
+    let challenge_d = <SC::Challenge as AbstractExtensionField<SC::Val>>::D;
+
+    // Work out the shape each table's proof should have, and check it.
+    let shapes = airs
+        .iter()
+        .zip(table_proofs)
+        .map(|(&air, table_proof)| {
+            let opened_values = &table_proof.opened_values;
+            let trace_width =
+                <dyn MultiTableAir<SC> as Air<SymbolicAirBuilder<SC::Domain>>>::width(air);
+            let quotient_degree_bits = get_log_quotient_degree::<SC::Domain, _>(
+                air,
+                0,
+                trace_width,
+                public_values.len(),
+                false,
+            );
+            let sends = <dyn MultiTableAir<SC> as Air<SymbolicAirBuilder<SC::Domain>>>::sends(air);
+            let receives =
+                <dyn MultiTableAir<SC> as Air<SymbolicAirBuilder<SC::Domain>>>::receives(air);
+            let has_permutation = !sends.is_empty() || !receives.is_empty();
+            let permutation_width = if has_permutation {
+                permutation_trace_width(&sends, &receives) * challenge_d
+            } else {
+                0
+            };
+            let quotient_width = (1 << quotient_degree_bits) * challenge_d;
+            let window_size =
+                <dyn MultiTableAir<SC> as Air<SymbolicAirBuilder<SC::Domain>>>::window_size(air);
+            assert!(
+                window_size >= 2,
+                "an AIR's window must span at least two rows"
+            );
+
+            // A committed batch must be opened at each point of the window, with its full width
+            // at each, while an absent one has no openings at all.
+            let batch_shape_ok = |values: &[Vec<SC::Challenge>], present: bool, width: usize| {
+                if present {
+                    values.len() == window_size && values.iter().all(|v| v.len() == width)
+                } else {
+                    values.is_empty()
+                }
+            };
+            // The table's quotient domain, extended by the PCS's blowup, must fit in the two-adic
+            // subgroup.
+            let degree_bits_ok = SC::Domain::TWO_ADICITY
+                .checked_sub(config.pcs().log_blowup() + quotient_degree_bits)
+                .is_some_and(|max_degree_bits| table_proof.degree_bits <= max_degree_bits);
+            let shape_ok = degree_bits_ok
+                && table_proof.permutation_commit.is_some() == has_permutation
+                && table_proof.cumulative_sum.is_some() == has_permutation
+                && opened_values.preprocessed.is_empty()
+                && batch_shape_ok(&opened_values.trace, true, trace_width)
+                && batch_shape_ok(
+                    &opened_values.permutation,
+                    has_permutation,
+                    permutation_width,
+                )
+                && batch_shape_ok(&opened_values.quotient_chunks, true, quotient_width);
+            if !shape_ok {
+                return Err(VerificationError::InvalidProofShape);
+            }
+            Ok(TableShape {
+                sends,
+                receives,
+                window_size,
+                trace_width,
+                permutation_width,
+                quotient_width,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Every interaction sent by one table must be received by some other, so the tables' LogUp
+    // sums must cancel out.
+    let total_sum = table_proofs
+        .iter()
+        .filter_map(|table_proof| table_proof.cumulative_sum)
+        .sum::<SC::Challenge>();
+    if total_sum != SC::Challenge::ZERO {
+        return Err(VerificationError::UnbalancedInteractions);
+    }
+
+    challenger.observe_elements(public_values);
+    for table_proof in table_proofs {
+        challenger.observe_element(SC::Val::from_canonical_usize(table_proof.degree_bits));
+        challenger.observe_elements(table_proof.trace_commit.as_ref());
+    }
+    let mut permutation_challenges = Vec::new();
+    if table_proofs.iter().any(|t| t.permutation_commit.is_some()) {
+        permutation_challenges = (0..NUM_PERMUTATION_CHALLENGES)
+            .map(|_| challenger.random_ext_element::<SC::Challenge>())
+            .collect();
+    }
+    for table_proof in table_proofs {
+        if let (Some(commit), Some(cumulative_sum)) =
+            (&table_proof.permutation_commit, table_proof.cumulative_sum)
+        {
+            challenger.observe_elements(commit.as_ref());
+            challenger.observe_ext_element(cumulative_sum);
+        }
+    }
+    let alpha = challenger.random_ext_element::<SC::Challenge>();
+    for table_proof in table_proofs {
+        challenger.observe_elements(table_proof.quotient_commit.as_ref());
+    }
+    let zeta = challenger.random_ext_element::<SC::Challenge>();
+
+    let public_values_dom: Vec<SC::Domain> = public_values
+        .iter()
+        .map(|&v| SC::Domain::from_base(v))
+        .collect();
+
+    for ((&air, table_proof), shape) in airs.iter().zip(table_proofs).zip(shapes) {
+        let degree_bits = table_proof.degree_bits;
+        let opened_values = &table_proof.opened_values;
+        let g_subgroup = SC::Domain::primitive_root_of_unity(degree_bits);
+
+        let window_size = shape.window_size;
+        let subgroup_last = g_subgroup.inverse();
+        // The points g^-1, ..., g^-(window_size - 1), for the transition selectors.
+        let last_rows = subgroup_last
+            .powers()
+            .skip(1)
+            .take(window_size - 1)
+            .collect_vec();
+
+        // Each committed batch, with its width and its openings at each point of the window, in
+        // the order in which the prover opens them.
+        let trace_batch = (
+            table_proof.trace_commit.clone(),
+            shape.trace_width,
+            opened_values.trace.clone(),
+        );
+        let permutation_batch = table_proof.permutation_commit.as_ref().map(|commit| {
+            (
+                commit.clone(),
+                shape.permutation_width,
+                opened_values.permutation.clone(),
+            )
+        });
+        let quotient_batch = (
+            table_proof.quotient_commit.clone(),
+            shape.quotient_width,
+            opened_values.quotient_chunks.clone(),
+        );
+        let batches = [trace_batch]
+            .into_iter()
+            .chain(permutation_batch)
+            .chain([quotient_batch]);
+        let (commits, widths, values): (Vec<_>, Vec<_>, Vec<_>) = batches.multiunzip();
+        let dims = widths
+            .into_iter()
+            .map(|width| {
+                vec![Dimensions {
+                    width,
+                    log2_height: degree_bits,
+                }]
+            })
+            .collect::<Vec<_>>();
+        let opening_points = g_subgroup
+            .powers()
+            .take(window_size)
+            .map(|g_i| zeta * g_i)
+            .collect_vec();
+        config
+            .pcs()
+            .verify_multi_batches(
+                &commits,
+                &dims,
+                &opening_points,
+                &values,
+                &table_proof.opening_proof,
+                challenger,
+            )
+            .map_err(VerificationError::InvalidOpeningArgument)?;
+
+        // Recompose the quotient from its chunks: each chunk's columns hold its base field
+        // coefficients, and the chunks are combined as Q(x) = sum_i x^(i n) Q_i(x).
+        let zeta_pow_n = zeta.exp_power_of_2(degree_bits);
+        let quotient = opened_values.quotient_chunks[0]
+            .chunks(challenge_d)
+            .zip(zeta_pow_n.powers())
+            .map(|(chunk_coeffs, zeta_pow_in)| {
+                from_base_coefficients::<SC::Val, SC::Challenge>(chunk_coeffs) * zeta_pow_in
+            })
+            .sum::<SC::Challenge>();
+
+        // Likewise, recombine the permutation trace's extension field values from their
+        // coefficients. Its constraints only span two rows.
+        let recombine = |coeffs: &[SC::Challenge]| {
+            coeffs
+                .chunks(challenge_d)
+                .map(from_base_coefficients::<SC::Val, SC::Challenge>)
+                .collect_vec()
+        };
+        let (permutation_local, permutation_next) = match opened_values.permutation.as_slice() {
+            [local, next, ..] => (recombine(local), recombine(next)),
+            _ => (Vec::new(), Vec::new()),
+        };
+        let main = opened_values.trace.concat();
+
+        let zerofier = zeta_pow_n - SC::Challenge::ONE;
+        let mut folder = VerifierConstraintFolder {
+            preprocessed: WindowMatrixView::new(&[], window_size),
+            main: WindowMatrixView::new(&main, window_size),
+            permutation: TwoRowMatrixView {
+                local: &permutation_local,
+                next: &permutation_next,
+            },
+            permutation_challenges: &permutation_challenges,
+            public_values: &public_values_dom,
+            is_first_row: zerofier / (zeta - SC::Challenge::ONE),
+            is_last_row: zerofier / (zeta - subgroup_last),
+            x: zeta,
+            last_rows: &last_rows,
+            alpha,
+            accumulator: SC::Challenge::ZERO,
+            _phantom_f: PhantomData,
+        };
+        air.eval(&mut folder);
+        if let Some(cumulative_sum) = table_proof.cumulative_sum {
+            eval_permutation_constraints(
+                &shape.sends,
+                &shape.receives,
+                &mut folder,
+                cumulative_sum,
+            );
+        }
+
+        if folder.accumulator != quotient * zerofier {
+            return Err(VerificationError::OodEvaluationMismatch);
+        }
+    }
+
+    Ok(())
+}
+
+/// What a table's proof should look like, given its AIR.
+struct TableShape<F: Field> {
+    sends: Vec<Interaction<F>>,
+    receives: Vec<Interaction<F>>,
+    window_size: usize,
+    trace_width: usize,
+    permutation_width: usize,
+    quotient_width: usize,
+}
+
+#[derive(Debug)]
+pub enum VerificationError<PcsErr> {
+    InvalidProofShape,
+    /// The PCS rejected the claimed openings of some table.
+    InvalidOpeningArgument(PcsErr),
+    /// Some table's constraints, evaluated at the out-of-domain point `zeta`, don't match its
+    /// quotient times the zerofier.
+    OodEvaluationMismatch,
+    /// The tables' LogUp sums don't add up to zero, so some interaction sent over a bus was never
+    /// received, or vice versa.
+    UnbalancedInteractions,
+}
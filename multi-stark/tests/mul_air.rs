@@ -1,72 +1,204 @@
-// use p3_air::{Air, AirBuilder};
-// use p3_multi_stark::{prove, StarkConfigImpl};
-// use p3_challenger::DuplexChallenger;
-// use p3_fri::FRIBasedPCS;
-// use p3_lde::NaiveCosetLDE;
-// use p3_matrix::dense::RowMajorMatrix;
-// use p3_matrix::Matrix;
-// use p3_merkle_tree::MerkleTreeMMCS;
-// use p3_poseidon::Poseidon;
-// use p3_symmetric::compression::TruncatedPermutation;
-// use p3_symmetric::permutation::{ArrayPermutation, CryptographicPermutation, MDSPermutation};
-// use p3_symmetric::sponge::PaddingFreeSponge;
-// use rand::thread_rng;
-// use p3_mersenne_31::Mersenne31;
-// use p3_tensor_pcs::TensorPCS;
-//
-// struct MulAir;
-//
-// impl<AB: AirBuilder> Air<AB> for MulAir {
-//     fn eval(&self, builder: &mut AB) {
-//         let main = builder.main();
-//         let main_local = main.row(0);
-//         let diff = main_local[0] * main_local[1] - main_local[2];
-//         builder.assert_zero(diff);
-//     }
-// }
-//
-// #[test]
-// #[ignore] // TODO: Not ready yet.
-// fn test_prove_goldilocks() {
-//     type Val = Mersenne31;
-//     type Challenge = Mersenne31; // TODO
-//
-//     #[derive(Clone)]
-//     struct MyMds;
-//     impl CryptographicPermutation<[Val; 8]> for MyMds {
-//         fn permute(&self, input: [Val; 8]) -> [Val; 8] {
-//             input // TODO
-//         }
-//     }
-//     impl ArrayPermutation<Val, 8> for MyMds {}
-//     impl MDSPermutation<Val, 8> for MyMds {}
-//
-//     type MDS = MyMds;
-//     let mds = MyMds;
-//
-//     type Perm = Poseidon<Val, MDS, 8, 7>;
-//     let perm = Perm::new(5, 5, vec![], mds);
-//
-//     type H4 = PaddingFreeSponge<Val, Perm, { 4 + 4 }>;
-//     let h4 = H4::new(perm.clone());
-//
-//     type C = TruncatedPermutation<Val, Perm, 2, 4, { 2 * 4 }>;
-//     let c = C::new(perm.clone());
-//
-//     type MMCS = MerkleTreeMMCS<Val, [Val; 4], H4, C>;
-//     type PCS = TensorPCS<Val, MyCode>;
-//     type MyConfig = StarkConfigImpl<Val, Challenge, Challenge, PCS>;
-//
-//     let mut rng = thread_rng();
-//     let trace = RowMajorMatrix::rand(&mut rng, 256, 10);
-//     let pcs = todo!();
-//     let config = StarkConfigImpl::new(pcs);
-//     let mut challenger = DuplexChallenger::new(perm);
-//     prove::<MyConfig, _, _>(&MulAir, config, &mut challenger, trace);
-// }
-//
-// #[test]
-// #[ignore] // TODO: Not ready yet.
-// fn test_prove_mersenne_31() {
-//     todo!()
-// }
+use p3_air::{Air, AirBuilder, Interaction, VirtualPairCol};
+use p3_challenger::DuplexChallenger;
+use p3_field::{AbstractField, Field, TwoAdicField};
+use p3_fri::{FRIBasedPCS, FriConfig, FriLDT};
+use p3_goldilocks::Goldilocks;
+use p3_lde::NaiveCosetLDE;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::MatrixRows;
+use p3_merkle_tree::MerkleTreeMMCS;
+use p3_multi_stark::{prove, verify, MultiTableAir, Proof, StarkConfigImpl, VerificationError};
+use p3_symmetric::compression::TruncatedPermutation;
+use p3_symmetric::mds::NaiveMDSMatrix;
+use p3_symmetric::sponge::PaddingFreeSponge;
+use rand::{thread_rng, Rng};
+
+/// The bus over which `MulAir` sends its products to `ProductsAir`.
+const PRODUCTS_BUS: usize = 0;
+
+/// Multiplications `a * b = c`, where each product `c` is looked up in `ProductsAir`.
+struct MulAir;
+
+impl<AB: AirBuilder> Air<AB> for MulAir {
+    fn width(&self) -> usize {
+        3
+    }
+
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row(0);
+        builder.assert_eq(local[0] * local[1], local[2]);
+    }
+
+    fn sends(&self) -> Vec<Interaction<AB::F>> {
+        vec![Interaction::new(
+            vec![VirtualPairCol::single_main(2)],
+            VirtualPairCol::one(),
+            PRODUCTS_BUS,
+        )]
+    }
+}
+
+/// A table of values, in its first column, along with the number of times each is looked up.
+struct ProductsAir;
+
+impl<AB: AirBuilder> Air<AB> for ProductsAir {
+    fn width(&self) -> usize {
+        2
+    }
+
+    fn eval(&self, _builder: &mut AB) {
+        // All the constraints come from the interactions.
+    }
+
+    fn receives(&self) -> Vec<Interaction<AB::F>> {
+        vec![Interaction::new(
+            vec![VirtualPairCol::single_main(0)],
+            VirtualPairCol::single_main(1),
+            PRODUCTS_BUS,
+        )]
+    }
+}
+
+/// Random multiplications of numbers below 3, along with a table of `0..table_rows` which counts
+/// their products.
+fn random_traces<F: Field>(
+    mul_rows: usize,
+    table_rows: usize,
+) -> (RowMajorMatrix<F>, RowMajorMatrix<F>) {
+    let mut rng = thread_rng();
+    let mut multiplicities = vec![0; table_rows];
+    let mul_values = (0..mul_rows)
+        .flat_map(|_| {
+            let (a, b) = (rng.gen_range(0..3), rng.gen_range(0..3));
+            multiplicities[a * b] += 1;
+            [a, b, a * b]
+        })
+        .map(F::from_canonical_usize)
+        .collect();
+    let table_values = multiplicities
+        .into_iter()
+        .enumerate()
+        .flat_map(|(value, multiplicity)| [value, multiplicity])
+        .map(F::from_canonical_usize)
+        .collect();
+    (
+        RowMajorMatrix::new(mul_values, 3),
+        RowMajorMatrix::new(table_values, 2),
+    )
+}
+
+type Val = Goldilocks;
+type Domain = Goldilocks;
+type Challenge = Goldilocks; // TODO
+type PackedChallenge = <Challenge as Field>::Packing;
+type Perm = NaiveMDSMatrix<Val, 8>; // TODO: Use a real permutation.
+type H4 = PaddingFreeSponge<Val, Perm, 8>;
+type C = TruncatedPermutation<Val, Perm, 2, 4, 8>;
+type Mmcs = MerkleTreeMMCS<Val, [Val; 4], H4, C, RowMajorMatrix<Val>>;
+type Ldt = FriLDT<Val, Challenge, Mmcs, Mmcs>;
+type Pcs = FRIBasedPCS<Val, Domain, Challenge, NaiveCosetLDE, Mmcs, Mmcs>;
+type MyConfig = StarkConfigImpl<Val, Domain, Challenge, PackedChallenge, Pcs, NaiveCosetLDE>;
+
+fn config(perm: &Perm) -> MyConfig {
+    let fri_config = FriConfig {
+        log_blowup: 1,
+        num_queries: 40,
+        proof_of_work_bits: 8,
+        log_folding_factor: 1,
+        log_final_poly_len: 0,
+    };
+    let mmcs = || Mmcs::new(H4::new(perm.clone()), C::new(perm.clone()), 2);
+    let ldt = Ldt::new(fri_config, mmcs());
+    let pcs = Pcs::new(NaiveCosetLDE, 1, mmcs(), ldt);
+    StarkConfigImpl::new(pcs, NaiveCosetLDE)
+}
+
+fn random_perm() -> Perm {
+    let mut rng = thread_rng();
+    Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())))
+}
+
+#[test]
+fn test_prove_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let airs: [&dyn MultiTableAir<MyConfig>; 2] = [&MulAir, &ProductsAir];
+    let (mul_trace, table_trace) = random_traces::<Val>(1 << 5, 1 << 3);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove(
+        &config,
+        &airs,
+        &mut challenger,
+        vec![mul_trace, table_trace],
+        &[],
+    );
+    let degree_bits = proof.table_proofs.iter().map(|t| t.degree_bits);
+    assert!(degree_bits.eq([5, 3]));
+
+    let mut challenger = DuplexChallenger::new(perm);
+    verify(&config, &airs, &mut challenger, &proof, &[]).expect("verification failed");
+}
+
+#[test]
+fn test_reject_unbalanced_lookup_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let airs: [&dyn MultiTableAir<MyConfig>; 2] = [&MulAir, &ProductsAir];
+    let (mul_trace, mut table_trace) = random_traces::<Val>(1 << 5, 1 << 3);
+    // Claim that one more product was looked up than really was.
+    table_trace.row_mut(4)[1] += Val::ONE;
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove(
+        &config,
+        &airs,
+        &mut challenger,
+        vec![mul_trace, table_trace],
+        &[],
+    );
+
+    let mut challenger = DuplexChallenger::new(perm);
+    assert!(matches!(
+        verify(&config, &airs, &mut challenger, &proof, &[]),
+        Err(VerificationError::UnbalancedInteractions)
+    ));
+}
+
+#[test]
+fn test_reject_malformed_proof_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let airs: [&dyn MultiTableAir<MyConfig>; 2] = [&MulAir, &ProductsAir];
+
+    // Each of these would otherwise make the verifier index out of bounds, or ask for a root of
+    // unity of an order the field doesn't have.
+    let tampers: [fn(&mut Proof<MyConfig>); 4] = [
+        |proof| proof.table_proofs[0].degree_bits = Val::TWO_ADICITY,
+        |proof| proof.table_proofs[1].degree_bits = usize::MAX,
+        |proof| proof.table_proofs[0].opened_values.trace.clear(),
+        |proof| {
+            let trace = &mut proof.table_proofs[1].opened_values.trace;
+            trace.iter_mut().for_each(Vec::clear);
+        },
+    ];
+    for tamper in tampers {
+        let (mul_trace, table_trace) = random_traces::<Val>(1 << 5, 1 << 3);
+        let mut challenger = DuplexChallenger::new(perm.clone());
+        let mut proof = prove(
+            &config,
+            &airs,
+            &mut challenger,
+            vec![mul_trace, table_trace],
+            &[],
+        );
+        tamper(&mut proof);
+
+        let mut challenger = DuplexChallenger::new(perm.clone());
+        assert!(matches!(
+            verify(&config, &airs, &mut challenger, &proof, &[]),
+            Err(VerificationError::InvalidProofShape)
+        ));
+    }
+}
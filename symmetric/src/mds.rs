@@ -1,5 +1,5 @@
 use crate::permutation::{ArrayPermutation, CryptographicPermutation};
-use p3_field::PrimeField;
+use p3_field::{PackedField, PrimeField};
 
 pub trait MDSPermutation<T, const WIDTH: usize>: ArrayPermutation<T, WIDTH> {}
 
@@ -14,11 +14,14 @@ impl<F: PrimeField, const WIDTH: usize> NaiveMDSMatrix<F, WIDTH> {
     }
 }
 
-impl<F: PrimeField, const WIDTH: usize> CryptographicPermutation<[F; WIDTH]>
-    for NaiveMDSMatrix<F, WIDTH>
+/// The matrix can be applied to packed states too, with each lane permuted independently.
+impl<F, P, const WIDTH: usize> CryptographicPermutation<[P; WIDTH]> for NaiveMDSMatrix<F, WIDTH>
+where
+    F: PrimeField,
+    P: PackedField<Scalar = F>,
 {
-    fn permute(&self, input: [F; WIDTH]) -> [F; WIDTH] {
-        let mut output = [F::ZERO; WIDTH];
+    fn permute(&self, input: [P; WIDTH]) -> [P; WIDTH] {
+        let mut output = [P::ZEROS; WIDTH];
         for (i, row) in self.matrix.iter().enumerate() {
             for (j, &x) in row.iter().enumerate() {
                 output[i] += input[j] * x;
@@ -28,6 +31,16 @@ impl<F: PrimeField, const WIDTH: usize> CryptographicPermutation<[F; WIDTH]>
     }
 }
 
-impl<F: PrimeField, const WIDTH: usize> ArrayPermutation<F, WIDTH> for NaiveMDSMatrix<F, WIDTH> {}
+impl<F, P, const WIDTH: usize> ArrayPermutation<P, WIDTH> for NaiveMDSMatrix<F, WIDTH>
+where
+    F: PrimeField,
+    P: PackedField<Scalar = F>,
+{
+}
 
-impl<F: PrimeField, const WIDTH: usize> MDSPermutation<F, WIDTH> for NaiveMDSMatrix<F, WIDTH> {}
+impl<F, P, const WIDTH: usize> MDSPermutation<P, WIDTH> for NaiveMDSMatrix<F, WIDTH>
+where
+    F: PrimeField,
+    P: PackedField<Scalar = F>,
+{
+}
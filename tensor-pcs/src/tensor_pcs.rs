@@ -4,7 +4,7 @@ use alloc::vec::Vec;
 use core::marker::PhantomData;
 use p3_challenger::Challenger;
 use p3_code::LinearCodeFamily;
-use p3_commit::{DirectMMCS, MultivariatePCS, PCS};
+use p3_commit::{Dimensions, DirectMMCS, MultivariatePCS, PCS};
 use p3_field::{ExtensionField, Field};
 use p3_matrix::dense::RowMajorMatrix;
 use p3_matrix::MatrixRows;
@@ -77,7 +77,7 @@ where
     ) -> (Vec<Vec<Vec<EF>>>, Self::Proof)
     where
         EF: ExtensionField<F>,
-        Chal: Challenger<F>,
+        Chal: Challenger<F> + Clone + Sync,
     {
         todo!()
     }
@@ -85,9 +85,11 @@ where
     fn verify_multi_batches<EF, Chal>(
         &self,
         _commits: &[Self::Commitment],
+        _dims: &[Vec<Dimensions>],
         _points: &[Vec<EF>],
         _values: &[Vec<Vec<EF>>],
         _proof: &Self::Proof,
+        _challenger: &mut Chal,
     ) -> Result<(), Self::Error>
     where
         EF: ExtensionField<F>,
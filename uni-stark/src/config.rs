@@ -1,8 +1,12 @@
+use alloc::vec::Vec;
+use core::cell::RefCell;
 use core::marker::PhantomData;
 use p3_commit::UnivariatePCS;
 use p3_field::{AbstractExtensionField, ExtensionField, Field, PackedField, TwoAdicField};
 use p3_lde::TwoAdicCosetLDE;
-use p3_matrix::dense::RowMajorMatrixView;
+use p3_matrix::dense::RowMajorMatrix;
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
 
 pub trait StarkConfig {
     /// A value of the trace.
@@ -12,19 +16,37 @@ pub trait StarkConfig {
     type Domain: ExtensionField<Self::Val> + TwoAdicField;
 
     /// The field from which most random challenges are drawn.
-    type Challenge: ExtensionField<Self::Domain>;
+    type Challenge: ExtensionField<Self::Val> + ExtensionField<Self::Domain>;
 
     type PackedChallenge: PackedField<Scalar = Self::Challenge>
         + AbstractExtensionField<<Self::Domain as Field>::Packing>;
 
-    /// The PCS used to commit to trace polynomials.
-    type PCS: for<'a> UnivariatePCS<Self::Val, RowMajorMatrixView<'a, Self::Val>>;
+    /// The PCS used to commit to trace and quotient polynomials.
+    type PCS: UnivariatePCS<Self::Val, RowMajorMatrix<Self::Val>>;
 
     type LDE: TwoAdicCosetLDE<Self::Val, Self::Domain>;
 
     fn pcs(&self) -> &Self::PCS;
 
     fn lde(&self) -> &Self::LDE;
+
+    /// The source of the values the prover blinds its polynomials with, if proofs should be
+    /// zero-knowledge. If so, the PCS must also be hiding, such as one which commits with
+    /// `HidingMerkleTreeMMCS`, so that its openings reveal nothing either.
+    fn blinding(&self) -> Option<&dyn BlindingRandomness<Self::Val>> {
+        None
+    }
+
+    /// Whether proofs should be zero-knowledge, i.e. whether the config supplies `blinding`.
+    fn is_zk(&self) -> bool {
+        self.blinding().is_some()
+    }
+}
+
+/// Randomness for blinding the polynomials of a zero-knowledge proof.
+pub trait BlindingRandomness<Val> {
+    /// `len` fresh random values.
+    fn random_vals(&self, len: usize) -> Vec<Val>;
 }
 
 pub struct StarkConfigImpl<Val, Domain, Challenge, PackedChallenge, PCS, LDE> {
@@ -56,9 +78,9 @@ impl<Val, Domain, Challenge, PackedChallenge, PCS, LDE> StarkConfig
 where
     Val: Field,
     Domain: ExtensionField<Val> + TwoAdicField,
-    Challenge: ExtensionField<Domain>,
+    Challenge: ExtensionField<Val> + ExtensionField<Domain>,
     PackedChallenge: PackedField<Scalar = Challenge> + AbstractExtensionField<Domain::Packing>,
-    PCS: for<'a> UnivariatePCS<Val, RowMajorMatrixView<'a, Val>>,
+    PCS: UnivariatePCS<Val, RowMajorMatrix<Val>>,
     LDE: TwoAdicCosetLDE<Val, Domain>,
 {
     type Val = Val;
@@ -76,3 +98,59 @@ where
         &self.lde
     }
 }
+
+/// A zero-knowledge variant of another config, which draws the values the prover blinds its
+/// polynomials with from `rng`. The inner config's PCS must be hiding.
+///
+/// The RNG is injected so that tests can use a seeded one.
+pub struct ZkStarkConfig<SC, R> {
+    inner: SC,
+    rng: RefCell<R>,
+}
+
+impl<SC, R> ZkStarkConfig<SC, R> {
+    pub fn new(inner: SC, rng: R) -> Self {
+        Self {
+            inner,
+            rng: RefCell::new(rng),
+        }
+    }
+}
+
+impl<SC, R> StarkConfig for ZkStarkConfig<SC, R>
+where
+    SC: StarkConfig,
+    R: Rng,
+    Standard: Distribution<SC::Val>,
+{
+    type Val = SC::Val;
+    type Domain = SC::Domain;
+    type Challenge = SC::Challenge;
+    type PackedChallenge = SC::PackedChallenge;
+    type PCS = SC::PCS;
+    type LDE = SC::LDE;
+
+    fn pcs(&self) -> &Self::PCS {
+        self.inner.pcs()
+    }
+
+    fn lde(&self) -> &Self::LDE {
+        self.inner.lde()
+    }
+
+    fn blinding(&self) -> Option<&dyn BlindingRandomness<Self::Val>> {
+        Some(self)
+    }
+}
+
+impl<SC, R> BlindingRandomness<SC::Val> for ZkStarkConfig<SC, R>
+where
+    SC: StarkConfig,
+    R: Rng,
+    Standard: Distribution<SC::Val>,
+{
+    fn random_vals(&self, len: usize) -> Vec<SC::Val> {
+        let mut rng = self.rng.borrow_mut();
+        (0..len).map(|_| rng.gen()).collect()
+    }
+}
@@ -0,0 +1,134 @@
+use alloc::vec::Vec;
+use p3_field::{ExtensionField, Field, TwoAdicField};
+use p3_util::{log2_strict_usize, reverse_slice_index_bits};
+
+/// Split the quotient polynomial `Q`, given by its evaluations over the coset `shift K` in natural
+/// order, into chunks `Q_i` of degree less than `n = 2^degree_bits`, such that
+///     Q(x) = sum_i x^(i n) Q_i(x).
+///
+/// Returns the evaluations of each `Q_i` over the subgroup `H` of order `n`, in natural order.
+pub fn decompose_quotient<Dom, Challenge>(
+    evals: Vec<Challenge>,
+    shift: Dom,
+    degree_bits: usize,
+) -> Vec<Vec<Challenge>>
+where
+    Dom: TwoAdicField,
+    Challenge: ExtensionField<Dom>,
+{
+    let subgroup_generator = Dom::primitive_root_of_unity(degree_bits);
+    chunk_coefficients(evals, shift, degree_bits)
+        .into_iter()
+        .map(|mut chunk| {
+            dft(&mut chunk, subgroup_generator);
+            chunk
+        })
+        .collect()
+}
+
+/// Like `decompose_quotient`, but blinds the chunks for zero knowledge. With random polynomials
+/// `r_i` of degree less than `n`, chunk `i` becomes
+///     Q_i(x) + x^n r_i(x) - r_{i-1}(x),
+/// where `r_{-1}` and the last `r_i` are zero. The blinding terms telescope, so the blinded chunks
+/// still recompose to `Q`.
+///
+/// Each blinded chunk has degree less than `2n`, so its evaluations are returned over the
+/// subgroup of order `2n` instead, in natural order.
+pub(crate) fn decompose_quotient_blinded<Dom, Challenge>(
+    evals: Vec<Challenge>,
+    shift: Dom,
+    degree_bits: usize,
+    mut random_value: impl FnMut() -> Challenge,
+) -> Vec<Vec<Challenge>>
+where
+    Dom: TwoAdicField,
+    Challenge: ExtensionField<Dom>,
+{
+    let chunks = chunk_coefficients(evals, shift, degree_bits);
+    let blinders: Vec<Vec<Challenge>> = (1..chunks.len())
+        .map(|_| (0..1 << degree_bits).map(|_| random_value()).collect())
+        .collect();
+
+    let generator = Dom::primitive_root_of_unity(degree_bits + 1);
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut chunk)| {
+            if let Some(prev_blinder) = i.checked_sub(1).map(|j| &blinders[j]) {
+                for (coeff, &r) in chunk.iter_mut().zip(prev_blinder) {
+                    *coeff -= r;
+                }
+            }
+            match blinders.get(i) {
+                Some(blinder) => chunk.extend_from_slice(blinder),
+                None => chunk.resize(2 << degree_bits, Challenge::ZERO),
+            }
+            dft(&mut chunk, generator);
+            chunk
+        })
+        .collect()
+}
+
+/// The coefficients of each chunk `Q_i` of the quotient, as described in `decompose_quotient`.
+fn chunk_coefficients<Dom, Challenge>(
+    mut evals: Vec<Challenge>,
+    shift: Dom,
+    degree_bits: usize,
+) -> Vec<Vec<Challenge>>
+where
+    Dom: TwoAdicField,
+    Challenge: ExtensionField<Dom>,
+{
+    let quotient_size_bits = log2_strict_usize(evals.len());
+
+    // Interpolating over K gives the coefficients of Q(shift x); undo the shift to get those of Q.
+    idft(&mut evals, Dom::primitive_root_of_unity(quotient_size_bits));
+    for (coeff, shift_inv_power) in evals.iter_mut().zip(shift.inverse().powers()) {
+        *coeff *= shift_inv_power;
+    }
+
+    evals
+        .chunks(1 << degree_bits)
+        .map(|coeffs| coeffs.to_vec())
+        .collect()
+}
+
+/// Evaluate a polynomial, given by its coefficients, over the subgroup generated by `root`, whose
+/// order must be `values.len()`. This is done in place, with a radix-2 Cooley-Tukey FFT.
+fn dft<Dom, Challenge>(values: &mut [Challenge], root: Dom)
+where
+    Dom: Field,
+    Challenge: ExtensionField<Dom>,
+{
+    let n = values.len();
+    let log_n = log2_strict_usize(n);
+    reverse_slice_index_bits(values);
+
+    for layer in 0..log_n {
+        let half_block = 1 << layer;
+        let block_root = root.exp_power_of_2(log_n - layer - 1);
+        let twiddles: Vec<Dom> = block_root.powers().take(half_block).collect();
+        for block in values.chunks_mut(2 * half_block) {
+            let (lo, hi) = block.split_at_mut(half_block);
+            for ((x, y), &twiddle) in lo.iter_mut().zip(hi).zip(&twiddles) {
+                let y_twiddled = *y * twiddle;
+                *y = *x - y_twiddled;
+                *x += y_twiddled;
+            }
+        }
+    }
+}
+
+/// The inverse of `dft`: interpolate the evaluations over the subgroup generated by `root`, in
+/// place.
+fn idft<Dom, Challenge>(values: &mut [Challenge], root: Dom)
+where
+    Dom: Field,
+    Challenge: ExtensionField<Dom>,
+{
+    dft(values, root.inverse());
+    let n_inv = Dom::from_canonical_usize(values.len()).inverse();
+    for value in values.iter_mut() {
+        *value *= n_inv;
+    }
+}
@@ -1,18 +1,29 @@
 use core::marker::PhantomData;
-use p3_air::{AirBuilder, TwoRowMatrixView};
-use p3_field::{AbstractExtensionField, ExtensionField, Field, PackedField};
+use p3_air::{
+    AirBuilder, AirBuilderWithPublicValues, PairBuilder, PermutationAirBuilder, TwoRowMatrixView,
+    WindowMatrixView,
+};
+use p3_field::{AbstractExtensionField, AbstractionOf, ExtensionField, Field, PackedField};
 
 pub struct ConstraintFolder<'a, F, Challenge, PackedChallenge>
 where
     F: Field,
 {
-    pub(crate) main: TwoRowMatrixView<'a, F::Packing>,
-    pub(crate) is_first_row: F::Packing,
-    pub(crate) is_last_row: F::Packing,
-    pub(crate) is_transition: F::Packing,
-    pub(crate) alpha: Challenge,
-    pub(crate) accumulator: PackedChallenge,
-    pub(crate) _phantom_f: PhantomData<F>,
+    pub preprocessed: WindowMatrixView<'a, F::Packing>,
+    pub main: WindowMatrixView<'a, F::Packing>,
+    pub permutation: TwoRowMatrixView<'a, PackedChallenge>,
+    pub permutation_challenges: &'a [Challenge],
+    pub public_values: &'a [F],
+    pub is_first_row: F::Packing,
+    pub is_last_row: F::Packing,
+    /// The point (or points) the constraints are being evaluated at.
+    pub x: F::Packing,
+    /// The points `g^-1, ..., g^-(w - 1)` of the trace domain, where `g` generates it and `w` is
+    /// the AIR's window size.
+    pub last_rows: &'a [F],
+    pub alpha: Challenge,
+    pub accumulator: PackedChallenge,
+    pub _phantom_f: PhantomData<F>,
 }
 
 impl<'a, F, Challenge, PackedChallenge> AirBuilder
@@ -25,7 +36,7 @@ where
     type F = F;
     type Expr = F::Packing;
     type Var = F::Packing;
-    type M = TwoRowMatrixView<'a, F::Packing>;
+    type M = WindowMatrixView<'a, F::Packing>;
 
     fn main(&self) -> Self::M {
         self.main
@@ -40,11 +51,7 @@ where
     }
 
     fn is_transition_window(&self, size: usize) -> Self::Expr {
-        if size == 2 {
-            self.is_transition
-        } else {
-            panic!("uni-stark only supports a window size of 2")
-        }
+        transition_selector(self.x, self.last_rows, size)
     }
 
     fn assert_zero<I: Into<Self::Expr>>(&mut self, x: I) {
@@ -53,3 +60,157 @@ where
         self.accumulator += x;
     }
 }
+
+impl<'a, F, Challenge, PackedChallenge> AirBuilderWithPublicValues
+    for ConstraintFolder<'a, F, Challenge, PackedChallenge>
+where
+    F: Field,
+    Challenge: ExtensionField<F>,
+    PackedChallenge: PackedField<Scalar = Challenge> + AbstractExtensionField<F::Packing>,
+{
+    fn public_values(&self) -> &[F] {
+        self.public_values
+    }
+}
+
+impl<'a, F, Challenge, PackedChallenge> PairBuilder
+    for ConstraintFolder<'a, F, Challenge, PackedChallenge>
+where
+    F: Field,
+    Challenge: ExtensionField<F>,
+    PackedChallenge: PackedField<Scalar = Challenge> + AbstractExtensionField<F::Packing>,
+{
+    fn preprocessed(&self) -> Self::M {
+        self.preprocessed
+    }
+}
+
+impl<'a, F, Challenge, PackedChallenge> PermutationAirBuilder
+    for ConstraintFolder<'a, F, Challenge, PackedChallenge>
+where
+    F: Field,
+    Challenge: ExtensionField<F>,
+    PackedChallenge: PackedField<Scalar = Challenge> + AbstractExtensionField<F::Packing>,
+{
+    type EF = Challenge;
+    type ExprEF = PackedChallenge;
+    type VarEF = PackedChallenge;
+    type MP = TwoRowMatrixView<'a, PackedChallenge>;
+
+    fn permutation(&self) -> Self::MP {
+        self.permutation
+    }
+
+    fn permutation_randomness(&self) -> &[Self::EF] {
+        self.permutation_challenges
+    }
+}
+
+/// An `AirBuilder` which evaluates constraints at a single out-of-domain point, folding them with
+/// powers of `alpha` just as `ConstraintFolder` does over the quotient domain.
+pub struct VerifierConstraintFolder<'a, F, Challenge> {
+    pub preprocessed: WindowMatrixView<'a, Challenge>,
+    pub main: WindowMatrixView<'a, Challenge>,
+    pub permutation: TwoRowMatrixView<'a, Challenge>,
+    pub permutation_challenges: &'a [Challenge],
+    pub public_values: &'a [F],
+    pub is_first_row: Challenge,
+    pub is_last_row: Challenge,
+    /// The point (or points) the constraints are being evaluated at.
+    pub x: Challenge,
+    /// The points `g^-1, ..., g^-(w - 1)` of the trace domain, where `g` generates it and `w` is
+    /// the AIR's window size.
+    pub last_rows: &'a [F],
+    pub alpha: Challenge,
+    pub accumulator: Challenge,
+    pub _phantom_f: PhantomData<F>,
+}
+
+impl<'a, F, Challenge> AirBuilder for VerifierConstraintFolder<'a, F, Challenge>
+where
+    F: Field,
+    Challenge: ExtensionField<F> + AbstractionOf<F>,
+{
+    type F = F;
+    type Expr = Challenge;
+    type Var = Challenge;
+    type M = WindowMatrixView<'a, Challenge>;
+
+    fn main(&self) -> Self::M {
+        self.main
+    }
+
+    fn is_first_row(&self) -> Self::Expr {
+        self.is_first_row
+    }
+
+    fn is_last_row(&self) -> Self::Expr {
+        self.is_last_row
+    }
+
+    fn is_transition_window(&self, size: usize) -> Self::Expr {
+        transition_selector(self.x, self.last_rows, size)
+    }
+
+    fn assert_zero<I: Into<Self::Expr>>(&mut self, x: I) {
+        let x: Challenge = x.into();
+        self.accumulator *= self.alpha;
+        self.accumulator += x;
+    }
+}
+
+impl<'a, F, Challenge> AirBuilderWithPublicValues for VerifierConstraintFolder<'a, F, Challenge>
+where
+    F: Field,
+    Challenge: ExtensionField<F> + AbstractionOf<F>,
+{
+    fn public_values(&self) -> &[F] {
+        self.public_values
+    }
+}
+
+impl<'a, F, Challenge> PairBuilder for VerifierConstraintFolder<'a, F, Challenge>
+where
+    F: Field,
+    Challenge: ExtensionField<F> + AbstractionOf<F>,
+{
+    fn preprocessed(&self) -> Self::M {
+        self.preprocessed
+    }
+}
+
+impl<'a, F, Challenge> PermutationAirBuilder for VerifierConstraintFolder<'a, F, Challenge>
+where
+    F: Field,
+    Challenge: ExtensionField<F> + AbstractionOf<F>,
+{
+    type EF = Challenge;
+    type ExprEF = Challenge;
+    type VarEF = Challenge;
+    type MP = TwoRowMatrixView<'a, Challenge>;
+
+    fn permutation(&self) -> Self::MP {
+        self.permutation
+    }
+
+    fn permutation_randomness(&self) -> &[Self::EF] {
+        self.permutation_challenges
+    }
+}
+
+/// The polynomial `(x - g^-1) ... (x - g^-(size - 1))`, which vanishes on the last `size - 1` rows of
+/// the trace domain, and nowhere else on it.
+fn transition_selector<F: Field, Expr: AbstractionOf<F>>(
+    x: Expr,
+    last_rows: &[F],
+    size: usize,
+) -> Expr {
+    assert!(
+        (1..=last_rows.len() + 1).contains(&size),
+        "a transition window can't be larger than the AIR's window"
+    );
+    last_rows[..size - 1]
+        .iter()
+        .map(|&last_row| x.clone() - last_row)
+        .product()
+}
@@ -0,0 +1,56 @@
+use crate::{Com, PcsProverData, StarkConfig, SymbolicAirBuilder};
+use p3_air::Air;
+use p3_commit::PCS;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::Matrix;
+use p3_util::log2_strict_usize;
+
+/// What the prover needs to know about an AIR's preprocessed trace, which is committed once by
+/// `setup` rather than in every proof.
+pub struct ProvingKey<SC: StarkConfig> {
+    pub verifying_key: VerifyingKey<SC>,
+    pub preprocessed_trace: RowMajorMatrix<SC::Val>,
+    pub preprocessed_data: PcsProverData<SC>,
+}
+
+/// What the verifier needs to know about an AIR's preprocessed trace.
+pub struct VerifyingKey<SC: StarkConfig> {
+    /// The log of the trace height. Traces proven with this key must have the same height as the
+    /// preprocessed trace.
+    pub degree_bits: usize,
+    pub preprocessed_width: usize,
+    pub preprocessed_commit: Com<SC>,
+}
+
+/// Commit to the preprocessed trace of `air`, returning keys which can be reused across proofs.
+///
+/// Panics if `air` has no preprocessed trace.
+pub fn setup<SC, A>(config: &SC, air: &A) -> (ProvingKey<SC>, VerifyingKey<SC>)
+where
+    SC: StarkConfig,
+    A: Air<SymbolicAirBuilder<SC::Val>>,
+    Com<SC>: Clone,
+{
+    let preprocessed_trace = <A as Air<SymbolicAirBuilder<SC::Val>>>::preprocessed_trace(air)
+        .expect("AIR has no preprocessed trace");
+    let degree_bits = log2_strict_usize(preprocessed_trace.height());
+    let preprocessed_width = preprocessed_trace.width();
+    let (preprocessed_commit, preprocessed_data) =
+        config.pcs().commit_batch(preprocessed_trace.clone());
+
+    let proving_key = ProvingKey {
+        verifying_key: VerifyingKey {
+            degree_bits,
+            preprocessed_width,
+            preprocessed_commit: preprocessed_commit.clone(),
+        },
+        preprocessed_trace,
+        preprocessed_data,
+    };
+    let verifying_key = VerifyingKey {
+        degree_bits,
+        preprocessed_width,
+        preprocessed_commit,
+    };
+    (proving_key, verifying_key)
+}
@@ -5,11 +5,23 @@
 extern crate alloc;
 
 mod config;
+mod decompose;
 mod folder;
+mod keys;
+mod proof;
 mod prover;
+mod quotient;
 mod sym_var;
+mod symbolic_builder;
+mod verifier;
 
 pub use config::*;
+pub use decompose::decompose_quotient;
 pub use folder::*;
+pub use keys::*;
+pub use proof::*;
 pub use prover::*;
+pub use quotient::*;
 pub use sym_var::*;
+pub use symbolic_builder::*;
+pub use verifier::*;
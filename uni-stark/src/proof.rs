@@ -0,0 +1,57 @@
+use crate::StarkConfig;
+use alloc::vec::Vec;
+use p3_commit::PCS;
+use p3_matrix::dense::RowMajorMatrix;
+
+/// The commitment type of a config's PCS.
+pub type Com<SC> = <<SC as StarkConfig>::PCS as PCS<
+    <SC as StarkConfig>::Val,
+    RowMajorMatrix<<SC as StarkConfig>::Val>,
+>>::Commitment;
+
+/// The data a config's PCS keeps for the prover, to help it open a commitment.
+pub type PcsProverData<SC> = <<SC as StarkConfig>::PCS as PCS<
+    <SC as StarkConfig>::Val,
+    RowMajorMatrix<<SC as StarkConfig>::Val>,
+>>::ProverData;
+
+/// The opening proof type of a config's PCS.
+pub type PcsProof<SC> = <<SC as StarkConfig>::PCS as PCS<
+    <SC as StarkConfig>::Val,
+    RowMajorMatrix<<SC as StarkConfig>::Val>,
+>>::Proof;
+
+/// The error type of a config's PCS.
+pub type PcsError<SC> = <<SC as StarkConfig>::PCS as PCS<
+    <SC as StarkConfig>::Val,
+    RowMajorMatrix<<SC as StarkConfig>::Val>,
+>>::Error;
+
+pub struct Proof<SC: StarkConfig> {
+    /// The log of the trace height.
+    pub degree_bits: usize,
+    pub trace_commit: Com<SC>,
+    /// A commitment to the LogUp permutation trace, if the AIR has any interactions.
+    pub permutation_commit: Option<Com<SC>>,
+    /// A commitment to the quotient chunks; see `OpenedValues::quotient_chunks`.
+    pub quotient_commit: Com<SC>,
+    pub opened_values: OpenedValues<SC::Challenge>,
+    pub opening_proof: PcsProof<SC>,
+}
+
+/// The values opened at the out-of-domain point `zeta`, and at its shifts `g^i zeta` for each
+/// further row `i` of the AIR's window, where `g` generates the trace domain. Each field holds the
+/// opened columns of one committed batch, point by point.
+pub struct OpenedValues<Challenge> {
+    /// Empty unless the AIR has a preprocessed trace.
+    pub preprocessed: Vec<Vec<Challenge>>,
+    pub trace: Vec<Vec<Challenge>>,
+    /// Empty unless the AIR has interactions. Like the quotient chunks, each extension field
+    /// column of the permutation trace is committed as `D` columns of base field coefficients.
+    pub permutation: Vec<Vec<Challenge>>,
+    /// The quotient `Q` is split as `Q(x) = sum_i x^(i n) Q_i(x)`, where `n` is the trace height,
+    /// and each chunk `Q_i` is committed as `D` columns holding its base field coefficients, chunk
+    /// by chunk. Only their evaluations at `zeta` are needed to check the constraints, but the PCS
+    /// opens every committed batch at every point.
+    pub quotient_chunks: Vec<Vec<Challenge>>,
+}
@@ -1,16 +1,17 @@
-use crate::{ConstraintFolder, StarkConfig};
+use crate::decompose::{decompose_quotient, decompose_quotient_blinded};
+use crate::{
+    get_log_quotient_degree, quotient_values, BlindingRandomness, Com, ConstraintFolder,
+    OpenedValues, Proof, ProvingKey, StarkConfig, SymbolicAirBuilder, TraceLdes,
+};
 use alloc::vec::Vec;
-use core::marker::PhantomData;
-use p3_air::{Air, TwoRowMatrixView};
+use itertools::Itertools;
+use p3_air::{generate_permutation_trace, Air, NUM_PERMUTATION_CHALLENGES};
 use p3_challenger::Challenger;
-use p3_commit::PCS;
-use p3_field::{
-    cyclic_subgroup_coset_known_order, AbstractField, Field, PackedField, TwoAdicField,
-};
+use p3_commit::{UnivariatePCS, PCS};
+use p3_field::{AbstractExtensionField, AbstractField, ExtensionField, Field, TwoAdicField};
 use p3_lde::{TwoAdicCosetLDE, TwoAdicLDE};
 use p3_matrix::dense::RowMajorMatrix;
-use p3_matrix::{Matrix, MatrixGet};
-use p3_maybe_rayon::{IndexedParallelIterator, MaybeIntoParIter, ParallelIterator};
+use p3_matrix::Matrix;
 use p3_util::log2_strict_usize;
 
 pub fn prove<SC, A, Chal>(
@@ -18,109 +19,276 @@ pub fn prove<SC, A, Chal>(
     config: &SC,
     challenger: &mut Chal,
     trace: RowMajorMatrix<SC::Val>,
-) where
+    public_values: &[SC::Val],
+) -> Proof<SC>
+where
+    SC: StarkConfig,
+    A: Air<SymbolicAirBuilder<SC::Domain>>
+        + for<'a> Air<ConstraintFolder<'a, SC::Domain, SC::Challenge, SC::PackedChallenge>>,
+    Chal: Challenger<SC::Val> + Clone + Sync,
+    Com<SC>: AsRef<[SC::Val]>,
+{
+    prove_internal(air, config, None, challenger, trace, public_values)
+}
+
+/// Like `prove`, but for an AIR with a preprocessed trace, which was committed by `setup`.
+pub fn prove_with_key<SC, A, Chal>(
+    air: &A,
+    config: &SC,
+    proving_key: &ProvingKey<SC>,
+    challenger: &mut Chal,
+    trace: RowMajorMatrix<SC::Val>,
+    public_values: &[SC::Val],
+) -> Proof<SC>
+where
+    SC: StarkConfig,
+    A: Air<SymbolicAirBuilder<SC::Domain>>
+        + for<'a> Air<ConstraintFolder<'a, SC::Domain, SC::Challenge, SC::PackedChallenge>>,
+    Chal: Challenger<SC::Val> + Clone + Sync,
+    Com<SC>: AsRef<[SC::Val]>,
+{
+    prove_internal(
+        air,
+        config,
+        Some(proving_key),
+        challenger,
+        trace,
+        public_values,
+    )
+}
+
+fn prove_internal<SC, A, Chal>(
+    air: &A,
+    config: &SC,
+    proving_key: Option<&ProvingKey<SC>>,
+    challenger: &mut Chal,
+    trace: RowMajorMatrix<SC::Val>,
+    public_values: &[SC::Val],
+) -> Proof<SC>
+where
     SC: StarkConfig,
-    A: for<'a> Air<ConstraintFolder<'a, SC::Domain, SC::Challenge, SC::PackedChallenge>>,
-    Chal: Challenger<SC::Domain>,
+    A: Air<SymbolicAirBuilder<SC::Domain>>
+        + for<'a> Air<ConstraintFolder<'a, SC::Domain, SC::Challenge, SC::PackedChallenge>>,
+    Chal: Challenger<SC::Val> + Clone + Sync,
+    Com<SC>: AsRef<[SC::Val]>,
 {
+    assert_eq!(
+        trace.width(),
+        <A as Air<SymbolicAirBuilder<SC::Domain>>>::width(air),
+        "the trace must have the AIR's width"
+    );
     let degree = trace.height();
     let degree_bits = log2_strict_usize(degree);
-    let quotient_degree_bits = 1; // TODO
-    let quotient_size_bits = degree_bits + quotient_degree_bits;
-    let quotient_size = 1 << quotient_size_bits;
-
+    if let Some(pk) = proving_key {
+        assert_eq!(
+            degree_bits, pk.verifying_key.degree_bits,
+            "the trace and preprocessed trace must have the same height"
+        );
+    }
+    let preprocessed_width = proving_key.map_or(0, |pk| pk.verifying_key.preprocessed_width);
+    let quotient_degree_bits = get_log_quotient_degree::<SC::Domain, A>(
+        air,
+        preprocessed_width,
+        trace.width(),
+        public_values.len(),
+        config.is_zk(),
+    );
+    let quotient_degree = 1 << quotient_degree_bits;
+    // In zero-knowledge mode, the trace, permutation and quotient polynomials are blinded up to
+    // twice the trace's degree, so they're committed over a subgroup twice as large.
+    let blinding = config.blinding();
+    assert!(
+        blinding.is_none() || config.pcs().is_hiding(),
+        "zero-knowledge mode requires a hiding PCS"
+    );
+    let blinded_bits = blinding.is_some() as usize;
+    let blind = |matrix: RowMajorMatrix<SC::Val>| match blinding {
+        Some(blinding) => interleave_random_rows(blinding, matrix),
+        None => matrix,
+    };
     let g_subgroup = SC::Domain::primitive_root_of_unity(degree_bits);
-    let g_extended = SC::Domain::primitive_root_of_unity(quotient_size_bits);
-    let subgroup_last = g_subgroup.inverse();
-    let next_step = 1 << quotient_degree_bits;
-
-    let coset_shift = config.lde().shift(quotient_size_bits);
-    let coset: Vec<_> =
-        cyclic_subgroup_coset_known_order(g_extended, coset_shift, quotient_size).collect();
-
-    // Evaluations of x^n on our coset s H. Note that
-    //     (s g^i)^n = s^n (g^n)^i,
-    // so this is the coset of <g^n> shifted by s^n.
-    let x_pow_n_evals = cyclic_subgroup_coset_known_order(
-        g_extended.exp_power_of_2(degree_bits),
-        coset_shift.exp_power_of_2(degree_bits),
-        quotient_size,
+    let window_size = <A as Air<SymbolicAirBuilder<SC::Domain>>>::window_size(air);
+    assert!(
+        window_size >= 2,
+        "an AIR's window must span at least two rows"
     );
+    let coset_shift = config.lde().shift(degree_bits + quotient_degree_bits);
 
-    // Evaluations of Z_H(x) = (x^n - 1) on our coset s H.
-    let zerofier_evals = x_pow_n_evals.map(|y| y - SC::Val::ONE);
-
-    // Evaluations of L_first(x) = Z_H(x) / (x - 1) on our coset s H.
-    let lagrange_first_evals: Vec<_> = g_subgroup
-        .powers()
-        .zip(zerofier_evals.clone())
-        .map(|(x, z)| z / (x - SC::Val::ONE))
-        .collect();
+    let sends = <A as Air<SymbolicAirBuilder<SC::Domain>>>::sends(air);
+    let receives = <A as Air<SymbolicAirBuilder<SC::Domain>>>::receives(air);
+    let has_permutation = !sends.is_empty() || !receives.is_empty();
+    let trace_dom = has_permutation.then(|| trace.map(SC::Domain::from_base));
+    let trace = blind(trace);
+    let trace_lde = config
+        .lde()
+        .lde_batch(trace.clone(), quotient_degree_bits - blinded_bits);
+    let preprocessed_lde = proving_key.map(|pk| {
+        config
+            .lde()
+            .lde_batch(pk.preprocessed_trace.clone(), quotient_degree_bits)
+    });
 
-    // Evaluations of L_last(x) = Z_H(x) / (x - g^-1) on our coset s H.
-    let lagrange_last_evals: Vec<_> = g_subgroup
-        .powers()
-        .zip(zerofier_evals)
-        .map(|(x, z)| z / (x - subgroup_last))
+    let public_values_dom: Vec<SC::Domain> = public_values
+        .iter()
+        .map(|&v| SC::Domain::from_base(v))
         .collect();
 
-    let trace_lde = config.lde().lde_batch(trace.clone(), quotient_degree_bits);
+    challenger.observe_element(SC::Val::from_canonical_usize(degree_bits));
+    if let Some(pk) = proving_key {
+        challenger.observe_elements(pk.verifying_key.preprocessed_commit.as_ref());
+    }
+    challenger.observe_elements(public_values);
+    let (trace_commit, trace_data) = config.pcs().commit_batch(trace);
+    challenger.observe_elements(trace_commit.as_ref());
 
-    let (_trace_commit, _trace_data) = config.pcs().commit_batch(trace.as_view());
+    // If the AIR has interactions, commit to its LogUp permutation trace in a second phase, once
+    // the randomness it depends on has been drawn.
+    let mut permutation_challenges = Vec::new();
+    let mut permutation_lde = None;
+    let mut permutation_commitment = None;
+    if let Some(trace_dom) = trace_dom {
+        permutation_challenges = (0..NUM_PERMUTATION_CHALLENGES)
+            .map(|_| challenger.random_ext_element::<SC::Challenge>())
+            .collect();
+        let preprocessed_dom =
+            proving_key.map(|pk| pk.preprocessed_trace.map(SC::Domain::from_base));
+        let permutation_trace = generate_permutation_trace(
+            &sends,
+            &receives,
+            preprocessed_dom.as_ref(),
+            &trace_dom,
+            &permutation_challenges,
+        );
+        let permutation_trace = blind(flatten_to_base::<SC::Val, SC::Challenge>(
+            &permutation_trace,
+        ));
+        let lde = config.lde().lde_batch(
+            permutation_trace.clone(),
+            quotient_degree_bits - blinded_bits,
+        );
+        let (permutation_commit, permutation_data) = config.pcs().commit_batch(permutation_trace);
+        challenger.observe_elements(permutation_commit.as_ref());
+        permutation_lde = Some(lde);
+        permutation_commitment = Some((permutation_commit, permutation_data));
+    }
 
-    // challenger.observe_ext_element(trace_commit); // TODO
     let alpha = challenger.random_ext_element::<SC::Challenge>();
+    let quotient_values =
+        quotient_values::<SC::Val, SC::Domain, SC::Challenge, SC::PackedChallenge, A>(
+            air,
+            degree_bits,
+            quotient_degree_bits,
+            coset_shift,
+            TraceLdes {
+                preprocessed: preprocessed_lde.as_ref(),
+                main: &trace_lde,
+                permutation: permutation_lde.as_ref(),
+            },
+            &public_values_dom,
+            &permutation_challenges,
+            // All interactions are within this one table, so they must balance.
+            SC::Challenge::ZERO,
+            alpha,
+        );
 
-    let _quotient_values = (0..quotient_size)
-        .into_par_iter()
-        .step_by(<SC::Val as Field>::Packing::WIDTH)
-        .flat_map_iter(|i_local_start| {
-            let wrap = |i| i % quotient_size;
-            let i_next_start = wrap(i_local_start + next_step);
-            let i_range = i_local_start..i_local_start + <SC::Val as Field>::Packing::WIDTH;
+    let challenge_d = <SC::Challenge as AbstractExtensionField<SC::Val>>::D;
+    let quotient_chunks = match blinding {
+        Some(blinding) => {
+            decompose_quotient_blinded(quotient_values, coset_shift, degree_bits, || {
+                SC::Challenge::from_base_slice(&blinding.random_vals(challenge_d))
+            })
+        }
+        None => decompose_quotient(quotient_values, coset_shift, degree_bits),
+    };
 
-            let x = *<SC::Domain as Field>::Packing::from_slice(&coset[i_range.clone()]);
-            let is_transition = x - subgroup_last;
-            let is_first_row =
-                *<SC::Domain as Field>::Packing::from_slice(&lagrange_first_evals[i_range.clone()]);
-            let is_last_row =
-                *<SC::Domain as Field>::Packing::from_slice(&lagrange_last_evals[i_range]);
+    // Commit to the chunks as a single matrix, flattening each value into its base field
+    // coefficients.
+    let quotient_chunk_values = (0..degree << blinded_bits)
+        .flat_map(|row| {
+            quotient_chunks.iter().flat_map(move |chunk| {
+                <SC::Challenge as AbstractExtensionField<SC::Val>>::as_base_slice(&chunk[row])
+                    .to_vec()
+            })
+        })
+        .collect();
+    let quotient_chunks_matrix =
+        RowMajorMatrix::new(quotient_chunk_values, quotient_degree * challenge_d);
+    let (quotient_commit, quotient_data) = config.pcs().commit_batch(quotient_chunks_matrix);
+    challenger.observe_elements(quotient_commit.as_ref());
 
-            let local: Vec<_> = (0..trace_lde.width())
-                .map(|col| {
-                    <SC::Domain as Field>::Packing::from_fn(|offset| {
-                        let row = wrap(i_local_start + offset);
-                        trace_lde.get(row, col)
-                    })
-                })
-                .collect();
-            let next: Vec<_> = (0..trace_lde.width())
-                .map(|col| {
-                    <SC::Domain as Field>::Packing::from_fn(|offset| {
-                        let row = wrap(i_next_start + offset);
-                        trace_lde.get(row, col)
-                    })
-                })
-                .collect();
+    let zeta = challenger.random_ext_element::<SC::Challenge>();
+    let prover_data = proving_key
+        .map(|pk| &pk.preprocessed_data)
+        .into_iter()
+        .chain([&trace_data])
+        .chain(permutation_commitment.as_ref().map(|(_, data)| data))
+        .chain([&quotient_data])
+        .collect_vec();
+    // Open every batch at zeta and its shifts by each further row of the window.
+    let opening_points = g_subgroup
+        .powers()
+        .take(window_size)
+        .map(|g_i| zeta * g_i)
+        .collect_vec();
+    let (opened_values, opening_proof) =
+        config
+            .pcs()
+            .open_multi_batches(&prover_data, &opening_points, challenger);
+    let mut opened_values = opened_values.into_iter();
+    let preprocessed = proving_key
+        .map(|_| opened_values.next().unwrap())
+        .unwrap_or_default();
+    let trace = opened_values.next().unwrap();
+    let permutation = permutation_commitment
+        .as_ref()
+        .map(|_| opened_values.next().unwrap())
+        .unwrap_or_default();
+    let quotient_chunks = opened_values.next().unwrap();
 
-            let accumulator = SC::PackedChallenge::ZEROS;
-            let mut builder = ConstraintFolder {
-                main: TwoRowMatrixView {
-                    local: &local,
-                    next: &next,
-                },
-                is_first_row,
-                is_last_row,
-                is_transition,
-                alpha,
-                accumulator,
-                _phantom_f: PhantomData,
-            };
-            air.eval(&mut builder);
+    Proof {
+        degree_bits,
+        trace_commit,
+        permutation_commit: permutation_commitment.map(|(commit, _)| commit),
+        quotient_commit,
+        opened_values: OpenedValues {
+            preprocessed,
+            trace,
+            permutation,
+            quotient_chunks,
+        },
+        opening_proof,
+    }
+}
 
-            // TODO: divide the constraints evaluations by `Z_H(x)`.
+/// Flatten a matrix of extension field values into a matrix of their base field coefficients,
+/// with `D` columns per original column.
+pub fn flatten_to_base<F: Field, EF: ExtensionField<F>>(
+    matrix: &RowMajorMatrix<EF>,
+) -> RowMajorMatrix<F> {
+    let values = matrix
+        .values
+        .iter()
+        .flat_map(|v| v.as_base_slice().to_vec())
+        .collect();
+    RowMajorMatrix::new(values, matrix.width() * EF::D)
+}
 
-            builder.accumulator.as_slice().to_vec()
-        })
-        .collect::<Vec<SC::Challenge>>();
+/// Blind each column of `matrix`, over the subgroup `H` of order `n`, by interleaving a random row
+/// after each of its rows.
+///
+/// Over the subgroup of order `2n`, which contains `H` as its even powers, the result is the
+/// evaluations of a polynomial of degree less than `2n` which agrees with the original on `H`.
+/// Equivalently, it adds a random multiple of the vanishing polynomial `Z_H` to the original.
+fn interleave_random_rows<F: Field>(
+    blinding: &dyn BlindingRandomness<F>,
+    matrix: RowMajorMatrix<F>,
+) -> RowMajorMatrix<F> {
+    let width = matrix.width();
+    let random_values = blinding.random_vals(matrix.values.len());
+    let values = matrix
+        .rows()
+        .zip(random_values.chunks(width))
+        .flat_map(|(row, random_row)| row.iter().chain(random_row).copied())
+        .collect();
+    RowMajorMatrix::new(values, width)
 }
@@ -0,0 +1,195 @@
+use crate::verifier::monomial;
+use crate::{ConstraintFolder, SymbolicAirBuilder};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use itertools::Itertools;
+use p3_air::{eval_permutation_constraints, Air, TwoRowMatrixView, WindowMatrixView};
+use p3_field::{
+    batch_multiplicative_inverse, cyclic_subgroup_coset_known_order, AbstractExtensionField,
+    ExtensionField, Field, PackedField, TwoAdicField,
+};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::{Matrix, MatrixGet};
+use p3_maybe_rayon::{IndexedParallelIterator, MaybeIntoParIter, ParallelIterator};
+
+/// The low-degree extensions of a table's traces onto its quotient domain.
+pub struct TraceLdes<'a, Domain> {
+    pub preprocessed: Option<&'a RowMajorMatrix<Domain>>,
+    pub main: &'a RowMajorMatrix<Domain>,
+    /// The LogUp permutation trace, if the AIR has interactions, with each extension field value
+    /// flattened into its base field coefficients, as by `flatten_to_base`.
+    pub permutation: Option<&'a RowMajorMatrix<Domain>>,
+}
+
+/// Evaluate an AIR's quotient polynomial over its quotient domain, the coset `coset_shift K` of
+/// the subgroup `K` of order `2^(degree_bits + quotient_degree_bits)`.
+///
+/// If the AIR has a permutation trace, its interactions are checked to sum to `cumulative_sum`.
+#[allow(clippy::too_many_arguments)]
+pub fn quotient_values<Val, Domain, Challenge, PackedChallenge, A>(
+    air: &A,
+    degree_bits: usize,
+    quotient_degree_bits: usize,
+    coset_shift: Domain,
+    ldes: TraceLdes<Domain>,
+    public_values: &[Domain],
+    permutation_challenges: &[Challenge],
+    cumulative_sum: Challenge,
+    alpha: Challenge,
+) -> Vec<Challenge>
+where
+    Val: Field,
+    Domain: ExtensionField<Val> + TwoAdicField,
+    Challenge: ExtensionField<Val> + ExtensionField<Domain>,
+    PackedChallenge:
+        PackedField<Scalar = Challenge> + AbstractExtensionField<<Domain as Field>::Packing>,
+    A: ?Sized
+        + Air<SymbolicAirBuilder<Domain>>
+        + for<'a> Air<ConstraintFolder<'a, Domain, Challenge, PackedChallenge>>,
+{
+    let sends = <A as Air<SymbolicAirBuilder<Domain>>>::sends(air);
+    let receives = <A as Air<SymbolicAirBuilder<Domain>>>::receives(air);
+    let window_size = <A as Air<SymbolicAirBuilder<Domain>>>::window_size(air);
+
+    let quotient_degree = 1 << quotient_degree_bits;
+    let quotient_size_bits = degree_bits + quotient_degree_bits;
+    let quotient_size = 1 << quotient_size_bits;
+
+    let g_subgroup = Domain::primitive_root_of_unity(degree_bits);
+    let g_extended = Domain::primitive_root_of_unity(quotient_size_bits);
+    let subgroup_last = g_subgroup.inverse();
+    let next_step = 1 << quotient_degree_bits;
+    // The points g^-1, ..., g^-(window_size - 1), for the transition selectors.
+    let last_rows = subgroup_last
+        .powers()
+        .skip(1)
+        .take(window_size - 1)
+        .collect_vec();
+
+    let coset: Vec<_> =
+        cyclic_subgroup_coset_known_order(g_extended, coset_shift, quotient_size).collect();
+
+    // Evaluations of x^n on our coset s K. Note that
+    //     (s g^i)^n = s^n (g^n)^i,
+    // so this is the coset of <g^n> shifted by s^n.
+    let x_pow_n_evals = cyclic_subgroup_coset_known_order(
+        g_extended.exp_power_of_2(degree_bits),
+        coset_shift.exp_power_of_2(degree_bits),
+        quotient_size,
+    );
+
+    // Evaluations of Z_H(x) = (x^n - 1) on our coset s K.
+    let zerofier_evals = x_pow_n_evals.map(|y| y - Domain::ONE);
+
+    // Since (g^n)^i only depends on i mod 2^quotient_degree_bits, so does Z_H(x_i).
+    let zerofier_invs =
+        batch_multiplicative_inverse(&zerofier_evals.clone().take(quotient_degree).collect_vec());
+
+    // Evaluations of L_first(x) = Z_H(x) / (x - 1) on our coset s K.
+    let lagrange_first_evals: Vec<_> = coset
+        .iter()
+        .zip(zerofier_evals.clone())
+        .map(|(&x, z)| z / (x - Domain::ONE))
+        .collect();
+
+    // Evaluations of L_last(x) = Z_H(x) / (x - g^-1) on our coset s K.
+    let lagrange_last_evals: Vec<_> = coset
+        .iter()
+        .zip(zerofier_evals)
+        .map(|(&x, z)| z / (x - subgroup_last))
+        .collect();
+
+    let challenge_d = <Challenge as AbstractExtensionField<Val>>::D;
+    let monomials = (0..challenge_d)
+        .map(monomial::<Val, Challenge>)
+        .collect_vec();
+
+    (0..quotient_size)
+        .into_par_iter()
+        .step_by(<Domain as Field>::Packing::WIDTH)
+        .flat_map_iter(|i_local_start| {
+            let wrap = |i| i % quotient_size;
+            let i_next_start = wrap(i_local_start + next_step);
+            let i_range = i_local_start..i_local_start + <Domain as Field>::Packing::WIDTH;
+
+            let x = *<Domain as Field>::Packing::from_slice(&coset[i_range.clone()]);
+            let is_first_row =
+                *<Domain as Field>::Packing::from_slice(&lagrange_first_evals[i_range.clone()]);
+            let is_last_row =
+                *<Domain as Field>::Packing::from_slice(&lagrange_last_evals[i_range]);
+
+            // The packed rows of `lde` starting at `i_start`, column by column.
+            let packed_rows = |lde: &RowMajorMatrix<Domain>, i_start: usize| -> Vec<_> {
+                (0..lde.width())
+                    .map(|col| {
+                        <Domain as Field>::Packing::from_fn(|offset| {
+                            lde.get(wrap(i_start + offset), col)
+                        })
+                    })
+                    .collect()
+            };
+            // The packed rows of `lde` in the window starting at `i_local_start`, row by row.
+            let packed_window = |lde: &RowMajorMatrix<Domain>| -> Vec<_> {
+                (0..window_size)
+                    .flat_map(|r| packed_rows(lde, wrap(i_local_start + r * next_step)))
+                    .collect()
+            };
+            let main = packed_window(ldes.main);
+            let preprocessed = ldes.preprocessed.map(packed_window).unwrap_or_default();
+            // Like `packed_rows`, but recombining each group of `D` base field columns into the
+            // extension field values they hold the coefficients of.
+            let packed_ext_rows = |lde: &RowMajorMatrix<Domain>, i_start: usize| -> Vec<_> {
+                packed_rows(lde, i_start)
+                    .chunks(challenge_d)
+                    .map(|coeffs| {
+                        coeffs
+                            .iter()
+                            .zip(&monomials)
+                            .map(|(&coeff, &monomial)| {
+                                <PackedChallenge as AbstractExtensionField<
+                                    <Domain as Field>::Packing,
+                                >>::from_base(coeff)
+                                    * monomial
+                            })
+                            .sum::<PackedChallenge>()
+                    })
+                    .collect()
+            };
+            let (permutation_local, permutation_next) = match ldes.permutation {
+                Some(lde) => (
+                    packed_ext_rows(lde, i_local_start),
+                    packed_ext_rows(lde, i_next_start),
+                ),
+                None => (Vec::new(), Vec::new()),
+            };
+
+            let mut builder = ConstraintFolder {
+                preprocessed: WindowMatrixView::new(&preprocessed, window_size),
+                main: WindowMatrixView::new(&main, window_size),
+                permutation: TwoRowMatrixView {
+                    local: &permutation_local,
+                    next: &permutation_next,
+                },
+                permutation_challenges,
+                public_values,
+                is_first_row,
+                is_last_row,
+                x,
+                last_rows: &last_rows,
+                alpha,
+                accumulator: PackedChallenge::ZEROS,
+                _phantom_f: PhantomData,
+            };
+            air.eval(&mut builder);
+            if ldes.permutation.is_some() {
+                eval_permutation_constraints(&sends, &receives, &mut builder, cumulative_sum);
+            }
+
+            let zerofier_inv = <Domain as Field>::Packing::from_fn(|offset| {
+                zerofier_invs[(i_local_start + offset) % quotient_degree]
+            });
+            let quotient = builder.accumulator * zerofier_inv;
+            quotient.as_slice().to_vec()
+        })
+        .collect()
+}
@@ -3,13 +3,42 @@ use core::ops::{Add, Mul, Sub};
 use p3_field::Field;
 use p3_field::SymbolicField;
 
+/// The value a `BasicSymVar` stands for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SymVarEntry {
+    /// A cell of the preprocessed trace, `row_offset` rows after the current row.
+    Preprocessed {
+        row_offset: usize,
+        column: usize,
+    },
+    /// A cell of the main trace, `row_offset` rows after the current row.
+    Main {
+        row_offset: usize,
+        column: usize,
+    },
+    IsFirstRow,
+    IsLastRow,
+    /// Nonzero on all rows but the last `window_size - 1`.
+    IsTransition {
+        window_size: usize,
+    },
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct BasicSymVar<F: Field> {
-    pub row_offset: usize,
-    pub column: usize,
+    pub entry: SymVarEntry,
     _phantom_f: PhantomData<F>,
 }
 
+impl<F: Field> BasicSymVar<F> {
+    pub fn new(entry: SymVarEntry) -> Self {
+        Self {
+            entry,
+            _phantom_f: PhantomData,
+        }
+    }
+}
+
 impl<F: Field> From<BasicSymVar<F>> for SymbolicField<F, BasicSymVar<F>> {
     fn from(value: BasicSymVar<F>) -> Self {
         SymbolicField::Variable(value)
@@ -0,0 +1,175 @@
+use crate::{BasicSymVar, SymVarEntry};
+use alloc::vec;
+use alloc::vec::Vec;
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, PairBuilder};
+use p3_field::{Field, SymbolicField};
+use p3_matrix::dense::RowMajorMatrix;
+use p3_util::log2_ceil_usize;
+
+/// Returns `log2(quotient_degree)`, where the quotient polynomial of `air`, over a trace of any
+/// height `n`, has degree less than `quotient_degree * n`.
+///
+/// In zero-knowledge mode, trace polynomials are blinded up to degree `2n`, which doubles the
+/// degree of each constraint.
+pub fn get_log_quotient_degree<F, A>(
+    air: &A,
+    preprocessed_width: usize,
+    width: usize,
+    num_public_values: usize,
+    is_zk: bool,
+) -> usize
+where
+    F: Field,
+    A: Air<SymbolicAirBuilder<F>> + ?Sized,
+{
+    // A constraint of degree d has a quotient of degree less than (d - 1) n. We use at least one
+    // chunk, even for constraints of degree 1. Blinded traces are committed over a domain of size
+    // 2n, so in that case the quotient domain must be at least that large.
+    let constraint_degree =
+        get_max_constraint_degree(air, preprocessed_width, width, num_public_values)
+            << is_zk as usize;
+    log2_ceil_usize(constraint_degree.saturating_sub(1).max(1)).max(is_zk as usize)
+}
+
+/// The largest degree of any of the constraints of `air`, in multiples of the trace height. This
+/// includes the LogUp constraints of its interactions, if any, which have degree 2 since their
+/// fields and counts are linear.
+pub fn get_max_constraint_degree<F, A>(
+    air: &A,
+    preprocessed_width: usize,
+    width: usize,
+    num_public_values: usize,
+) -> usize
+where
+    F: Field,
+    A: Air<SymbolicAirBuilder<F>> + ?Sized,
+{
+    let mut builder = SymbolicAirBuilder::new(
+        air.window_size(),
+        preprocessed_width,
+        width,
+        num_public_values,
+    );
+    air.eval(&mut builder);
+    let has_interactions = !air.sends().is_empty() || !air.receives().is_empty();
+    builder
+        .constraints()
+        .iter()
+        .map(degree_multiple)
+        .chain(has_interactions.then_some(2))
+        .max()
+        .unwrap_or(0)
+}
+
+/// The degree of `expr`, in multiples of the trace height. Trace cells and the row selectors all
+/// count as degree 1, as do preprocessed cells; `is_transition_window` is in fact of degree
+/// `window_size - 1`, far below the trace height, so this is an upper bound.
+fn degree_multiple<F: Field>(expr: &SymbolicField<F, BasicSymVar<F>>) -> usize {
+    match expr {
+        SymbolicField::Variable(_) => 1,
+        SymbolicField::Constant(_) => 0,
+        SymbolicField::Add(x, y) | SymbolicField::Sub(x, y) => {
+            degree_multiple(x).max(degree_multiple(y))
+        }
+        SymbolicField::Neg(x) => degree_multiple(x),
+        SymbolicField::Mul(x, y) => degree_multiple(x) + degree_multiple(y),
+    }
+}
+
+/// An `AirBuilder` which records each constraint as a `SymbolicField` expression, so that its
+/// structure, such as its degree, can be inspected.
+pub struct SymbolicAirBuilder<F: Field> {
+    window_size: usize,
+    /// The preprocessed window is built on demand, since most AIRs have no preprocessed columns,
+    /// and a `RowMajorMatrix` can't have width zero.
+    preprocessed_width: usize,
+    main: RowMajorMatrix<BasicSymVar<F>>,
+    /// Placeholders for the public values. They are constants, so their values don't affect the
+    /// structure of the constraints.
+    public_values: Vec<F>,
+    constraints: Vec<SymbolicField<F, BasicSymVar<F>>>,
+}
+
+impl<F: Field> SymbolicAirBuilder<F> {
+    pub fn new(
+        window_size: usize,
+        preprocessed_width: usize,
+        width: usize,
+        num_public_values: usize,
+    ) -> Self {
+        Self {
+            window_size,
+            preprocessed_width,
+            main: symbolic_window(window_size, width, |row_offset, column| SymVarEntry::Main {
+                row_offset,
+                column,
+            }),
+            public_values: vec![F::ZERO; num_public_values],
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn constraints(&self) -> &[SymbolicField<F, BasicSymVar<F>>] {
+        &self.constraints
+    }
+}
+
+impl<F: Field> AirBuilder for SymbolicAirBuilder<F> {
+    type F = F;
+    type Expr = SymbolicField<F, BasicSymVar<F>>;
+    type Var = BasicSymVar<F>;
+    type M = RowMajorMatrix<BasicSymVar<F>>;
+
+    fn main(&self) -> Self::M {
+        self.main.clone()
+    }
+
+    fn is_first_row(&self) -> Self::Expr {
+        BasicSymVar::new(SymVarEntry::IsFirstRow).into()
+    }
+
+    fn is_last_row(&self) -> Self::Expr {
+        BasicSymVar::new(SymVarEntry::IsLastRow).into()
+    }
+
+    fn is_transition_window(&self, size: usize) -> Self::Expr {
+        BasicSymVar::new(SymVarEntry::IsTransition { window_size: size }).into()
+    }
+
+    fn assert_zero<I: Into<Self::Expr>>(&mut self, x: I) {
+        self.constraints.push(x.into());
+    }
+}
+
+impl<F: Field> AirBuilderWithPublicValues for SymbolicAirBuilder<F> {
+    fn public_values(&self) -> &[F] {
+        &self.public_values
+    }
+}
+
+impl<F: Field> PairBuilder for SymbolicAirBuilder<F> {
+    fn preprocessed(&self) -> Self::M {
+        symbolic_window(
+            self.window_size,
+            self.preprocessed_width,
+            |row_offset, column| SymVarEntry::Preprocessed { row_offset, column },
+        )
+    }
+}
+
+/// A window of symbolic variables, with `entry(row_offset, column)` for each cell.
+fn symbolic_window<F, E>(
+    window_size: usize,
+    width: usize,
+    entry: E,
+) -> RowMajorMatrix<BasicSymVar<F>>
+where
+    F: Field,
+    E: Fn(usize, usize) -> SymVarEntry,
+{
+    let values = (0..window_size)
+        .flat_map(|row_offset| (0..width).map(move |column| (row_offset, column)))
+        .map(|(row_offset, column)| BasicSymVar::new(entry(row_offset, column)))
+        .collect();
+    RowMajorMatrix::new(values, width)
+}
@@ -0,0 +1,322 @@
+use crate::{
+    get_log_quotient_degree, Com, PcsError, Proof, StarkConfig, SymbolicAirBuilder,
+    VerifierConstraintFolder, VerifyingKey,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use itertools::Itertools;
+use p3_air::{
+    eval_permutation_constraints, permutation_trace_width, Air, TwoRowMatrixView, WindowMatrixView,
+    NUM_PERMUTATION_CHALLENGES,
+};
+use p3_challenger::Challenger;
+use p3_commit::{Dimensions, UnivariatePCS};
+use p3_field::{AbstractExtensionField, AbstractField, AbstractionOf, Field, TwoAdicField};
+
+pub fn verify<SC, A, Chal>(
+    air: &A,
+    config: &SC,
+    challenger: &mut Chal,
+    proof: &Proof<SC>,
+    public_values: &[SC::Val],
+) -> Result<(), VerificationError<PcsError<SC>>>
+where
+    SC: StarkConfig,
+    SC::Challenge: AbstractionOf<SC::Domain>,
+    A: Air<SymbolicAirBuilder<SC::Domain>>
+        + for<'a> Air<VerifierConstraintFolder<'a, SC::Domain, SC::Challenge>>,
+    Chal: Challenger<SC::Val>,
+    Com<SC>: AsRef<[SC::Val]> + Clone,
+{
+    verify_internal(air, config, None, challenger, proof, public_values)
+}
+
+/// Like `verify`, but for an AIR with a preprocessed trace, which was committed by `setup`.
+pub fn verify_with_key<SC, A, Chal>(
+    air: &A,
+    config: &SC,
+    verifying_key: &VerifyingKey<SC>,
+    challenger: &mut Chal,
+    proof: &Proof<SC>,
+    public_values: &[SC::Val],
+) -> Result<(), VerificationError<PcsError<SC>>>
+where
+    SC: StarkConfig,
+    SC::Challenge: AbstractionOf<SC::Domain>,
+    A: Air<SymbolicAirBuilder<SC::Domain>>
+        + for<'a> Air<VerifierConstraintFolder<'a, SC::Domain, SC::Challenge>>,
+    Chal: Challenger<SC::Val>,
+    Com<SC>: AsRef<[SC::Val]> + Clone,
+{
+    verify_internal(
+        air,
+        config,
+        Some(verifying_key),
+        challenger,
+        proof,
+        public_values,
+    )
+}
+
+fn verify_internal<SC, A, Chal>(
+    air: &A,
+    config: &SC,
+    verifying_key: Option<&VerifyingKey<SC>>,
+    challenger: &mut Chal,
+    proof: &Proof<SC>,
+    public_values: &[SC::Val],
+) -> Result<(), VerificationError<PcsError<SC>>>
+where
+    SC: StarkConfig,
+    SC::Challenge: AbstractionOf<SC::Domain>,
+    A: Air<SymbolicAirBuilder<SC::Domain>>
+        + for<'a> Air<VerifierConstraintFolder<'a, SC::Domain, SC::Challenge>>,
+    Chal: Challenger<SC::Val>,
+    Com<SC>: AsRef<[SC::Val]> + Clone,
+{
+    let Proof {
+        degree_bits,
+        trace_commit,
+        permutation_commit,
+        quotient_commit,
+        opened_values,
+        opening_proof,
+    } = proof;
+    let degree_bits = *degree_bits;
+    let trace_width = <A as Air<SymbolicAirBuilder<SC::Domain>>>::width(air);
+    let preprocessed_width = verifying_key.map_or(0, |vk| vk.preprocessed_width);
+    let quotient_degree_bits = get_log_quotient_degree::<SC::Domain, A>(
+        air,
+        preprocessed_width,
+        trace_width,
+        public_values.len(),
+        config.is_zk(),
+    );
+    let quotient_degree = 1 << quotient_degree_bits;
+    let challenge_d = <SC::Challenge as AbstractExtensionField<SC::Val>>::D;
+
+    let sends = <A as Air<SymbolicAirBuilder<SC::Domain>>>::sends(air);
+    let receives = <A as Air<SymbolicAirBuilder<SC::Domain>>>::receives(air);
+    let has_permutation = !sends.is_empty() || !receives.is_empty();
+    let permutation_width = if has_permutation {
+        permutation_trace_width(&sends, &receives) * challenge_d
+    } else {
+        0
+    };
+
+    let quotient_width = quotient_degree * challenge_d;
+    let window_size = <A as Air<SymbolicAirBuilder<SC::Domain>>>::window_size(air);
+    assert!(
+        window_size >= 2,
+        "an AIR's window must span at least two rows"
+    );
+
+    // A committed batch must be opened at each point of the window, with its full width at each,
+    // while an absent one has no openings at all.
+    let batch_shape_ok = |values: &[Vec<SC::Challenge>], present: bool, width: usize| {
+        if present {
+            values.len() == window_size && values.iter().all(|v| v.len() == width)
+        } else {
+            values.is_empty()
+        }
+    };
+    // The quotient domain, extended by the PCS's blowup, must fit in the two-adic subgroup. This
+    // also bounds the trace and quotient chunk domains, since the quotient degree is at least as
+    // large as any blinding.
+    let degree_bits_ok = SC::Domain::TWO_ADICITY
+        .checked_sub(config.pcs().log_blowup() + quotient_degree_bits)
+        .is_some_and(|max_degree_bits| degree_bits <= max_degree_bits);
+    let shape_ok = degree_bits_ok
+        && permutation_commit.is_some() == has_permutation
+        && verifying_key.is_none_or(|vk| vk.degree_bits == degree_bits)
+        && batch_shape_ok(
+            &opened_values.preprocessed,
+            verifying_key.is_some(),
+            preprocessed_width,
+        )
+        && batch_shape_ok(&opened_values.trace, true, trace_width)
+        && batch_shape_ok(
+            &opened_values.permutation,
+            has_permutation,
+            permutation_width,
+        )
+        && batch_shape_ok(&opened_values.quotient_chunks, true, quotient_width);
+    if !shape_ok {
+        return Err(VerificationError::InvalidProofShape);
+    }
+
+    let g_subgroup = SC::Domain::primitive_root_of_unity(degree_bits);
+    let subgroup_last = g_subgroup.inverse();
+    // The points g^-1, ..., g^-(window_size - 1), for the transition selectors.
+    let last_rows = subgroup_last
+        .powers()
+        .skip(1)
+        .take(window_size - 1)
+        .collect_vec();
+
+    challenger.observe_element(SC::Val::from_canonical_usize(degree_bits));
+    if let Some(vk) = verifying_key {
+        challenger.observe_elements(vk.preprocessed_commit.as_ref());
+    }
+    challenger.observe_elements(public_values);
+    challenger.observe_elements(trace_commit.as_ref());
+    let mut permutation_challenges = Vec::new();
+    if let Some(permutation_commit) = permutation_commit {
+        permutation_challenges = (0..NUM_PERMUTATION_CHALLENGES)
+            .map(|_| challenger.random_ext_element::<SC::Challenge>())
+            .collect();
+        challenger.observe_elements(permutation_commit.as_ref());
+    }
+    let alpha = challenger.random_ext_element::<SC::Challenge>();
+    challenger.observe_elements(quotient_commit.as_ref());
+    let zeta = challenger.random_ext_element::<SC::Challenge>();
+
+    // Each committed batch, with its width and its openings at each point of the window. The
+    // preprocessed batch, if any, comes first, matching the order in which the prover opens them.
+    let preprocessed_batch = verifying_key.map(|vk| {
+        (
+            vk.preprocessed_commit.clone(),
+            preprocessed_width,
+            opened_values.preprocessed.clone(),
+        )
+    });
+    let trace_batch = (
+        trace_commit.clone(),
+        trace_width,
+        opened_values.trace.clone(),
+    );
+    let permutation_batch = permutation_commit.as_ref().map(|commit| {
+        (
+            commit.clone(),
+            permutation_width,
+            opened_values.permutation.clone(),
+        )
+    });
+    let batches = preprocessed_batch
+        .into_iter()
+        .chain([trace_batch])
+        .chain(permutation_batch)
+        .chain([(
+            quotient_commit.clone(),
+            quotient_width,
+            opened_values.quotient_chunks.clone(),
+        )]);
+    let (commits, widths, values): (Vec<_>, Vec<_>, Vec<_>) = batches.multiunzip();
+    // In zero-knowledge mode, all but the preprocessed trace are blinded, and committed over a
+    // subgroup twice as large as the trace's.
+    let blinded_bits = config.is_zk() as usize;
+    let dims = widths
+        .into_iter()
+        .enumerate()
+        .map(|(i, width)| {
+            let is_preprocessed = verifying_key.is_some() && i == 0;
+            vec![Dimensions {
+                width,
+                log2_height: degree_bits + if is_preprocessed { 0 } else { blinded_bits },
+            }]
+        })
+        .collect::<Vec<_>>();
+    let opening_points = g_subgroup
+        .powers()
+        .take(window_size)
+        .map(|g_i| zeta * g_i)
+        .collect_vec();
+    config
+        .pcs()
+        .verify_multi_batches(
+            &commits,
+            &dims,
+            &opening_points,
+            &values,
+            opening_proof,
+            challenger,
+        )
+        .map_err(VerificationError::InvalidOpeningArgument)?;
+
+    // Recompose the quotient from its chunks: each chunk's columns hold its base field
+    // coefficients, and the chunks are combined as Q(x) = sum_i x^(i n) Q_i(x).
+    let zeta_pow_n = zeta.exp_power_of_2(degree_bits);
+    let quotient = opened_values.quotient_chunks[0]
+        .chunks(challenge_d)
+        .zip(zeta_pow_n.powers())
+        .map(|(chunk_coeffs, zeta_pow_in)| {
+            from_base_coefficients::<SC::Val, SC::Challenge>(chunk_coeffs) * zeta_pow_in
+        })
+        .sum::<SC::Challenge>();
+
+    // Likewise, recombine the permutation trace's extension field values from their coefficients.
+    // Its constraints only span two rows.
+    let recombine = |coeffs: &[SC::Challenge]| {
+        coeffs
+            .chunks(challenge_d)
+            .map(from_base_coefficients::<SC::Val, SC::Challenge>)
+            .collect_vec()
+    };
+    let (permutation_local, permutation_next) = match opened_values.permutation.as_slice() {
+        [local, next, ..] => (recombine(local), recombine(next)),
+        _ => (Vec::new(), Vec::new()),
+    };
+    let preprocessed = opened_values.preprocessed.concat();
+    let main = opened_values.trace.concat();
+
+    let public_values_dom: Vec<SC::Domain> = public_values
+        .iter()
+        .map(|&v| SC::Domain::from_base(v))
+        .collect();
+    let zerofier = zeta_pow_n - SC::Challenge::ONE;
+    let mut folder = VerifierConstraintFolder {
+        preprocessed: WindowMatrixView::new(&preprocessed, window_size),
+        main: WindowMatrixView::new(&main, window_size),
+        permutation: TwoRowMatrixView {
+            local: &permutation_local,
+            next: &permutation_next,
+        },
+        permutation_challenges: &permutation_challenges,
+        public_values: &public_values_dom,
+        is_first_row: zerofier / (zeta - SC::Challenge::ONE),
+        is_last_row: zerofier / (zeta - subgroup_last),
+        x: zeta,
+        last_rows: &last_rows,
+        alpha,
+        accumulator: SC::Challenge::ZERO,
+        _phantom_f: PhantomData,
+    };
+    air.eval(&mut folder);
+    if has_permutation {
+        eval_permutation_constraints(&sends, &receives, &mut folder, SC::Challenge::ZERO);
+    }
+
+    if folder.accumulator == quotient * zerofier {
+        Ok(())
+    } else {
+        Err(VerificationError::OodEvaluationMismatch)
+    }
+}
+
+#[derive(Debug)]
+pub enum VerificationError<PcsErr> {
+    InvalidProofShape,
+    /// The PCS rejected the claimed openings.
+    InvalidOpeningArgument(PcsErr),
+    /// The constraints, evaluated at the out-of-domain point `zeta`, don't match the quotient
+    /// times the zerofier.
+    OodEvaluationMismatch,
+}
+
+/// The `j`th monomial of the extension field `EF` over `F`, i.e. `X^j` in its polynomial basis.
+pub fn monomial<F: Field, EF: AbstractExtensionField<F>>(j: usize) -> EF {
+    let mut coeffs = vec![F::ZERO; EF::D];
+    coeffs[j] = F::ONE;
+    EF::from_base_slice(&coeffs)
+}
+
+/// The extension field element with the given coefficients, which are themselves extension field
+/// elements, such as the openings of a committed coefficient column.
+pub fn from_base_coefficients<F: Field, EF: AbstractExtensionField<F>>(coeffs: &[EF]) -> EF {
+    coeffs
+        .iter()
+        .enumerate()
+        .map(|(j, coeff)| coeff.clone() * monomial::<F, EF>(j))
+        .sum()
+}
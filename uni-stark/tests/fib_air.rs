@@ -0,0 +1,274 @@
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues};
+use p3_challenger::DuplexChallenger;
+use p3_field::{AbstractField, Field};
+use p3_fri::{FRIBasedPCS, FriConfig, FriLDT};
+use p3_goldilocks::Goldilocks;
+use p3_lde::NaiveCosetLDE;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::MatrixRows;
+use p3_merkle_tree::{HidingMerkleTreeMMCS, MerkleTreeMMCS};
+use p3_symmetric::compression::TruncatedPermutation;
+use p3_symmetric::mds::NaiveMDSMatrix;
+use p3_symmetric::sponge::PaddingFreeSponge;
+use p3_uni_stark::{prove, verify, StarkConfigImpl, ZkStarkConfig};
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+
+/// Computes a Fibonacci sequence. The public values are the two starting values, followed by the
+/// final value.
+struct FibonacciAir;
+
+impl<AB: AirBuilderWithPublicValues> Air<AB> for FibonacciAir {
+    fn width(&self) -> usize {
+        2
+    }
+
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row(0), main.row(1));
+        let &[a, b, result] = builder.public_values() else {
+            panic!("Expected three public values");
+        };
+
+        let mut when_first_row = builder.when_first_row();
+        when_first_row.assert_eq(local[0], a);
+        when_first_row.assert_eq(local[1], b);
+
+        let mut when_transition = builder.when_transition();
+        when_transition.assert_eq(next[0], local[1]);
+        when_transition.assert_eq(next[1], local[0] + local[1]);
+
+        builder.when_last_row().assert_eq(local[1], result);
+    }
+}
+
+/// The same sequence in a single column, so that each transition spans three rows. There are no
+/// public values; the sequence starts with 0, 1.
+struct WindowedFibonacciAir;
+
+impl<AB: AirBuilder> Air<AB> for WindowedFibonacciAir {
+    fn width(&self) -> usize {
+        1
+    }
+
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (x_0, x_1, x_2) = (main.row(0)[0], main.row(1)[0], main.row(2)[0]);
+
+        let mut when_first_row = builder.when_first_row();
+        when_first_row.assert_zero(x_0);
+        when_first_row.assert_one(x_1);
+
+        builder.when_transition_window(3).assert_eq(x_2, x_0 + x_1);
+    }
+
+    fn window_size(&self) -> usize {
+        3
+    }
+}
+
+/// Returns the trace, along with the final value.
+fn fibonacci_trace<F: Field>(a: u64, b: u64, rows: usize) -> (RowMajorMatrix<F>, F) {
+    let mut values = Vec::with_capacity(rows * 2);
+    let (mut x, mut y) = (F::from_canonical_u64(a), F::from_canonical_u64(b));
+    for _ in 0..rows {
+        values.extend([x, y]);
+        (x, y) = (y, x + y);
+    }
+    (RowMajorMatrix::new(values, 2), x)
+}
+
+type Val = Goldilocks;
+type Domain = Goldilocks;
+type Challenge = Goldilocks; // TODO
+type PackedChallenge = <Challenge as Field>::Packing;
+type Perm = NaiveMDSMatrix<Val, 8>; // TODO: Use a real permutation.
+type H4 = PaddingFreeSponge<Val, Perm, 8>;
+type C = TruncatedPermutation<Val, Perm, 2, 4, 8>;
+type Mmcs = MerkleTreeMMCS<Val, [Val; 4], H4, C, RowMajorMatrix<Val>>;
+type Ldt = FriLDT<Val, Challenge, Mmcs, Mmcs>;
+type Pcs = FRIBasedPCS<Val, Domain, Challenge, NaiveCosetLDE, Mmcs, Mmcs>;
+type MyConfig = StarkConfigImpl<Val, Domain, Challenge, PackedChallenge, Pcs, NaiveCosetLDE>;
+
+fn config(perm: &Perm) -> MyConfig {
+    let fri_config = FriConfig {
+        log_blowup: 1,
+        num_queries: 40,
+        proof_of_work_bits: 8,
+        log_folding_factor: 1,
+        log_final_poly_len: 0,
+    };
+    let mmcs = || Mmcs::new(H4::new(perm.clone()), C::new(perm.clone()), 2);
+    let ldt = Ldt::new(fri_config, mmcs());
+    let pcs = Pcs::new(NaiveCosetLDE, 1, mmcs(), ldt);
+    StarkConfigImpl::new(pcs, NaiveCosetLDE)
+}
+
+type HidingMmcs = HidingMerkleTreeMMCS<Val, [Val; 4], H4, C, StdRng, RowMajorMatrix<Val>, 4>;
+type HidingLdt = FriLDT<Val, Challenge, HidingMmcs, HidingMmcs>;
+type HidingPcs = FRIBasedPCS<Val, Domain, Challenge, NaiveCosetLDE, HidingMmcs, HidingMmcs>;
+type ZkConfig = ZkStarkConfig<
+    StarkConfigImpl<Val, Domain, Challenge, PackedChallenge, HidingPcs, NaiveCosetLDE>,
+    StdRng,
+>;
+
+fn zk_config(perm: &Perm, seed: u64) -> ZkConfig {
+    let fri_config = FriConfig {
+        log_blowup: 1,
+        num_queries: 40,
+        proof_of_work_bits: 8,
+        log_folding_factor: 1,
+        log_final_poly_len: 0,
+    };
+    let mmcs = |seed: u64| {
+        HidingMmcs::new(
+            H4::new(perm.clone()),
+            C::new(perm.clone()),
+            2,
+            StdRng::seed_from_u64(seed),
+        )
+    };
+    let ldt = HidingLdt::new(fri_config, mmcs(seed.wrapping_add(1)));
+    let pcs = HidingPcs::new(NaiveCosetLDE, 1, mmcs(seed.wrapping_add(2)), ldt);
+    ZkStarkConfig::new(
+        StarkConfigImpl::new(pcs, NaiveCosetLDE),
+        StdRng::seed_from_u64(seed),
+    )
+}
+
+#[test]
+fn test_public_values_goldilocks() {
+    let mut rng = thread_rng();
+    let perm = Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())));
+    let config = config(&perm);
+
+    let (trace, result) = fibonacci_trace::<Val>(0, 1, 1 << 5);
+    let public_values = [Val::ZERO, Val::ONE, result];
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove::<MyConfig, _, _>(
+        &FibonacciAir,
+        &config,
+        &mut challenger,
+        trace,
+        &public_values,
+    );
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    verify(
+        &FibonacciAir,
+        &config,
+        &mut challenger,
+        &proof,
+        &public_values,
+    )
+    .expect("verification failed");
+
+    // The proof doesn't hold for any other final value.
+    let wrong_public_values = [Val::ZERO, Val::ONE, result + Val::ONE];
+    let mut challenger = DuplexChallenger::new(perm);
+    assert!(verify(
+        &FibonacciAir,
+        &config,
+        &mut challenger,
+        &proof,
+        &wrong_public_values,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_window_goldilocks() {
+    let mut rng = thread_rng();
+    let perm = Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())));
+    let config = config(&perm);
+
+    // Keep only the first column, which holds the whole sequence.
+    let (pairs, _) = fibonacci_trace::<Val>(0, 1, 1 << 5);
+    let column: Vec<Val> = pairs.values.iter().step_by(2).copied().collect();
+    let trace = RowMajorMatrix::new(column.clone(), 1);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof =
+        prove::<MyConfig, _, _>(&WindowedFibonacciAir, &config, &mut challenger, trace, &[]);
+    // The trace is opened at each of the three rows of the window.
+    assert_eq!(proof.opened_values.trace.len(), 3);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    verify(&WindowedFibonacciAir, &config, &mut challenger, &proof, &[])
+        .expect("verification failed");
+
+    // Break the sequence partway through.
+    let mut column = column;
+    column[10] += Val::ONE;
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove::<MyConfig, _, _>(
+        &WindowedFibonacciAir,
+        &config,
+        &mut challenger,
+        RowMajorMatrix::new(column, 1),
+        &[],
+    );
+    let mut challenger = DuplexChallenger::new(perm);
+    assert!(verify(&WindowedFibonacciAir, &config, &mut challenger, &proof, &[]).is_err());
+}
+
+#[test]
+fn test_zk_goldilocks() {
+    let mut rng = thread_rng();
+    let perm = Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())));
+    let config = zk_config(&perm, rng.gen());
+
+    let (trace, result) = fibonacci_trace::<Val>(0, 1, 1 << 5);
+    let public_values = [Val::ZERO, Val::ONE, result];
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove::<ZkConfig, _, _>(
+        &FibonacciAir,
+        &config,
+        &mut challenger,
+        trace,
+        &public_values,
+    );
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    verify(
+        &FibonacciAir,
+        &config,
+        &mut challenger,
+        &proof,
+        &public_values,
+    )
+    .expect("verification failed");
+
+    let wrong_public_values = [Val::ZERO, Val::ONE, result + Val::ONE];
+    let mut challenger = DuplexChallenger::new(perm);
+    assert!(verify(
+        &FibonacciAir,
+        &config,
+        &mut challenger,
+        &proof,
+        &wrong_public_values,
+    )
+    .is_err());
+}
+
+#[test]
+#[should_panic(expected = "zero-knowledge mode requires a hiding PCS")]
+fn test_zk_requires_hiding_pcs() {
+    let mut rng = thread_rng();
+    let perm = Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())));
+    let config = ZkStarkConfig::new(config(&perm), StdRng::seed_from_u64(rng.gen()));
+
+    let (trace, result) = fibonacci_trace::<Val>(0, 1, 1 << 5);
+    let public_values = [Val::ZERO, Val::ONE, result];
+
+    let mut challenger = DuplexChallenger::new(perm);
+    prove(
+        &FibonacciAir,
+        &config,
+        &mut challenger,
+        trace,
+        &public_values,
+    );
+}
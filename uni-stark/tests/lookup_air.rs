@@ -0,0 +1,123 @@
+use p3_air::{Air, AirBuilder, Interaction, VirtualPairCol};
+use p3_challenger::DuplexChallenger;
+use p3_field::{AbstractField, Field};
+use p3_fri::{FRIBasedPCS, FriConfig, FriLDT};
+use p3_goldilocks::Goldilocks;
+use p3_lde::NaiveCosetLDE;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_merkle_tree::MerkleTreeMMCS;
+use p3_symmetric::compression::TruncatedPermutation;
+use p3_symmetric::mds::NaiveMDSMatrix;
+use p3_symmetric::sponge::PaddingFreeSponge;
+use p3_uni_stark::{prove, verify, StarkConfigImpl};
+use rand::{thread_rng, Rng};
+
+const VALUE: usize = 0;
+const TABLE: usize = 1;
+const MULTIPLICITY: usize = 2;
+
+/// An AIR which looks up each value in its first column in a table held in its second column. The
+/// third column holds the number of times each table entry is looked up.
+struct LookupAir;
+
+impl<AB: AirBuilder> Air<AB> for LookupAir {
+    fn width(&self) -> usize {
+        3
+    }
+
+    fn eval(&self, _builder: &mut AB) {
+        // All the constraints come from the interactions.
+    }
+
+    fn sends(&self) -> Vec<Interaction<AB::F>> {
+        vec![Interaction::new(
+            vec![VirtualPairCol::single_main(VALUE)],
+            VirtualPairCol::one(),
+            0,
+        )]
+    }
+
+    fn receives(&self) -> Vec<Interaction<AB::F>> {
+        vec![Interaction::new(
+            vec![VirtualPairCol::single_main(TABLE)],
+            VirtualPairCol::single_main(MULTIPLICITY),
+            0,
+        )]
+    }
+}
+
+/// A trace whose table is `0..rows`, with random lookups into it.
+fn random_valid_trace<F: Field>(rows: usize) -> RowMajorMatrix<F> {
+    let mut rng = thread_rng();
+    let values: Vec<usize> = (0..rows).map(|_| rng.gen_range(0..rows)).collect();
+    let mut multiplicities = vec![0; rows];
+    for &v in &values {
+        multiplicities[v] += 1;
+    }
+    let trace_values = (0..rows)
+        .flat_map(|i| [values[i], i, multiplicities[i]].map(F::from_canonical_usize))
+        .collect();
+    RowMajorMatrix::new(trace_values, 3)
+}
+
+type Val = Goldilocks;
+type Domain = Goldilocks;
+type Challenge = Goldilocks; // TODO
+type PackedChallenge = <Challenge as Field>::Packing;
+type Perm = NaiveMDSMatrix<Val, 8>; // TODO: Use a real permutation.
+type H4 = PaddingFreeSponge<Val, Perm, 8>;
+type C = TruncatedPermutation<Val, Perm, 2, 4, 8>;
+type Mmcs = MerkleTreeMMCS<Val, [Val; 4], H4, C, RowMajorMatrix<Val>>;
+type Ldt = FriLDT<Val, Challenge, Mmcs, Mmcs>;
+type Pcs = FRIBasedPCS<Val, Domain, Challenge, NaiveCosetLDE, Mmcs, Mmcs>;
+type MyConfig = StarkConfigImpl<Val, Domain, Challenge, PackedChallenge, Pcs, NaiveCosetLDE>;
+
+fn config(perm: &Perm) -> MyConfig {
+    let fri_config = FriConfig {
+        log_blowup: 1,
+        num_queries: 40,
+        proof_of_work_bits: 8,
+        log_folding_factor: 1,
+        log_final_poly_len: 0,
+    };
+    let mmcs = || Mmcs::new(H4::new(perm.clone()), C::new(perm.clone()), 2);
+    let ldt = Ldt::new(fri_config, mmcs());
+    let pcs = Pcs::new(NaiveCosetLDE, 1, mmcs(), ldt);
+    StarkConfigImpl::new(pcs, NaiveCosetLDE)
+}
+
+fn random_perm() -> Perm {
+    let mut rng = thread_rng();
+    Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())))
+}
+
+#[test]
+fn test_prove_lookup_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let trace = random_valid_trace::<Val>(1 << 5);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove::<MyConfig, _, _>(&LookupAir, &config, &mut challenger, trace, &[]);
+    assert!(proof.permutation_commit.is_some());
+    // One column per interaction, plus the running sum.
+    assert_eq!(proof.opened_values.permutation[0].len(), 3);
+
+    let mut challenger = DuplexChallenger::new(perm);
+    verify(&LookupAir, &config, &mut challenger, &proof, &[]).expect("verification failed");
+}
+
+#[test]
+fn test_reject_unbalanced_lookup_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let mut trace = random_valid_trace::<Val>(1 << 5);
+    // Look up a value which isn't in the table.
+    trace.row_mut(7)[VALUE] = Val::from_canonical_usize(1 << 5);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove::<MyConfig, _, _>(&LookupAir, &config, &mut challenger, trace, &[]);
+
+    let mut challenger = DuplexChallenger::new(perm);
+    assert!(verify(&LookupAir, &config, &mut challenger, &proof, &[]).is_err());
+}
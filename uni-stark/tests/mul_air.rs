@@ -1,72 +1,220 @@
+use itertools::Itertools;
 use p3_air::{Air, AirBuilder};
 use p3_challenger::DuplexChallenger;
-use p3_fri::FRIBasedPCS;
+use p3_field::{AbstractField, Field, TwoAdicField};
+use p3_fri::{FRIBasedPCS, FriConfig, FriLDT};
 use p3_goldilocks::Goldilocks;
 use p3_lde::NaiveCosetLDE;
 use p3_matrix::dense::RowMajorMatrix;
 use p3_matrix::MatrixRows;
 use p3_merkle_tree::MerkleTreeMMCS;
-use p3_poseidon::Poseidon;
 use p3_symmetric::compression::TruncatedPermutation;
-use p3_symmetric::mds::MDSPermutation;
-use p3_symmetric::permutation::{ArrayPermutation, CryptographicPermutation};
+use p3_symmetric::mds::NaiveMDSMatrix;
 use p3_symmetric::sponge::PaddingFreeSponge;
-use p3_uni_stark::{prove, StarkConfigImpl};
-use rand::thread_rng;
+use p3_uni_stark::{
+    get_log_quotient_degree, get_max_constraint_degree, prove, verify, Proof, StarkConfigImpl,
+    VerificationError,
+};
+use rand::distributions::{Distribution, Standard};
+use rand::{thread_rng, Rng};
 
-struct MulAir;
+/// How many `a^(degree - 1) * b = c` operations to do per row in the AIR.
+const REPETITIONS: usize = 3;
+const TRACE_WIDTH: usize = REPETITIONS * 3;
+
+/// An AIR whose constraints have the given degree.
+struct MulAir {
+    degree: u64,
+}
+
+impl Default for MulAir {
+    fn default() -> Self {
+        Self { degree: 2 }
+    }
+}
 
 impl<AB: AirBuilder> Air<AB> for MulAir {
+    fn width(&self) -> usize {
+        TRACE_WIDTH
+    }
+
     fn eval(&self, builder: &mut AB) {
         let main = builder.main();
         let main_local = main.row(0);
-        let diff = main_local[0] * main_local[1] - main_local[2];
-        builder.assert_zero(diff);
+        for i in 0..REPETITIONS {
+            let start = i * 3;
+            let a = main_local[start];
+            let b = main_local[start + 1];
+            let c = main_local[start + 2];
+            let mut product: AB::Expr = b.into();
+            for _ in 1..self.degree {
+                product = product * a;
+            }
+            builder.assert_eq(product, c);
+        }
+    }
+}
+
+impl MulAir {
+    fn random_valid_trace<F>(&self, rows: usize) -> RowMajorMatrix<F>
+    where
+        F: Field,
+        Standard: Distribution<F>,
+    {
+        let mut rng = thread_rng();
+        let mut trace_values = vec![F::ZERO; rows * TRACE_WIDTH];
+        for (a, b, c) in trace_values.iter_mut().tuples() {
+            *a = rng.gen();
+            *b = rng.gen();
+            *c = a.exp_u64(self.degree - 1) * *b;
+        }
+        RowMajorMatrix::new(trace_values, TRACE_WIDTH)
     }
 }
 
+type Val = Goldilocks;
+type Domain = Goldilocks;
+type Challenge = Goldilocks; // TODO
+type PackedChallenge = <Challenge as Field>::Packing;
+type Perm = NaiveMDSMatrix<Val, 8>; // TODO: Use a real permutation.
+type H4 = PaddingFreeSponge<Val, Perm, 8>;
+type C = TruncatedPermutation<Val, Perm, 2, 4, 8>;
+type Mmcs = MerkleTreeMMCS<Val, [Val; 4], H4, C, RowMajorMatrix<Val>>;
+type Ldt = FriLDT<Val, Challenge, Mmcs, Mmcs>;
+type Pcs = FRIBasedPCS<Val, Domain, Challenge, NaiveCosetLDE, Mmcs, Mmcs>;
+type MyConfig = StarkConfigImpl<Val, Domain, Challenge, PackedChallenge, Pcs, NaiveCosetLDE>;
+
+fn config(perm: &Perm) -> MyConfig {
+    let fri_config = FriConfig {
+        log_blowup: 1,
+        num_queries: 40,
+        proof_of_work_bits: 8,
+        log_folding_factor: 1,
+        log_final_poly_len: 0,
+    };
+    let mmcs = || Mmcs::new(H4::new(perm.clone()), C::new(perm.clone()), 2);
+    let ldt = Ldt::new(fri_config, mmcs());
+    let pcs = Pcs::new(NaiveCosetLDE, 1, mmcs(), ldt);
+    StarkConfigImpl::new(pcs, NaiveCosetLDE)
+}
+
+fn random_perm() -> Perm {
+    let mut rng = thread_rng();
+    Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())))
+}
+
 #[test]
-#[ignore] // TODO: Not ready yet.
-#[allow(clippy::items_after_statements)]
-#[allow(clippy::upper_case_acronyms)]
 fn test_prove_goldilocks() {
-    type Val = Goldilocks;
-    type Domain = Goldilocks;
-    type Challenge = Goldilocks; // TODO
-
-    #[derive(Clone)]
-    struct MyMds;
-    impl CryptographicPermutation<[Val; 8]> for MyMds {
-        fn permute(&self, input: [Val; 8]) -> [Val; 8] {
-            input // TODO
-        }
+    let perm = random_perm();
+    let config = config(&perm);
+    let air = MulAir::default();
+    let trace = air.random_valid_trace::<Val>(1 << 6);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove::<MyConfig, _, _>(&air, &config, &mut challenger, trace, &[]);
+    assert_eq!(proof.degree_bits, 6);
+    assert_eq!(proof.opened_values.trace[0].len(), TRACE_WIDTH);
+
+    let mut challenger = DuplexChallenger::new(perm);
+    verify(&air, &config, &mut challenger, &proof, &[]).expect("verification failed");
+}
+
+#[test]
+fn test_prove_goldilocks_high_degree() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let air = MulAir { degree: 5 };
+    let trace = air.random_valid_trace::<Val>(1 << 5);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove::<MyConfig, _, _>(&air, &config, &mut challenger, trace, &[]);
+    assert_eq!(proof.opened_values.quotient_chunks[0].len(), 4);
+
+    let mut challenger = DuplexChallenger::new(perm);
+    verify(&air, &config, &mut challenger, &proof, &[]).expect("verification failed");
+}
+
+#[test]
+fn test_quotient_degree() {
+    for (degree, log_quotient_degree) in [(1, 0), (2, 0), (3, 1), (4, 2), (5, 2), (6, 3)] {
+        let air = MulAir { degree };
+        assert_eq!(
+            get_max_constraint_degree::<Val, _>(&air, 0, TRACE_WIDTH, 0),
+            degree as usize
+        );
+        assert_eq!(
+            get_log_quotient_degree::<Val, _>(&air, 0, TRACE_WIDTH, 0, false),
+            log_quotient_degree
+        );
     }
-    impl ArrayPermutation<Val, 8> for MyMds {}
-    impl MDSPermutation<Val, 8> for MyMds {}
 
-    type MDS = MyMds;
-    let mds = MyMds;
+    // Blinding doubles the degree of the trace polynomials, and so of each constraint, and the
+    // quotient domain must be at least twice the trace's size.
+    for (degree, log_quotient_degree) in [(1, 1), (2, 2), (3, 3), (5, 4)] {
+        let air = MulAir { degree };
+        assert_eq!(
+            get_log_quotient_degree::<Val, _>(&air, 0, TRACE_WIDTH, 0, true),
+            log_quotient_degree
+        );
+    }
+}
 
-    type Perm = Poseidon<Val, MDS, 8, 7>;
-    let perm = Perm::new(5, 5, vec![], mds);
+#[test]
+fn test_reject_invalid_trace_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let air = MulAir::default();
+    let mut trace = air.random_valid_trace::<Val>(1 << 6);
+    trace.row_mut(5)[2] += Val::ONE;
 
-    type H4 = PaddingFreeSponge<Val, Perm, { 4 + 4 }>;
-    let h4 = H4::new(perm.clone());
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove::<MyConfig, _, _>(&air, &config, &mut challenger, trace, &[]);
 
-    type C = TruncatedPermutation<Val, Perm, 2, 4, { 2 * 4 }>;
-    let c = C::new(perm.clone());
+    let mut challenger = DuplexChallenger::new(perm);
+    assert!(verify(&air, &config, &mut challenger, &proof, &[]).is_err());
+}
+
+#[test]
+fn test_reject_tampered_opening_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let air = MulAir::default();
+    let trace = air.random_valid_trace::<Val>(1 << 6);
 
-    type MMCS = MerkleTreeMMCS<Val, [Val; 4], H4, C, RowMajorMatrix<Val>>;
-    type LDE = NaiveCosetLDE;
-    type PCS = FRIBasedPCS<Val, Domain, Challenge, LDE, MMCS, MMCS>;
-    type MyConfig = StarkConfigImpl<Val, Domain, Challenge, Challenge, PCS, LDE>;
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let mut proof = prove::<MyConfig, _, _>(&air, &config, &mut challenger, trace, &[]);
+    proof.opened_values.trace[0][0] += Val::ONE;
 
-    let mut rng = thread_rng();
-    let trace = RowMajorMatrix::rand(&mut rng, 256, 10);
-    let pcs = PCS::new(NaiveCosetLDE, 1, MMCS::new(h4, c));
-    let config = StarkConfigImpl::new(pcs, NaiveCosetLDE);
     let mut challenger = DuplexChallenger::new(perm);
-    prove::<MyConfig, _, _>(&MulAir, &config, &mut challenger, trace);
+    assert!(verify(&air, &config, &mut challenger, &proof, &[]).is_err());
+}
+
+#[test]
+fn test_reject_malformed_proof_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let air = MulAir::default();
+    let trace = air.random_valid_trace::<Val>(1 << 6);
+
+    // Each of these would otherwise make the verifier index out of bounds, or ask for a root of
+    // unity of an order the field doesn't have.
+    let tampers: [fn(&mut Proof<MyConfig>); 4] = [
+        |proof| proof.degree_bits = Val::TWO_ADICITY,
+        |proof| proof.degree_bits = usize::MAX,
+        |proof| proof.opened_values.trace.clear(),
+        |proof| {
+            proof.opened_values.trace[1].pop();
+        },
+    ];
+    for tamper in tampers {
+        let mut challenger = DuplexChallenger::new(perm.clone());
+        let mut proof = prove::<MyConfig, _, _>(&air, &config, &mut challenger, trace.clone(), &[]);
+        tamper(&mut proof);
+
+        let mut challenger = DuplexChallenger::new(perm.clone());
+        let result = verify(&air, &config, &mut challenger, &proof, &[]);
+        assert!(matches!(result, Err(VerificationError::InvalidProofShape)));
+    }
 }
 
 #[test]
@@ -0,0 +1,156 @@
+use p3_air::{Air, PairBuilder};
+use p3_challenger::DuplexChallenger;
+use p3_field::{AbstractField, Field};
+use p3_fri::{FRIBasedPCS, FriConfig, FriLDT};
+use p3_goldilocks::Goldilocks;
+use p3_lde::NaiveCosetLDE;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::MatrixRows;
+use p3_merkle_tree::MerkleTreeMMCS;
+use p3_symmetric::compression::TruncatedPermutation;
+use p3_symmetric::mds::NaiveMDSMatrix;
+use p3_symmetric::sponge::PaddingFreeSponge;
+use p3_uni_stark::{prove_with_key, setup, verify_with_key, StarkConfigImpl};
+use rand::{thread_rng, Rng};
+
+/// An AIR whose main column holds the squares of a fixed table, which is stored in a preprocessed
+/// column.
+struct SquaresAir {
+    table: Vec<u64>,
+}
+
+impl<AB: PairBuilder> Air<AB> for SquaresAir {
+    fn width(&self) -> usize {
+        1
+    }
+
+    fn eval(&self, builder: &mut AB) {
+        let preprocessed = builder.preprocessed();
+        let main = builder.main();
+        let x = preprocessed.row(0)[0];
+        let y = main.row(0)[0];
+        builder.assert_eq(x * x, y);
+    }
+
+    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<AB::F>> {
+        let values = self
+            .table
+            .iter()
+            .map(|&x| AB::F::from_canonical_u64(x))
+            .collect();
+        Some(RowMajorMatrix::new(values, 1))
+    }
+}
+
+impl SquaresAir {
+    fn random(rows: usize) -> Self {
+        let mut rng = thread_rng();
+        let table = (0..rows).map(|_| rng.gen::<u32>() as u64).collect();
+        Self { table }
+    }
+
+    fn valid_trace<F: Field>(&self) -> RowMajorMatrix<F> {
+        let values = self
+            .table
+            .iter()
+            .map(|&x| F::from_canonical_u64(x).square())
+            .collect();
+        RowMajorMatrix::new(values, 1)
+    }
+}
+
+type Val = Goldilocks;
+type Domain = Goldilocks;
+type Challenge = Goldilocks; // TODO
+type PackedChallenge = <Challenge as Field>::Packing;
+type Perm = NaiveMDSMatrix<Val, 8>; // TODO: Use a real permutation.
+type H4 = PaddingFreeSponge<Val, Perm, 8>;
+type C = TruncatedPermutation<Val, Perm, 2, 4, 8>;
+type Mmcs = MerkleTreeMMCS<Val, [Val; 4], H4, C, RowMajorMatrix<Val>>;
+type Ldt = FriLDT<Val, Challenge, Mmcs, Mmcs>;
+type Pcs = FRIBasedPCS<Val, Domain, Challenge, NaiveCosetLDE, Mmcs, Mmcs>;
+type MyConfig = StarkConfigImpl<Val, Domain, Challenge, PackedChallenge, Pcs, NaiveCosetLDE>;
+
+fn config(perm: &Perm) -> MyConfig {
+    let fri_config = FriConfig {
+        log_blowup: 1,
+        num_queries: 40,
+        proof_of_work_bits: 8,
+        log_folding_factor: 1,
+        log_final_poly_len: 0,
+    };
+    let mmcs = || Mmcs::new(H4::new(perm.clone()), C::new(perm.clone()), 2);
+    let ldt = Ldt::new(fri_config, mmcs());
+    let pcs = Pcs::new(NaiveCosetLDE, 1, mmcs(), ldt);
+    StarkConfigImpl::new(pcs, NaiveCosetLDE)
+}
+
+fn random_perm() -> Perm {
+    let mut rng = thread_rng();
+    Perm::new([(); 8].map(|_| [(); 8].map(|_| rng.gen())))
+}
+
+#[test]
+fn test_prove_preprocessed_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let air = SquaresAir::random(1 << 5);
+    let (pk, vk) = setup(&config, &air);
+
+    // The same keys can be used for several proofs.
+    for _ in 0..2 {
+        let mut challenger = DuplexChallenger::new(perm.clone());
+        let proof = prove_with_key::<MyConfig, _, _>(
+            &air,
+            &config,
+            &pk,
+            &mut challenger,
+            air.valid_trace(),
+            &[],
+        );
+        assert_eq!(proof.opened_values.preprocessed[0].len(), 1);
+
+        let mut challenger = DuplexChallenger::new(perm.clone());
+        verify_with_key(&air, &config, &vk, &mut challenger, &proof, &[])
+            .expect("verification failed");
+    }
+}
+
+#[test]
+fn test_reject_invalid_trace_preprocessed_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let air = SquaresAir::random(1 << 5);
+    let (pk, vk) = setup(&config, &air);
+
+    let mut trace = air.valid_trace::<Val>();
+    trace.row_mut(3)[0] += Val::ONE;
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove_with_key::<MyConfig, _, _>(&air, &config, &pk, &mut challenger, trace, &[]);
+
+    let mut challenger = DuplexChallenger::new(perm);
+    assert!(verify_with_key(&air, &config, &vk, &mut challenger, &proof, &[]).is_err());
+}
+
+#[test]
+fn test_reject_wrong_verifying_key_goldilocks() {
+    let perm = random_perm();
+    let config = config(&perm);
+    let air = SquaresAir::random(1 << 5);
+    let (pk, _) = setup(&config, &air);
+    let other_air = SquaresAir::random(1 << 5);
+    let (_, other_vk) = setup(&config, &other_air);
+
+    let mut challenger = DuplexChallenger::new(perm.clone());
+    let proof = prove_with_key::<MyConfig, _, _>(
+        &air,
+        &config,
+        &pk,
+        &mut challenger,
+        air.valid_trace(),
+        &[],
+    );
+
+    let mut challenger = DuplexChallenger::new(perm);
+    assert!(verify_with_key(&air, &config, &other_vk, &mut challenger, &proof, &[]).is_err());
+}
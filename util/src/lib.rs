@@ -31,6 +31,36 @@ pub fn log2_strict_usize(n: usize) -> usize {
     res as usize
 }
 
+/// Reverses the low `bit_len` bits of `x`, discarding any higher bits.
+#[must_use]
+pub const fn reverse_bits_len(x: usize, bit_len: usize) -> usize {
+    // `checked_shr` handles the `bit_len == 0` case, where a plain shift by `usize::BITS` would
+    // overflow.
+    match x.reverse_bits().checked_shr(usize::BITS - bit_len as u32) {
+        Some(y) => y,
+        None => 0,
+    }
+}
+
+/// Permutes `vals` such that the element at index `i` moves to index `reverse_bits_len(i, log_n)`,
+/// where `n = 2^log_n` is the length of `vals`.
+///
+/// # Panics
+/// Panics if the length of `vals` is not a power of two.
+pub fn reverse_slice_index_bits<T>(vals: &mut [T]) {
+    let n = vals.len();
+    if n == 0 {
+        return;
+    }
+    let log_n = log2_strict_usize(n);
+    for i in 0..n {
+        let j = reverse_bits_len(i, log_n);
+        if i < j {
+            vals.swap(i, j);
+        }
+    }
+}
+
 #[inline(always)]
 pub fn assume(p: bool) {
     debug_assert!(p);